@@ -40,7 +40,7 @@ impl Decodable for OriginKeyIdent {
 
 impl Decodable for Package {
     fn decode<D: Decoder>(d: &mut D) -> result::Result<Self, D::Error> {
-        d.read_struct("Package", 7, |d| {
+        d.read_struct("Package", 13, |d| {
             let mut package = Package::new();
             package.set_ident(try!(d.read_struct_field("ident", 0, |d| Decodable::decode(d))));
             package.set_checksum(try!(d.read_struct_field("checksum", 1, |d| Decodable::decode(d))));
@@ -53,6 +53,24 @@ impl Decodable for Package {
             if let Some(cfg) = try!(d.read_struct_field("config", 6, |d| Ok(Decodable::decode(d).ok()))) {
                 package.set_config(cfg);
             }
+            if let Some(licenses) = try!(d.read_struct_field("licenses", 7, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_licenses(protobuf::RepeatedField::from_vec(licenses));
+            }
+            if let Some(v) = try!(d.read_struct_field("source", 8, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_source(v);
+            }
+            if let Some(v) = try!(d.read_struct_field("shasum", 9, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_shasum(v);
+            }
+            if let Some(v) = try!(d.read_struct_field("build_host", 10, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_build_host(v);
+            }
+            if let Some(v) = try!(d.read_struct_field("builder_version", 11, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_builder_version(v);
+            }
+            if let Some(v) = try!(d.read_struct_field("plan_commit", 12, |d| Ok(Decodable::decode(d).ok()))) {
+                package.set_plan_commit(v);
+            }
             Ok(package)
         })
     }
@@ -168,6 +186,12 @@ impl FromArchive for Package {
         let exposes = try!(archive.exposes()).into_iter().map(|d| d as u32).collect();
         let config = try!(archive.config());
         let checksum = try!(archive.checksum());
+        let licenses = try!(archive.licenses());
+        let source = try!(archive.source());
+        let shasum = try!(archive.shasum());
+        let build_host = try!(archive.build_host());
+        let builder_version = try!(archive.builder_version());
+        let plan_commit = try!(archive.plan_commit());
 
         let mut package = Package::new();
         package.set_ident(ident);
@@ -179,6 +203,22 @@ impl FromArchive for Package {
             package.set_config(cfg);
         }
         package.set_checksum(checksum);
+        package.set_licenses(protobuf::RepeatedField::from_vec(licenses));
+        if let Some(v) = source {
+            package.set_source(v);
+        }
+        if let Some(v) = shasum {
+            package.set_shasum(v);
+        }
+        if let Some(v) = build_host {
+            package.set_build_host(v);
+        }
+        if let Some(v) = builder_version {
+            package.set_builder_version(v);
+        }
+        if let Some(v) = plan_commit {
+            package.set_plan_commit(v);
+        }
         Ok(package)
     }
 }
@@ -291,6 +331,22 @@ impl ToJson for Package {
         m.insert("tdeps".to_string(), self.get_tdeps().to_vec().to_json());
         m.insert("exposes".to_string(), self.get_exposes().to_json());
         m.insert("config".to_string(), self.get_config().to_json());
+        m.insert("licenses".to_string(), self.get_licenses().to_vec().to_json());
+        if self.has_source() {
+            m.insert("source".to_string(), self.get_source().to_json());
+        }
+        if self.has_shasum() {
+            m.insert("shasum".to_string(), self.get_shasum().to_json());
+        }
+        if self.has_build_host() {
+            m.insert("build_host".to_string(), self.get_build_host().to_json());
+        }
+        if self.has_builder_version() {
+            m.insert("builder_version".to_string(), self.get_builder_version().to_json());
+        }
+        if self.has_plan_commit() {
+            m.insert("plan_commit".to_string(), self.get_plan_commit().to_json());
+        }
         Json::Object(m)
     }
 }
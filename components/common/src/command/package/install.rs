@@ -28,26 +28,35 @@
 //!
 //! This would install the `3.0.1` version of redis.
 //!
+//! ```bash
+//! $ hab pkg install 'core/redis >= 3.0, < 3.2'
+//! ```
+//!
+//! This would install the newest redis release from `3.0` up to, but not including, `3.2`.
+//!
 //! # Internals
 //!
 //! * Download the artifact
-//! * Verify it is un-altered
-//! * Unpack it
+//! * Unpack it into a staging directory, hashing it on the way
+//! * Verify it is un-altered, and only then move it into place
 //!
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use ansi_term::Colour::{Blue, Green, Yellow};
-use depot_client::Client;
+use depot_client::{self, Client};
 use hcore::crypto::{artifact, SigKeyPair};
+use hcore::crypto::artifact::SignaturePolicy;
 use hcore::crypto::keys::parse_name_with_rev;
 use hcore::fs::cache_artifact_path;
-use hcore::package::{Identifiable, PackageArchive, PackageIdent, PackageInstall};
+use hcore::package::{Identifiable, PackageArchive, PackageIdent, PackageIdentReq,
+                     PackageInstall};
 use protocol::depotsrv;
 
 use command::ProgressBar;
-use error::Result;
+use command::package::resolve;
+use error::{Error, Result};
 
 pub fn start<P1: ?Sized, P2: ?Sized, P3: ?Sized>(url: &str,
                                                  ident_or_archive: &str,
@@ -66,7 +75,12 @@ pub fn start<P1: ?Sized, P2: ?Sized, P3: ?Sized>(url: &str,
                           cache_artifact_path,
                           cache_key_path));
     } else {
-        let ident = try!(PackageIdent::from_str(ident_or_archive));
+        let ident = if resolve::is_req(ident_or_archive) {
+            let req = try!(PackageIdentReq::from_str(ident_or_archive));
+            try!(resolve::newest(url, &req, fs_root_path))
+        } else {
+            try!(PackageIdent::from_str(ident_or_archive))
+        };
         try!(from_url(url,
                       &ident,
                       fs_root_path,
@@ -181,9 +195,8 @@ fn install_from_depot(url: &str,
             let mut archive = try!(depot_client.fetch_package((*ident).clone(),
                                                                cache_artifact_path,
                                                                Some(&mut progress)));
+            try!(verify_and_unpack(url, &mut archive, fs_root_path, cache_key_path));
             let ident = try!(archive.ident());
-            try!(verify(url, &archive, &ident, fs_root_path, cache_key_path));
-            try!(archive.unpack(Some(fs_root_path)));
             println!("{} {}", Green.bold().paint("✓ Installed"), ident.as_ref());
         }
     }
@@ -191,7 +204,7 @@ fn install_from_depot(url: &str,
 }
 
 fn install_from_archive(url: &str,
-                        archive: PackageArchive,
+                        mut archive: PackageArchive,
                         ident: &PackageIdent,
                         fs_root_path: &Path,
                         cache_key_path: &Path)
@@ -204,8 +217,7 @@ fn install_from_archive(url: &str,
             println!("{} {} from cache",
                      Green.bold().paint("← Extracting"),
                      ident);
-            try!(verify(url, &archive, &ident, fs_root_path, cache_key_path));
-            try!(archive.unpack(Some(fs_root_path)));
+            try!(verify_and_unpack(url, &mut archive, fs_root_path, cache_key_path));
             println!("{} {}", Green.bold().paint("✓ Installed"), ident);
         }
     }
@@ -213,28 +225,40 @@ fn install_from_archive(url: &str,
 }
 
 /// get the signer for the artifact and see if we have the key locally.
-/// If we don't, attempt to download it from the depot.
-fn verify(url: &str,
-          archive: &PackageArchive,
-          ident: &PackageIdent,
-          fs_root_path: &Path,
-          cache_key_path: &Path)
-          -> Result<()> {
+/// If we don't, attempt to download it from the depot. Then verify and unpack the artifact in one
+/// pass.
+fn verify_and_unpack(url: &str,
+                     archive: &mut PackageArchive,
+                     fs_root_path: &Path,
+                     cache_key_path: &Path)
+                     -> Result<()> {
     let nwr = try!(artifact::artifact_signer(&archive.path));
+    let (name, rev) = try!(parse_name_with_rev(&nwr));
+    let depot_client = try!(Client::new(url, Some(fs_root_path)));
     if let Err(_) = SigKeyPair::get_public_key_path(&nwr, cache_key_path) {
         println!("{} {} public origin key",
                  Green.bold().paint("↓ Downloading"),
                  &nwr);
-        let (name, rev) = try!(parse_name_with_rev(&nwr));
         let mut progress = ProgressBar::default();
-        let depot_client = try!(Client::new(url, Some(fs_root_path)));
         try!(depot_client.fetch_origin_key(&name, &rev, cache_key_path, Some(&mut progress)));
         println!("{} {} public origin key",
                  Green.bold().paint("☑ Cached"),
                  &nwr);
     }
+    // Pick up keys the origin has revoked since we last looked. If the depot can't tell us, the
+    // revocation lists already cached still apply, but a list which doesn't verify is an error.
+    match depot_client.fetch_revocation_list(&name, cache_key_path) {
+        Ok(_) => (),
+        Err(depot_client::Error::HabitatCore(e)) => return Err(Error::HabitatCore(e)),
+        Err(e) => {
+            println!("{} {}: {}",
+                     Yellow.bold().paint("» Using cached revocation lists for"),
+                     &name,
+                     e);
+        }
+    }
 
-    try!(archive.verify(&cache_key_path));
-    info!("Verified {} signed by {}", &ident, &nwr);
+    try!(archive.verify_and_unpack(Some(fs_root_path), &cache_key_path, &SignaturePolicy::Origin));
+    info!("Verified {} signed by {}", try!(archive.ident()), &nwr);
     Ok(())
 }
@@ -0,0 +1,96 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolves a package requirement, like `core/openssl >= 1.0.2, < 1.1`, to the newest package
+//! that meets it, looking at both the installed packages and the depot.
+
+use std::path::Path;
+
+use ansi_term::Colour::Yellow;
+use depot_client::{self, Client};
+use hcore;
+use hcore::package::{PackageIdent, PackageIdentReq, PackageInstall};
+
+use error::{Error, Result};
+
+/// True if the argument is written as a requirement, with operators, wildcards or a list of
+/// constraints, rather than as a plain package ident.
+pub fn is_req(ident_or_req: &str) -> bool {
+    ident_or_req.trim().contains(|c: char| {
+        c.is_whitespace() || c == ',' || c == '*' || c == '<' || c == '>' || c == '=' ||
+        c == '~' || c == '^'
+    })
+}
+
+/// Returns the newest fully qualified ident meeting the requirement, from the packages
+/// installed under `fs_root_path` and the releases in the depot at `url`.
+///
+/// If the depot can't be reached, the newest matching installed package is used.
+///
+/// # Failures
+///
+/// * Neither an installed package nor a depot release meets the requirement
+/// * The depot can't be reached, and no installed package meets the requirement
+pub fn newest<P: ?Sized>(url: &str,
+                         req: &PackageIdentReq,
+                         fs_root_path: &P)
+                         -> Result<PackageIdent>
+    where P: AsRef<Path>
+{
+    let installed = PackageInstall::load_req(req, Some(fs_root_path.as_ref()))
+        .ok()
+        .map(|pkg| pkg.ident().clone());
+    let depot_client = try!(Client::new(url, Some(fs_root_path.as_ref())));
+    let remote = match depot_client.show_package_versions(&req.origin, &req.name) {
+        Ok(idents) => req.newest(&idents).cloned(),
+        Err(depot_client::Error::RemotePackageNotFound(_)) => None,
+        Err(e) => {
+            if installed.is_none() {
+                return Err(Error::from(e));
+            }
+            println!("{} {}",
+                     Yellow.bold().paint("» Using the installed package, the depot failed:"),
+                     e);
+            None
+        }
+    };
+    let candidates: Vec<PackageIdent> = installed.into_iter().chain(remote).collect();
+    match req.newest(&candidates) {
+        Some(ident) => Ok(ident.clone()),
+        None => {
+            let ident = PackageIdent::new(req.origin.clone(), req.name.clone(), None, None);
+            Err(Error::from(hcore::Error::PackageNotFound(ident)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_req;
+
+    #[test]
+    fn plain_idents_are_not_reqs() {
+        assert!(!is_req("core/openssl"));
+        assert!(!is_req("core/openssl/1.0.2"));
+        assert!(!is_req("core/openssl/1.0.2/20160612081125"));
+    }
+
+    #[test]
+    fn constrained_idents_are_reqs() {
+        assert!(is_req("core/openssl >= 1.0.2, < 1.1"));
+        assert!(is_req("core/redis ~> 3.0"));
+        assert!(is_req("core/glibc ^2.22"));
+        assert!(is_req("core/zlib/1.2.*"));
+    }
+}
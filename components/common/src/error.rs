@@ -28,8 +28,12 @@ pub type Result<T> = result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
     CantUploadGossipToml,
+    CantUploadSecretsToml,
     CryptoKeyError(String),
+    GossipFileBadSignature(String),
     GossipFileRelativePath(String),
+    GossipFileSignerNotAllowed(String),
+    GossipFileUnsigned(String),
     DepotClient(depot_client::Error),
     FileNameError,
     HabitatCore(hcore::Error),
@@ -49,11 +53,21 @@ impl fmt::Display for Error {
             Error::CantUploadGossipToml => {
                 format!("Can't upload gossip.toml, it's a reserved file name")
             }
+            Error::CantUploadSecretsToml => {
+                format!("Can't upload secrets.toml, it's a reserved file name")
+            }
             Error::CryptoKeyError(ref s) => format!("Missing or invalid key: {}", s),
+            Error::GossipFileBadSignature(ref s) => {
+                format!("Gossip file signature does not match its contents: {}", s)
+            }
             Error::GossipFileRelativePath(ref s) => {
                 format!("Path for gossip file cannot have relative components (eg: ..): {}",
                         s)
             }
+            Error::GossipFileSignerNotAllowed(ref s) => {
+                format!("Gossip file signer is not allowed: {}", s)
+            }
+            Error::GossipFileUnsigned(ref s) => format!("Gossip file is not signed: {}", s),
             Error::DepotClient(ref err) => format!("{}", err),
             Error::FileNameError => format!("Failed to extract a filename"),
             Error::HabitatCore(ref e) => format!("{}", e),
@@ -73,10 +87,14 @@ impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::CantUploadGossipToml => "Can't upload gossip.toml, it's a reserved filename",
+            Error::CantUploadSecretsToml => "Can't upload secrets.toml, it's a reserved filename",
             Error::CryptoKeyError(_) => "Missing or invalid key",
+            Error::GossipFileBadSignature(_) => "Gossip file signature does not match its contents",
             Error::GossipFileRelativePath(_) => {
                 "Path for gossip file cannot have relative components (eg: ..)"
             }
+            Error::GossipFileSignerNotAllowed(_) => "Gossip file signer is not allowed",
+            Error::GossipFileUnsigned(_) => "Gossip file is not signed",
             Error::DepotClient(ref err) => err.description(),
             Error::FileNameError => "Failed to extract a filename from a path",
             Error::HabitatCore(ref err) => err.description(),
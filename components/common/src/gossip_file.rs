@@ -20,7 +20,8 @@ use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use hcore::crypto::{BoxKeyPair, default_cache_key_path};
+use hcore::crypto::{BoxKeyPair, SigKeyPair, default_cache_key_path};
+use hcore::crypto::keys::parse_name_with_rev;
 use hcore::fs;
 use hcore::service::ServiceGroup;
 use openssl::crypto::hash as openssl_hash;
@@ -33,6 +34,9 @@ use error::{Error, Result};
 const IDEMPOTENCY_INTERVAL_MINUTES: i64 = 5;
 
 pub const GOSSIP_TOML: &'static str = "gossip.toml";
+/// Secrets for a service group. Always encrypted for the service group's box key, and never
+/// written to disk; the supervisor decrypts them in memory for its templates.
+pub const SECRETS_TOML: &'static str = "secrets.toml";
 
 /// The gossip file struct.
 #[derive(Clone, Debug, Eq, RustcDecodable, RustcEncodable)]
@@ -44,6 +48,8 @@ pub struct GossipFile {
     version_number: u64,
     written: bool,
     encrypted: bool,
+    signer: Option<String>,
+    signature: Option<Vec<u8>>,
 }
 
 impl GossipFile {
@@ -78,6 +84,8 @@ impl GossipFile {
             version_number: version_number,
             written: false,
             encrypted: false,
+            signer: None,
+            signature: None,
         };
         Ok(cf)
     }
@@ -96,33 +104,67 @@ impl GossipFile {
         if path.file_name().unwrap() == GOSSIP_TOML {
             return Err(Error::CantUploadGossipToml);
         }
-        for part in path.components() {
-            let pstr = format!("{}", part.as_os_str().to_string_lossy().into_owned());
-            if &pstr == ".." {
-                return Err(Error::GossipFileRelativePath(path.to_string_lossy()
-                    .into_owned()));
-            }
+        if path.file_name().unwrap() == SECRETS_TOML {
+            return Err(Error::CantUploadSecretsToml);
         }
-        let mut f = try!(File::open(&path));
-        let mut body = Vec::new();
-        try!(f.read_to_end(&mut body));
+        let body = try!(read_body(path));
+        let file_name = try!(path.file_name().ok_or(Error::FileNameError));
+        GossipFile::encrypted(user_pair,
+                              service_pair,
+                              file_name.to_string_lossy().to_string(),
+                              body,
+                              version_number)
+    }
 
-        let encrypted_body = try!(user_pair.encrypt(&body, service_pair));
+    /// Creates an encrypted GossipFile of secrets for a service group, from a file containing
+    /// valid TOML. The GossipFile.file_name is automatically assigned to `secrets.toml`.
+    pub fn secrets_from_file_encrypt<P: AsRef<Path> + ?Sized>(user_pair: &BoxKeyPair,
+                                                              service_pair: &BoxKeyPair,
+                                                              file_path: &P,
+                                                              version_number: u64)
+                                                              -> Result<GossipFile> {
+        let body = try!(read_body(file_path.as_ref()));
+        let toml_body = try!(String::from_utf8(body.clone()));
+        try!(is_data_toml(toml_body));
+        GossipFile::encrypted(user_pair,
+                              service_pair,
+                              SECRETS_TOML.to_string(),
+                              body,
+                              version_number)
+    }
 
-        let file_name = try!(path.file_name().ok_or(Error::FileNameError));
+    fn encrypted(user_pair: &BoxKeyPair,
+                 service_pair: &BoxKeyPair,
+                 file_name: String,
+                 body: Vec<u8>,
+                 version_number: u64)
+                 -> Result<GossipFile> {
+        let encrypted_body = try!(user_pair.encrypt(&body, service_pair));
         let checksum = openssl_hash::hash(openssl_hash::Type::SHA256, &body);
         let cf = GossipFile {
             service_group: try!(ServiceGroup::from_str(&service_pair.name)),
-            file_name: file_name.to_string_lossy().to_string(),
+            file_name: file_name,
             body: encrypted_body,
             checksum: checksum.as_slice().to_hex(),
             version_number: version_number,
             written: false,
             encrypted: true,
+            signer: None,
+            signature: None,
         };
         Ok(cf)
     }
 
+    /// Decrypts the body of an encrypted file with the service's key from the key cache. Bodies
+    /// that aren't encrypted are returned as they are.
+    pub fn decrypt(&self) -> Result<Vec<u8>> {
+        if self.encrypted {
+            let decrypted = try!(BoxKeyPair::decrypt(&self.body, &default_cache_key_path(None)));
+            Ok(decrypted)
+        } else {
+            Ok(self.body.clone())
+        }
+    }
 
     /// creates a GossipFile with a Vec<u8> containing valid TOML
     /// The GossipFile.file_name is automatically assigned to `gossip.toml`.
@@ -143,10 +185,66 @@ impl GossipFile {
             version_number: version_number,
             written: false,
             encrypted: false,
+            signer: None,
+            signature: None,
         };
         Ok(cf)
     }
 
+    /// Signs this file with a sig key, so supervisors can check who published it. The signature
+    /// covers the service group, file name, version number and the body.
+    pub fn sign(&mut self, pair: &SigKeyPair) -> Result<()> {
+        let signature = try!(pair.sign(&self.signed_payload()));
+        self.signer = Some(pair.name_with_rev());
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// The name with revision of the key that signed this file, if any.
+    pub fn signer(&self) -> Option<&str> {
+        self.signer.as_ref().map(|s| &s[..])
+    }
+
+    /// Verifies the signature on this file. The signing key must be one of `allowed_signers`,
+    /// which may list either key names (any revision is accepted) or names with revisions, and
+    /// its public key must be in the key cache.
+    ///
+    /// Returns the name with revision of the signing key.
+    pub fn verify<P: AsRef<Path> + ?Sized>(&self,
+                                           allowed_signers: &[String],
+                                           cache_key_path: &P)
+                                           -> Result<String> {
+        let (signer, signature) = match (&self.signer, &self.signature) {
+            (&Some(ref signer), &Some(ref signature)) => (signer, signature),
+            _ => return Err(Error::GossipFileUnsigned(self.to_string())),
+        };
+        let (name, _) = try!(parse_name_with_rev(signer));
+        if !allowed_signers.iter().any(|s| s == &name || s == signer) {
+            return Err(Error::GossipFileSignerNotAllowed(signer.clone()));
+        }
+        // Only the public key is needed, so an encrypted secret key is never prompted for
+        let pair = try!(SigKeyPair::get_public_pair_for(signer, cache_key_path));
+        let payload = match pair.verify(signature) {
+            Ok(payload) => payload,
+            Err(_) => return Err(Error::GossipFileBadSignature(self.to_string())),
+        };
+        if payload != self.signed_payload() {
+            return Err(Error::GossipFileBadSignature(self.to_string()));
+        }
+        Ok(signer.clone())
+    }
+
+    fn signed_payload(&self) -> Vec<u8> {
+        let body_checksum = openssl_hash::hash(openssl_hash::Type::SHA256, &self.body);
+        format!("{}\n{}\n{}\n{}\n{}",
+                self.service_group,
+                self.file_name,
+                self.version_number,
+                self.checksum,
+                body_checksum.as_slice().to_hex())
+            .into_bytes()
+    }
+
     /// Updates this struct against another `GossipFile`. If true is returned, we have changed the gossip file
     /// and the rumour should stay hot. If false is returned, nothing has changed and the rumour
     /// can start to go cold. The algorithm is as follows:
@@ -220,8 +318,7 @@ impl GossipFile {
                     // However, if decrypt() can't find user/service keys,
                     // this write will fail.
                     println!("Attempting to decrypt {}", &self.file_name);
-                    let decrypted_bytes = try!(BoxKeyPair::decrypt(&self.body,
-                                                                   &default_cache_key_path(None)));
+                    let decrypted_bytes = try!(self.decrypt());
                     println!("Successfully decrypted {}", &self.file_name);
                     try!(new_file.write_all(&decrypted_bytes));
                 } else {
@@ -254,6 +351,20 @@ impl PartialEq for GossipFile {
 }
 
 
+/// read a file to gossip, refusing paths with relative components
+fn read_body(path: &Path) -> Result<Vec<u8>> {
+    for part in path.components() {
+        let pstr = format!("{}", part.as_os_str().to_string_lossy().into_owned());
+        if &pstr == ".." {
+            return Err(Error::GossipFileRelativePath(path.to_string_lossy().into_owned()));
+        }
+    }
+    let mut f = try!(File::open(&path));
+    let mut body = Vec::new();
+    try!(f.read_to_end(&mut body));
+    Ok(body)
+}
+
 /// try to parse the string as Toml
 fn is_data_toml(body: String) -> Result<()> {
     let mut parser = toml::Parser::new(&body);
@@ -322,23 +433,67 @@ impl Encodable for FileWriteRetry {
 #[derive(Debug)]
 pub struct GossipFileList {
     my_service_group: ServiceGroup,
+    // Keys allowed to sign files for my_service_group; when empty, files need not be signed
+    allowed_signers: Vec<String>,
     // (ServiceGroup, Filename)
     gossip_files: HashMap<(ServiceGroup, String), GossipFile>,
     next_idempotency_check: SteadyTime,
     pub file_write_retries: HashMap<String, FileWriteRetry>,
+    // Checksum of the secrets we last told the supervisor about
+    secrets_checksum: Option<String>,
+    // (Filename, Checksum) of files the supervisor refused to apply
+    rejected: HashMap<String, String>,
 }
 
 impl GossipFileList {
     pub fn new(service_group: ServiceGroup) -> GossipFileList {
         GossipFileList {
             my_service_group: service_group,
+            allowed_signers: Vec::new(),
             gossip_files: HashMap::new(),
             next_idempotency_check: SteadyTime::now() +
                                     Duration::minutes(IDEMPOTENCY_INTERVAL_MINUTES),
             file_write_retries: HashMap::new(),
+            secrets_checksum: None,
+            rejected: HashMap::new(),
         }
     }
 
+    /// Require every file for our service group to be signed by one of these keys before we
+    /// apply it. See `GossipFile::verify`.
+    pub fn set_allowed_signers(&mut self, signers: Vec<String>) {
+        self.allowed_signers = signers;
+    }
+
+    /// The checksum and contents of a file for our service group that differ from what's on
+    /// disk, so the caller can check them before they are written. Encrypted files are decrypted.
+    pub fn pending(&self, file_name: &str) -> Option<(String, Result<Vec<u8>>)> {
+        let key = (self.my_service_group.clone(), file_name.to_string());
+        let gf = match self.gossip_files.get(&key) {
+            Some(gf) => gf,
+            None => return None,
+        };
+        if self.rejected.get(file_name) == Some(&gf.checksum) {
+            return None;
+        }
+        match gf.checksum_file() {
+            Ok(ref checksum) if checksum == &gf.checksum => return None,
+            _ => {}
+        }
+        let body = if gf.encrypted {
+            gf.decrypt()
+        } else {
+            Ok(gf.body.clone())
+        };
+        Some((gf.checksum.clone(), body))
+    }
+
+    /// Never write this version of a file to disk; what's there now is kept until a different
+    /// version is gossiped.
+    pub fn reject(&mut self, file_name: &str, checksum: &str) {
+        self.rejected.insert(file_name.to_string(), checksum.to_string());
+    }
+
     // We should write out if our metadata tells us to. Also, if we haven't checked the state of
     // the filesystem in the last 5 minutes, we're going to call write regardless, just to make
     // sure nothing has changed out from under us on disk. Viva la Chef!
@@ -373,6 +528,21 @@ impl GossipFileList {
                 continue;
             }
 
+            // Secrets never touch the disk; the supervisor reads them with `secrets()` when it
+            // reconfigures.
+            if gf.file_name == SECRETS_TOML {
+                if self.secrets_checksum.as_ref() != Some(&gf.checksum) {
+                    self.secrets_checksum = Some(gf.checksum.clone());
+                    needs_reconfigure = true;
+                }
+                continue;
+            }
+
+            if self.rejected.get(&gf.file_name) == Some(&gf.checksum) {
+                debug!("Not writing rejected file {}", &gf.file_name);
+                continue;
+            }
+
             // see if we need to skip this write if it's a retry but
             // it's not quite time yet
             let needs_retry = self.file_write_retries.contains_key(&gf.file_name);
@@ -437,7 +607,19 @@ impl GossipFileList {
         Ok((needs_file_updated, needs_reconfigure))
     }
 
+    /// Process a gossip file we received. If we require signed files, files for our service group
+    /// must carry a valid signature from an allowed signer, or they are dropped. Files for other
+    /// service groups are passed along; their own supervisors will check them.
     pub fn process(&mut self, remote_gf: GossipFile) -> bool {
+        if !self.allowed_signers.is_empty() && remote_gf.service_group == self.my_service_group {
+            match remote_gf.verify(&self.allowed_signers, &default_cache_key_path(None)) {
+                Ok(signer) => debug!("{} signed by {}", remote_gf, signer),
+                Err(e) => {
+                    println!("Rejecting {}: {}", remote_gf, e);
+                    return false;
+                }
+            }
+        }
         if let Some(mut current_gf) = self.get_mut(&remote_gf.service_group, &remote_gf.file_name) {
             return current_gf.update_via(remote_gf);
         }
@@ -446,6 +628,22 @@ impl GossipFileList {
         true
     }
 
+    /// Decrypts the secrets gossiped for our service group, if there are any. They are only ever
+    /// held in memory.
+    pub fn secrets(&self) -> Result<Option<toml::Table>> {
+        let key = (self.my_service_group.clone(), SECRETS_TOML.to_string());
+        let gf = match self.gossip_files.get(&key) {
+            Some(gf) => gf,
+            None => return Ok(None),
+        };
+        let body = try!(String::from_utf8(try!(gf.decrypt())));
+        let mut parser = toml::Parser::new(&body);
+        match parser.parse() {
+            Some(table) => Ok(Some(table)),
+            None => Err(Error::InvalidTomlError(format_errors(&parser))),
+        }
+    }
+
     fn get_mut(&mut self,
                service_group: &ServiceGroup,
                file_name: &str)
@@ -466,9 +664,9 @@ mod test {
     use tempdir::TempDir;
     use time::SteadyTime;
 
-    use hcore::crypto::BoxKeyPair;
+    use hcore::crypto::{BoxKeyPair, SigKeyPair};
     use hcore::service::ServiceGroup;
-    use gossip_file::{GossipFile, FileWriteRetry, GOSSIP_TOML};
+    use gossip_file::{GossipFile, GossipFileList, FileWriteRetry, GOSSIP_TOML, SECRETS_TOML};
 
     fn fixture(name: &str) -> PathBuf {
         env::current_exe()
@@ -539,6 +737,78 @@ mod test {
         assert!(decrypted == s);
     }
 
+    #[test]
+    fn new_secrets_from_file_encrypt() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let user_pair = BoxKeyPair::generate_pair_for_user("testuser", cache.path()).unwrap();
+        let service_pair =
+            BoxKeyPair::generate_pair_for_service("someorg", "petty.gunslingers", cache.path())
+                .unwrap();
+        let gf = GossipFile::secrets_from_file_encrypt(&user_pair,
+                                                       &service_pair,
+                                                       fixture("foo.toml").as_path(),
+                                                       1)
+            .unwrap();
+        assert_eq!(gf.file_name, SECRETS_TOML);
+        assert!(gf.encrypted);
+        let val_bytes = BoxKeyPair::decrypt(&gf.body, cache.path()).unwrap();
+        let mut f = File::open(fixture("foo.toml").as_path()).unwrap();
+        let mut s = String::new();
+        f.read_to_string(&mut s).unwrap();
+        assert_eq!(String::from_utf8(val_bytes).unwrap(), s);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_secrets_from_file_encrypt_invalid_toml() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let user_pair = BoxKeyPair::generate_pair_for_user("testuser", cache.path()).unwrap();
+        let service_pair =
+            BoxKeyPair::generate_pair_for_service("someorg", "petty.gunslingers", cache.path())
+                .unwrap();
+        GossipFile::secrets_from_file_encrypt(&user_pair,
+                                              &service_pair,
+                                              fixture("bad.toml").as_path(),
+                                              1)
+            .unwrap();
+    }
+
+    #[test]
+    fn secrets_reconfigure_once_without_writing() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let user_pair = BoxKeyPair::generate_pair_for_user("testuser", cache.path()).unwrap();
+        let service_pair =
+            BoxKeyPair::generate_pair_for_service("someorg", "petty.gunslingers", cache.path())
+                .unwrap();
+        let gf = GossipFile::secrets_from_file_encrypt(&user_pair,
+                                                       &service_pair,
+                                                       fixture("foo.toml").as_path(),
+                                                       1)
+            .unwrap();
+        let mut gfl = GossipFileList::new(gf.service_group.clone());
+        assert!(gfl.process(gf));
+        assert_eq!(gfl.write().unwrap(), (false, true));
+        assert_eq!(gfl.write().unwrap(), (false, false));
+        assert!(gfl.file_write_retries.is_empty());
+    }
+
+    #[test]
+    fn rejected_file_is_not_written() {
+        let gf = GossipFile::from_body(ServiceGroup::from_str("chromeo.footwork").unwrap(),
+                                       "Rage=1\n".as_bytes().to_vec(),
+                                       1)
+            .unwrap();
+        let mut gfl = GossipFileList::new(gf.service_group.clone());
+        assert!(gfl.process(gf.clone()));
+        let (checksum, body) = gfl.pending(GOSSIP_TOML).unwrap();
+        assert_eq!(checksum, gf.checksum);
+        assert_eq!(body.unwrap(), "Rage=1\n".as_bytes().to_vec());
+        gfl.reject(GOSSIP_TOML, &checksum);
+        assert!(gfl.pending(GOSSIP_TOML).is_none());
+        assert_eq!(gfl.write().unwrap(), (false, false));
+        assert!(gfl.file_write_retries.is_empty());
+    }
+
     #[test]
     fn new_from_body() {
         let cf = GossipFile::from_body(ServiceGroup::from_str("chromeo.footwork").unwrap(),
@@ -649,6 +919,74 @@ mod test {
         assert_eq!(me == other, true);
     }
 
+    #[test]
+    fn sign_and_verify() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("ops", cache.path()).unwrap();
+        let mut gf = GossipFile::from_body(ServiceGroup::from_str("heart.barracuda").unwrap(),
+                                           "woot=1\n".as_bytes().to_vec(),
+                                           20)
+            .unwrap();
+        gf.sign(&pair).unwrap();
+        assert_eq!(gf.signer(), Some(&pair.name_with_rev()[..]));
+
+        // Allowed by key name, or by name with revision
+        assert_eq!(gf.verify(&["ops".to_string()], cache.path()).unwrap(),
+                   pair.name_with_rev());
+        assert!(gf.verify(&[pair.name_with_rev()], cache.path()).is_ok());
+
+        // Not in the allowed list
+        assert!(gf.verify(&["devs".to_string()], cache.path()).is_err());
+    }
+
+    #[test]
+    fn verify_unsigned() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let gf = GossipFile::from_body(ServiceGroup::from_str("heart.barracuda").unwrap(),
+                                       "woot=1\n".as_bytes().to_vec(),
+                                       20)
+            .unwrap();
+        assert!(gf.verify(&["ops".to_string()], cache.path()).is_err());
+    }
+
+    #[test]
+    fn verify_tampered() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("ops", cache.path()).unwrap();
+        let mut gf = GossipFile::from_body(ServiceGroup::from_str("heart.barracuda").unwrap(),
+                                           "woot=1\n".as_bytes().to_vec(),
+                                           20)
+            .unwrap();
+        gf.sign(&pair).unwrap();
+        gf.version_number = 21;
+        assert!(gf.verify(&["ops".to_string()], cache.path()).is_err());
+
+        let mut gf = GossipFile::from_body(ServiceGroup::from_str("heart.barracuda").unwrap(),
+                                           "woot=1\n".as_bytes().to_vec(),
+                                           20)
+            .unwrap();
+        gf.sign(&pair).unwrap();
+        gf.body = "woot=2\n".as_bytes().to_vec();
+        assert!(gf.verify(&["ops".to_string()], cache.path()).is_err());
+    }
+
+    #[test]
+    fn process_requires_signature() {
+        let sg = ServiceGroup::from_str("heart.barracuda").unwrap();
+        let mut gfl = GossipFileList::new(sg.clone());
+        gfl.set_allowed_signers(vec!["ops".to_string()]);
+        let unsigned = GossipFile::from_body(sg.clone(), "woot=1\n".as_bytes().to_vec(), 20)
+            .unwrap();
+        assert_eq!(gfl.process(unsigned), false);
+
+        // Files for other service groups are left for their own supervisors to check
+        let other = GossipFile::from_body(ServiceGroup::from_str("heart.oops").unwrap(),
+                                          "woot=1\n".as_bytes().to_vec(),
+                                          20)
+            .unwrap();
+        assert_eq!(gfl.process(other), true);
+    }
+
     #[test]
     fn file_write_retry_encode() {
         let fwr = FileWriteRetry::new("foo".to_string(),
@@ -19,6 +19,10 @@
 //! over a UTP socket between Supervisors or from a CLI to one or more Supervisors. The message can
 //! optionally be encrypted or decrypted with a symmetric encryption key. The message itself
 //! contains extra information if encryption is used, and blank if the message is plaintext.
+//!
+//! A message may also carry the schema version of its payload, so a receiver can pick the right
+//! decoding for it. Messages sent before schemas were versioned have no schema, and are treated
+//! as `LEGACY_SCHEMA`.
 
 use std::str;
 
@@ -29,6 +33,9 @@ use error::{Error, Result};
 
 const WIRE_VERSION: &'static str = "WIRE-1";
 
+/// The schema version of any message which does not state its own.
+pub const LEGACY_SCHEMA: u32 = 1;
+
 /// The types of valid messages: currently `Plain` or `Encrypted`.
 #[derive(Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub enum MessageFormat {
@@ -60,6 +67,8 @@ pub struct WireMessage {
     /// A byte vector containing the raw message if plaintext or the the encrypted message if
     /// encrypted.
     msg_bytes: Vec<u8>,
+    /// The schema version of the message payload. Older senders do not set this.
+    schema: Option<u32>,
 }
 
 impl WireMessage {
@@ -103,6 +112,7 @@ impl WireMessage {
             key: None,
             nonce: None,
             msg_bytes: try!(json::encode(&msg)).into_bytes(),
+            schema: None,
         })
     }
 
@@ -155,9 +165,21 @@ impl WireMessage {
             key: Some(sym_key.name_with_rev()),
             nonce: Some(nonce),
             msg_bytes: ciphertext,
+            schema: None,
         })
     }
 
+    /// Returns the schema version of the message payload, which is `LEGACY_SCHEMA` if the sender
+    /// didn't state one.
+    pub fn schema(&self) -> u32 {
+        self.schema.unwrap_or(LEGACY_SCHEMA)
+    }
+
+    /// Sets the schema version of the message payload.
+    pub fn set_schema(&mut self, schema: u32) {
+        self.schema = Some(schema);
+    }
+
     /// # Examples
     ///
     /// Basic usage:
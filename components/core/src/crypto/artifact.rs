@@ -12,19 +12,180 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fs::File;
+use std::fmt;
+use std::fs::{self, File};
 use std::io;
 use std::io::prelude::*;
 use std::io::{BufReader, BufWriter};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::result;
+use std::str::FromStr;
 
 use rustc_serialize::base64::{STANDARD, ToBase64, FromBase64};
 use sodiumoxide::crypto::sign;
 
 use error::{Error, Result};
-use super::{HART_FORMAT_VERSION, SIG_HASH_TYPE, SigKeyPair};
+use super::{HART_FORMAT_VERSION, HART_MULTISIG_FORMAT_VERSION, SIG_HASH_TYPE, RevocationList,
+            SigKeyPair};
 use super::keys::parse_name_with_rev;
 
+/// One signature from the header of a Habitat artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// The name with revision of the key that made the signature
+    pub key_name_with_rev: String,
+    /// The signed hash of the payload
+    pub value: Vec<u8>,
+}
+
+/// The plaintext header of a Habitat artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactHeader {
+    pub format_version: String,
+    pub hash_type: String,
+    /// Every signature on the artifact, the origin signature first
+    pub signatures: Vec<Signature>,
+}
+
+/// Which signatures an artifact must carry to pass verification.
+///
+/// Policies are written as a `+` separated list of terms which must all hold, where each term is
+/// a `|` separated list of alternatives, at least one of which must hold. An alternative is
+/// either `@origin`, the first signature on the artifact, or a key name, with or without its
+/// revision. `@origin+release|qa` requires the origin signature and a signature by either the
+/// `release` or `qa` key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignaturePolicy {
+    /// The first signature, made with the origin key when the artifact was built
+    Origin,
+    /// A signature by the named key
+    Key(String),
+    /// Every one of the policies
+    All(Vec<SignaturePolicy>),
+    /// At least one of the policies
+    Any(Vec<SignaturePolicy>),
+}
+
+impl SignaturePolicy {
+    /// Checks the policy against the signatures in the header, returning the names with revision
+    /// of the keys whose signatures it relied on.
+    fn evaluate(&self,
+                header: &ArtifactHeader,
+                hash: &str,
+                cache_key_path: &Path)
+                -> Result<Vec<String>> {
+        match *self {
+            SignaturePolicy::Origin => {
+                let signature = &header.signatures[0];
+                try!(check_signature(signature, hash, cache_key_path));
+                Ok(vec![signature.key_name_with_rev.clone()])
+            }
+            SignaturePolicy::Key(ref name) => {
+                let mut last_err = None;
+                for signature in header.signatures.iter().filter(|s| s.signed_by(name)) {
+                    match check_signature(signature, hash, cache_key_path) {
+                        Ok(()) => return Ok(vec![signature.key_name_with_rev.clone()]),
+                        Err(e) => last_err = Some(e),
+                    }
+                }
+                Err(last_err.unwrap_or_else(|| {
+                    Error::CryptoError(format!("Artifact is not signed with {}", name))
+                }))
+            }
+            SignaturePolicy::All(ref policies) => {
+                let mut signers = Vec::new();
+                for policy in policies {
+                    for signer in try!(policy.evaluate(header, hash, cache_key_path)) {
+                        if !signers.contains(&signer) {
+                            signers.push(signer);
+                        }
+                    }
+                }
+                Ok(signers)
+            }
+            SignaturePolicy::Any(ref policies) => {
+                for policy in policies {
+                    match policy.evaluate(header, hash, cache_key_path) {
+                        Ok(signers) => return Ok(signers),
+                        Err(e) => debug!("Signature policy {} not met: {}", policy, e),
+                    }
+                }
+                Err(Error::CryptoError(format!("Artifact signatures don't meet the policy {}",
+                                               self)))
+            }
+        }
+    }
+}
+
+impl Default for SignaturePolicy {
+    fn default() -> SignaturePolicy {
+        SignaturePolicy::Origin
+    }
+}
+
+impl fmt::Display for SignaturePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SignaturePolicy::Origin => write!(f, "@origin"),
+            SignaturePolicy::Key(ref name) => write!(f, "{}", name),
+            SignaturePolicy::All(ref policies) => {
+                let terms: Vec<String> = policies.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", terms.join("+"))
+            }
+            SignaturePolicy::Any(ref policies) => {
+                let terms: Vec<String> = policies.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", terms.join("|"))
+            }
+        }
+    }
+}
+
+impl FromStr for SignaturePolicy {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        let mut all = Vec::new();
+        for term in value.split('+') {
+            let mut any = Vec::new();
+            for alternative in term.split('|').map(|a| a.trim()) {
+                let policy = match alternative {
+                    "" => {
+                        return Err(Error::CryptoError(format!("Invalid signature policy: {}",
+                                                              value)))
+                    }
+                    "@origin" => SignaturePolicy::Origin,
+                    name => SignaturePolicy::Key(name.to_string()),
+                };
+                any.push(policy);
+            }
+            if any.len() == 1 {
+                all.push(any.remove(0));
+            } else {
+                all.push(SignaturePolicy::Any(any));
+            }
+        }
+        if all.len() == 1 {
+            Ok(all.remove(0))
+        } else {
+            Ok(SignaturePolicy::All(all))
+        }
+    }
+}
+
+impl Signature {
+    /// True if the signature was made with the named key. A name without a revision matches
+    /// every revision of the key.
+    fn signed_by(&self, name: &str) -> bool {
+        if self.key_name_with_rev == name {
+            return true;
+        }
+        match parse_name_with_rev(&self.key_name_with_rev) {
+            Ok((key_name, _)) => key_name == name,
+            Err(_) => false,
+        }
+    }
+}
+
 /// Generate and sign a package
 pub fn sign<P1: ?Sized, P2: ?Sized>(src: &P1, dst: &P2, pair: &SigKeyPair) -> Result<()>
     where P1: AsRef<Path>,
@@ -47,107 +208,135 @@ pub fn sign<P1: ?Sized, P2: ?Sized>(src: &P1, dst: &P2, pair: &SigKeyPair) -> Re
     Ok(())
 }
 
-/// return a BufReader to the .tar bytestream, skipping the signed header
-pub fn get_archive_reader<P: AsRef<Path>>(src: &P) -> Result<BufReader<File>> {
+/// Countersign a Habitat artifact, adding a signature with the given key to those already in its
+/// header. The origin signature must verify first, so a tampered artifact is never countersigned.
+///
+/// The signed artifact is written to `dst`, which may be the same path as `src`. An artifact
+/// with more than one signature uses the `HART-2` header format.
+pub fn append_signature<P1: ?Sized, P2: ?Sized, P3: ?Sized>(src: &P1,
+                                                            dst: &P2,
+                                                            pair: &SigKeyPair,
+                                                            cache_key_path: &P3)
+                                                            -> Result<()>
+    where P1: AsRef<Path>,
+          P2: AsRef<Path>,
+          P3: AsRef<Path>
+{
     let f = try!(File::open(src));
-    let mut your_format_version = String::new();
-    let mut your_key_name = String::new();
-    let mut your_hash_type = String::new();
-    let mut your_signature_raw = String::new();
-    let mut empty_line = String::new();
-
     let mut reader = BufReader::new(f);
-    if try!(reader.read_line(&mut your_format_version)) <= 0 {
-        return Err(Error::CryptoError("Can't read format version".to_string()));
-    }
-    if try!(reader.read_line(&mut your_key_name)) <= 0 {
-        return Err(Error::CryptoError("Can't read keyname".to_string()));
+    let mut header = try!(read_header(&mut reader));
+    if header.signatures.iter().any(|s| s.key_name_with_rev == pair.name_with_rev()) {
+        return Err(Error::CryptoError(format!("Artifact is already signed with {}",
+                                              pair.name_with_rev())));
     }
-    if try!(reader.read_line(&mut your_hash_type)) <= 0 {
-        return Err(Error::CryptoError("Can't read hash type".to_string()));
-    }
-    if try!(reader.read_line(&mut your_signature_raw)) <= 0 {
-        return Err(Error::CryptoError("Can't read signature".to_string()));
-    }
-    if try!(reader.read_line(&mut empty_line)) <= 0 {
-        return Err(Error::CryptoError("Can't end of header".to_string()));
+    let hash = try!(super::hash::hash_reader(&mut reader));
+    try!(SignaturePolicy::Origin.evaluate(&header, &hash, cache_key_path.as_ref()));
+    header.signatures.push(Signature {
+        key_name_with_rev: pair.name_with_rev(),
+        value: sign::sign(&hash.as_bytes(), try!(pair.secret())),
+    });
+
+    let tmp_path = temp_path(dst.as_ref());
+    {
+        let output_file = try!(File::create(&tmp_path));
+        let mut writer = BufWriter::new(&output_file);
+        try!(write_header(&mut writer, &header));
+        let mut payload = try!(get_archive_reader(&src));
+        try!(io::copy(&mut payload, &mut writer));
+        try!(writer.flush());
     }
+    try!(fs::rename(&tmp_path, dst));
+    Ok(())
+}
+
+/// Read the plaintext header of a Habitat artifact.
+pub fn get_artifact_header<P: AsRef<Path>>(src: &P) -> Result<ArtifactHeader> {
+    let f = try!(File::open(src));
+    let mut reader = BufReader::new(f);
+    read_header(&mut reader)
+}
+
+/// return a BufReader to the .tar bytestream, skipping the signed header
+pub fn get_archive_reader<P: AsRef<Path>>(src: &P) -> Result<BufReader<File>> {
+    let f = try!(File::open(src));
+    let mut reader = BufReader::new(f);
+    try!(read_header(&mut reader));
     Ok(reader)
 }
 
+/// Read the plaintext header of a Habitat artifact, returning it with a BufReader to the .tar
+/// bytestream which follows it.
+pub fn get_header_and_archive_reader<P: AsRef<Path>>(src: &P)
+                                                     -> Result<(ArtifactHeader, BufReader<File>)> {
+    let f = try!(File::open(src));
+    let mut reader = BufReader::new(f);
+    let header = try!(read_header(&mut reader));
+    Ok((header, reader))
+}
+
 /// verify the crypto signature of a .hart file
 pub fn verify<P1: ?Sized, P2: ?Sized>(src: &P1, cache_key_path: &P2) -> Result<(String, String)>
     where P1: AsRef<Path>,
           P2: AsRef<Path>
 {
-    let f = try!(File::open(src));
-    let mut reader = BufReader::new(f);
+    let (mut signers, hash) =
+        try!(verify_with_policy(src, cache_key_path, &SignaturePolicy::Origin));
+    Ok((signers.remove(0), hash))
+}
 
-    let _ = {
-        let mut buffer = String::new();
-        match reader.read_line(&mut buffer) {
-            Ok(0) => {
-                return Err(Error::CryptoError("Corrupt payload, can't read format version"
-                    .to_string()))
-            }
-            Ok(_) => {
-                if buffer.trim() != HART_FORMAT_VERSION {
-                    let msg = format!("Unsupported format version: {}", &buffer.trim());
-                    return Err(Error::CryptoError(msg));
-                }
-            }
-            Err(e) => return Err(Error::from(e)),
+/// Verify the signatures of a .hart file against a policy, returning the names with revision of
+/// the keys the policy relied on, and the payload's hash. Signatures the policy doesn't ask for
+/// are not checked.
+pub fn verify_with_policy<P1: ?Sized, P2: ?Sized>(src: &P1,
+                                                  cache_key_path: &P2,
+                                                  policy: &SignaturePolicy)
+                                                  -> Result<(Vec<String>, String)>
+    where P1: AsRef<Path>,
+          P2: AsRef<Path>
+{
+    let (header, mut reader) = try!(get_header_and_archive_reader(&src.as_ref()));
+    let computed_hash = try!(super::hash::hash_reader(&mut reader));
+    let signers = try!(verify_hash_with_policy(&header, &computed_hash, cache_key_path, policy));
+    Ok((signers, computed_hash))
+}
 
-        };
-        buffer.trim().to_string()
-    };
-    let pair = {
-        let mut buffer = String::new();
-        if try!(reader.read_line(&mut buffer)) <= 0 {
-            return Err(Error::CryptoError("Corrupt payload, can't read origin key name"
-                .to_string()));
-        }
-        try!(SigKeyPair::get_pair_for(buffer.trim(), cache_key_path))
-    };
-    let _ = {
-        let mut buffer = String::new();
-        match reader.read_line(&mut buffer) {
-            Ok(0) => {
-                return Err(Error::CryptoError("Corrupt payload, can't read hash type".to_string()))
-            }
-            Ok(_) => {
-                if buffer.trim() != SIG_HASH_TYPE {
-                    let msg = format!("Unsupported signature type: {}", &buffer.trim());
-                    return Err(Error::CryptoError(msg));
-                }
-            }
-            Err(e) => return Err(Error::from(e)),
-        };
-    };
-    let signature = {
-        let mut buffer = String::new();
-        match reader.read_line(&mut buffer) {
-            Ok(0) => {
-                return Err(Error::CryptoError("Corrupt payload, can't read signature".to_string()))
-            }
-            Ok(_) => {
-                match buffer.trim().as_bytes().from_base64() {
-                    Ok(sig) => sig,
-                    Err(e) => {
-                        return Err(Error::CryptoError(format!("Can't decode signature: {}", e)));
-                    }
-                }
-            }
-            Err(e) => return Err(Error::from(e)),
-        }
-    };
-    let _ = {
-        let mut buffer = String::new();
-        if try!(reader.read_line(&mut buffer)) <= 0 {
-            return Err(Error::CryptoError("Corrupt payload, can't find end of header".to_string()));
-        }
-    };
-    let expected_hash = match sign::verify(signature.as_slice(), try!(pair.public())) {
+/// Verify the signatures in an artifact header against a policy, for a payload hash the caller
+/// computed from the artifact it has open. Returns the names with revision of the keys the
+/// policy relied on.
+pub fn verify_hash_with_policy<P: ?Sized>(header: &ArtifactHeader,
+                                          computed_hash: &str,
+                                          cache_key_path: &P,
+                                          policy: &SignaturePolicy)
+                                          -> Result<Vec<String>>
+    where P: AsRef<Path>
+{
+    debug!("My hash {}", computed_hash);
+    policy.evaluate(header, computed_hash, cache_key_path.as_ref())
+}
+
+/// The name with revision of the origin key which signed the artifact.
+pub fn artifact_signer<P: AsRef<Path>>(src: &P) -> Result<String> {
+    let header = try!(get_artifact_header(src));
+    Ok(header.signatures[0].key_name_with_rev.clone())
+}
+
+/// The names with revision of every key which signed the artifact, the origin key first.
+pub fn artifact_signers<P: AsRef<Path>>(src: &P) -> Result<Vec<String>> {
+    let header = try!(get_artifact_header(src));
+    Ok(header.signatures.into_iter().map(|s| s.key_name_with_rev).collect())
+}
+
+/// Check one signature against the payload's hash, with the public key from the key cache.
+fn check_signature(signature: &Signature,
+                   computed_hash: &str,
+                   cache_key_path: &Path)
+                   -> Result<()> {
+    let pair = try!(SigKeyPair::get_public_pair_for(&signature.key_name_with_rev, cache_key_path));
+    if try!(RevocationList::is_revoked(&signature.key_name_with_rev, cache_key_path)) {
+        return Err(Error::CryptoError(format!("Key {} has been revoked",
+                                              &signature.key_name_with_rev)));
+    }
+    let expected_hash = match sign::verify(signature.value.as_slice(), try!(pair.public())) {
         Ok(signed_data) => {
             match String::from_utf8(signed_data) {
                 Ok(hash) => hash,
@@ -158,14 +347,11 @@ pub fn verify<P1: ?Sized, P2: ?Sized>(src: &P1, cache_key_path: &P2) -> Result<(
         }
         Err(_) => return Err(Error::CryptoError("Verification failed".to_string())),
     };
-    debug!("VERIFIED, checking signed hash against mine");
-
-    let computed_hash = try!(super::hash::hash_reader(&mut reader));
-
+    debug!("VERIFIED {}, checking signed hash against mine",
+           &signature.key_name_with_rev);
     debug!("Expected hash {}", expected_hash);
-    debug!("My hash {}", computed_hash);
     if computed_hash == expected_hash {
-        Ok((pair.name_with_rev(), expected_hash))
+        Ok(())
     } else {
         let msg = format!("Habitat artifact is invalid, \
                           hashes don't match (expected: {}, computed: {})",
@@ -175,38 +361,117 @@ pub fn verify<P1: ?Sized, P2: ?Sized>(src: &P1, cache_key_path: &P2) -> Result<(
     }
 }
 
-pub fn artifact_signer<P: AsRef<Path>>(src: &P) -> Result<String> {
-    let f = try!(File::open(src));
-    let mut reader = BufReader::new(f);
+/// Read one line of the header, failing with `msg` if the file ends first.
+fn read_header_line<R: BufRead>(reader: &mut R, msg: &str) -> Result<String> {
+    let mut buffer = String::new();
+    if try!(reader.read_line(&mut buffer)) <= 0 {
+        return Err(Error::CryptoError(format!("Corrupt payload, {}", msg)));
+    }
+    Ok(buffer.trim().to_string())
+}
 
-    let _ = {
-        let mut buffer = String::new();
-        match reader.read_line(&mut buffer) {
-            Ok(0) => {
-                return Err(Error::CryptoError("Corrupt payload, can't read format version"
-                    .to_string()))
+fn decode_signature(value: &str) -> Result<Vec<u8>> {
+    value.as_bytes()
+        .from_base64()
+        .map_err(|e| Error::CryptoError(format!("Can't decode signature: {}", e)))
+}
+
+/// Read the header, leaving the reader at the start of the payload.
+///
+/// A `HART-1` header has a single signature, with its key and value on separate lines. A `HART-2`
+/// header has the hash type first, then one `<key-name-with-rev> <signature>` line per signature.
+/// Both end with an empty line.
+fn read_header<R: BufRead>(reader: &mut R) -> Result<ArtifactHeader> {
+    let format_version = try!(read_header_line(reader, "can't read format version"));
+    let mut signatures = Vec::new();
+    let hash_type = if format_version == HART_FORMAT_VERSION {
+        let key_name_with_rev = try!(read_header_line(reader, "can't read origin key name"));
+        try!(parse_name_with_rev(&key_name_with_rev));
+        let hash_type = try!(read_header_line(reader, "can't read hash type"));
+        try!(check_hash_type(&hash_type));
+        let value = try!(read_header_line(reader, "can't read signature"));
+        signatures.push(Signature {
+            key_name_with_rev: key_name_with_rev,
+            value: try!(decode_signature(&value)),
+        });
+        try!(read_header_line(reader, "can't find end of header"));
+        hash_type
+    } else if format_version == HART_MULTISIG_FORMAT_VERSION {
+        let hash_type = try!(read_header_line(reader, "can't read hash type"));
+        try!(check_hash_type(&hash_type));
+        loop {
+            let line = try!(read_header_line(reader, "can't find end of header"));
+            if line.is_empty() {
+                break;
             }
-            Ok(_) => {
-                if buffer.trim() != HART_FORMAT_VERSION {
-                    let msg = format!("Unsupported format version: {}", &buffer.trim());
-                    return Err(Error::CryptoError(msg));
+            let mut parts = line.split_whitespace();
+            let (key_name_with_rev, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.to_string(), value),
+                _ => {
+                    return Err(Error::CryptoError(format!("Corrupt payload, can't read \
+                                                           signature: {}",
+                                                          line)))
                 }
-            }
-            Err(e) => return Err(Error::from(e)),
-
-        };
-        buffer.trim().to_string()
-    };
-    let name_with_rev = {
-        let mut buffer = String::new();
-        if try!(reader.read_line(&mut buffer)) <= 0 {
-            return Err(Error::CryptoError("Corrupt payload, can't read origin key name"
+            };
+            try!(parse_name_with_rev(&key_name_with_rev));
+            signatures.push(Signature {
+                key_name_with_rev: key_name_with_rev,
+                value: try!(decode_signature(value)),
+            });
+        }
+        if signatures.is_empty() {
+            return Err(Error::CryptoError("Corrupt payload, artifact has no signatures"
                 .to_string()));
         }
-        try!(parse_name_with_rev(buffer.trim()));
-        buffer.trim().to_string()
+        hash_type
+    } else {
+        let msg = format!("Unsupported format version: {}", &format_version);
+        return Err(Error::CryptoError(msg));
     };
-    Ok(name_with_rev)
+    Ok(ArtifactHeader {
+        format_version: format_version,
+        hash_type: hash_type,
+        signatures: signatures,
+    })
+}
+
+fn check_hash_type(hash_type: &str) -> Result<()> {
+    if hash_type != SIG_HASH_TYPE {
+        let msg = format!("Unsupported signature type: {}", hash_type);
+        return Err(Error::CryptoError(msg));
+    }
+    Ok(())
+}
+
+/// Write the header, in the `HART-1` format for a single signature so older tools can still
+/// read the artifact, and in the `HART-2` format otherwise.
+fn write_header<W: Write>(writer: &mut W, header: &ArtifactHeader) -> Result<()> {
+    if header.signatures.len() == 1 {
+        let signature = &header.signatures[0];
+        try!(write!(writer,
+                    "{}\n{}\n{}\n{}\n\n",
+                    HART_FORMAT_VERSION,
+                    signature.key_name_with_rev,
+                    header.hash_type,
+                    signature.value.to_base64(STANDARD)));
+    } else {
+        try!(write!(writer, "{}\n{}\n", HART_MULTISIG_FORMAT_VERSION, header.hash_type));
+        for signature in header.signatures.iter() {
+            try!(write!(writer,
+                        "{} {}\n",
+                        signature.key_name_with_rev,
+                        signature.value.to_base64(STANDARD)));
+        }
+        try!(write!(writer, "\n"));
+    }
+    Ok(())
+}
+
+fn temp_path(dst: &Path) -> PathBuf {
+    let file_name = dst.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or(String::new());
+    dst.with_file_name(format!(".{}.tmp", file_name))
 }
 
 #[cfg(test)]
@@ -217,7 +482,7 @@ mod test {
     use tempdir::TempDir;
 
     use super::*;
-    use super::super::SigKeyPair;
+    use super::super::{RevocationList, SigKeyPair};
     use super::super::test_support::*;
 
     #[test]
@@ -415,4 +680,85 @@ mod test {
         reader.read_to_string(&mut buffer).unwrap();
         assert_eq!(buffer.as_bytes(), "harty goodness".as_bytes());
     }
+
+    #[test]
+    fn append_signature_and_verify_with_policy() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let origin = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let release = SigKeyPair::generate_pair_for_origin("release", cache.path()).unwrap();
+        let src = cache.path().join("src.in");
+        let dst = cache.path().join("src.signed");
+        let mut f = File::create(&src).unwrap();
+        f.write_all("harty goodness".as_bytes()).unwrap();
+        sign(&src, &dst, &origin).unwrap();
+        append_signature(&dst, &dst, &release, cache.path()).unwrap();
+
+        let header = get_artifact_header(&dst).unwrap();
+        assert_eq!(header.format_version, "HART-2");
+        assert_eq!(artifact_signers(&dst).unwrap(),
+                   vec![origin.name_with_rev(), release.name_with_rev()]);
+        assert_eq!(artifact_signer(&dst).unwrap(), origin.name_with_rev());
+
+        let (name_with_rev, _) = verify(&dst, cache.path()).unwrap();
+        assert_eq!(name_with_rev, origin.name_with_rev());
+        let policy = "@origin+release|qa".parse().unwrap();
+        let (signers, _) = verify_with_policy(&dst, cache.path(), &policy).unwrap();
+        assert_eq!(signers, vec![origin.name_with_rev(), release.name_with_rev()]);
+
+        let mut buffer = String::new();
+        let mut reader = get_archive_reader(&dst).unwrap();
+        reader.read_to_string(&mut buffer).unwrap();
+        assert_eq!(buffer.as_bytes(), "harty goodness".as_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "Artifact is not signed with release")]
+    fn verify_with_policy_missing_signature() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let dst = cache.path().join("signed.dat");
+        sign(&fixture("signme.dat"), &dst, &pair).unwrap();
+
+        let policy = "@origin+release".parse().unwrap();
+        verify_with_policy(&dst, cache.path(), &policy).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Artifact is already signed with")]
+    fn append_signature_twice() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let dst = cache.path().join("signed.dat");
+        sign(&fixture("signme.dat"), &dst, &pair).unwrap();
+
+        append_signature(&dst, &dst, &pair, cache.path()).unwrap();
+    }
+
+    #[test]
+    fn signature_policy_from_str() {
+        let policy: SignaturePolicy = "@origin+release|qa".parse().unwrap();
+        assert_eq!(policy,
+                   SignaturePolicy::All(vec![SignaturePolicy::Origin,
+                                             SignaturePolicy::Any(vec![
+                        SignaturePolicy::Key("release".to_string()),
+                        SignaturePolicy::Key("qa".to_string())])]));
+        assert_eq!(policy.to_string(), "@origin+release|qa");
+        assert_eq!("@origin".parse::<SignaturePolicy>().unwrap(),
+                   SignaturePolicy::Origin);
+        assert!("@origin+".parse::<SignaturePolicy>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "has been revoked")]
+    fn verify_revoked_key() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let dst = cache.path().join("signed.dat");
+        sign(&fixture("signme.dat"), &dst, &pair).unwrap();
+        // A key may revoke itself
+        let content = RevocationList::sign(&pair, &[pair.name_with_rev()]).unwrap();
+        RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+
+        verify(&dst, cache.path()).unwrap();
+    }
 }
@@ -41,6 +41,8 @@ lazy_static! {
 }
 
 pub mod box_key_pair;
+pub mod passphrase;
+pub mod revocation_list;
 pub mod sym_key;
 pub mod sig_key_pair;
 
@@ -323,6 +325,14 @@ fn read_key_bytes(keyfile: &Path) -> Result<Vec<u8>> {
         }
     };
 
+    if passphrase::is_encrypted(&s[start_index..]) {
+        let prompt = format!("Passphrase for {}: ", keyfile.display());
+        let pass = try!(passphrase::get(&prompt));
+        return passphrase::decrypt(&s[start_index..], &pass).map_err(|e| {
+            passphrase::forget();
+            e
+        });
+    }
     match s[start_index..].as_bytes().from_base64() {
         Ok(keybytes) => Ok(keybytes),
         Err(e) => {
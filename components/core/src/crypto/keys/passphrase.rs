@@ -0,0 +1,240 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Passphrase protection for secret keys at rest.
+//!
+//! An encrypted secret key file keeps the usual version, name with revision and empty lines, but
+//! its body holds the key encrypted with a key derived from a passphrase:
+//!
+//! ```text
+//! SIG-SEC-1
+//! core-20160810182414
+//!
+//! ENCRYPTED-1:<Base64 salt>:<Base64 nonce>:<Base64 ciphertext>
+//! ```
+//!
+//! The key is derived with scrypt (`crypto_pwhash_scryptsalsa208sha256`) at its interactive
+//! limits, and the secret key is sealed with `crypto_secretbox`. Encrypted keys are decrypted as
+//! they are read, with the passphrase from the `HAB_KEY_PASSPHRASE` environment variable, or
+//! asked for on the terminal once per process.
+
+use std::io::{self, Write};
+use std::mem;
+use std::sync::Mutex;
+
+use libc;
+use rustc_serialize::base64::{STANDARD, ToBase64, FromBase64};
+use sodiumoxide::crypto::pwhash;
+use sodiumoxide::crypto::secretbox;
+
+use env as henv;
+use error::{Error, Result};
+use super::super::KEY_PASSPHRASE_ENV_VAR;
+
+/// The start of the body of a passphrase encrypted key
+pub static ENCRYPTED_KEY_PREFIX: &'static str = "ENCRYPTED-1:";
+
+lazy_static! {
+    // The passphrase asked for on the terminal, so it is only asked for once
+    static ref PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+}
+
+/// True if a key file body is passphrase encrypted.
+pub fn is_encrypted(body: &str) -> bool {
+    body.trim().starts_with(ENCRYPTED_KEY_PREFIX)
+}
+
+/// Encrypts raw key bytes with a passphrase, returning the key file body.
+pub fn encrypt(key_bytes: &[u8], passphrase: &str) -> Result<String> {
+    let salt = pwhash::gen_salt();
+    let key = try!(derive_key(passphrase, &salt));
+    let nonce = secretbox::gen_nonce();
+    let ciphertext = secretbox::seal(key_bytes, &nonce, &key);
+    Ok(format!("{}{}:{}:{}",
+               ENCRYPTED_KEY_PREFIX,
+               salt.as_ref().to_base64(STANDARD),
+               nonce.as_ref().to_base64(STANDARD),
+               ciphertext.to_base64(STANDARD)))
+}
+
+/// Decrypts a passphrase encrypted key file body, returning the raw key bytes.
+///
+/// # Errors
+///
+/// * If the body is malformed
+/// * If the passphrase is wrong
+pub fn decrypt(body: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let body = body.trim();
+    if !is_encrypted(body) {
+        return Err(Error::CryptoError("Key is not passphrase encrypted".to_string()));
+    }
+    let parts: Vec<Vec<u8>> = try!(body[ENCRYPTED_KEY_PREFIX.len()..]
+        .split(':')
+        .map(|p| p.as_bytes().from_base64())
+        .collect::<::std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::CryptoError(format!("Can't decode encrypted key: {}", e))));
+    if parts.len() != 3 {
+        return Err(Error::CryptoError("Malformed encrypted key".to_string()));
+    }
+    let salt = match pwhash::Salt::from_slice(&parts[0]) {
+        Some(salt) => salt,
+        None => return Err(Error::CryptoError("Invalid salt in encrypted key".to_string())),
+    };
+    let nonce = match secretbox::Nonce::from_slice(&parts[1]) {
+        Some(nonce) => nonce,
+        None => return Err(Error::CryptoError("Invalid nonce in encrypted key".to_string())),
+    };
+    let key = try!(derive_key(passphrase, &salt));
+    match secretbox::open(&parts[2], &nonce, &key) {
+        Ok(key_bytes) => Ok(key_bytes),
+        Err(_) => Err(Error::CryptoError("Can't decrypt secret key, is the passphrase right?"
+            .to_string())),
+    }
+}
+
+/// Encrypts the body of a key file's contents, leaving its header as it is. Contents which are
+/// already encrypted are returned unchanged.
+pub fn encrypt_key_str(content: &str, passphrase: &str) -> Result<String> {
+    let index = match content.find("\n\n") {
+        Some(i) => i + 2,
+        None => return Err(Error::CryptoError("Malformed key contents".to_string())),
+    };
+    let (header, body) = content.split_at(index);
+    if is_encrypted(body) {
+        return Ok(content.to_string());
+    }
+    let key_bytes = match body.trim().as_bytes().from_base64() {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(Error::CryptoError(format!("Can't read raw key: {}", e))),
+    };
+    Ok(format!("{}{}", header, try!(encrypt(&key_bytes, passphrase))))
+}
+
+/// Returns the passphrase for reading encrypted keys, from `HAB_KEY_PASSPHRASE` or asked for on
+/// the terminal.
+///
+/// # Errors
+///
+/// * If the environment variable isn't set and standard input isn't a terminal
+pub fn get(prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = henv::var(KEY_PASSPHRASE_ENV_VAR) {
+        return Ok(passphrase);
+    }
+    let mut cached = PASSPHRASE.lock().expect("Passphrase lock poisoned");
+    if let Some(ref passphrase) = *cached {
+        return Ok(passphrase.clone());
+    }
+    let passphrase = try!(ask(prompt));
+    *cached = Some(passphrase.clone());
+    Ok(passphrase)
+}
+
+/// Returns a passphrase for encrypting a key, from `HAB_KEY_PASSPHRASE` or asked for twice on
+/// the terminal.
+pub fn get_new() -> Result<String> {
+    if let Ok(passphrase) = henv::var(KEY_PASSPHRASE_ENV_VAR) {
+        return Ok(passphrase);
+    }
+    let passphrase = try!(ask("New passphrase: "));
+    if passphrase.is_empty() {
+        return Err(Error::CryptoError("Passphrase can't be empty".to_string()));
+    }
+    if try!(ask("Repeat the passphrase: ")) != passphrase {
+        return Err(Error::CryptoError("Passphrases don't match".to_string()));
+    }
+    Ok(passphrase)
+}
+
+/// Forgets a passphrase asked for on the terminal, so a wrong one isn't used again.
+pub fn forget() {
+    *PASSPHRASE.lock().expect("Passphrase lock poisoned") = None;
+}
+
+fn derive_key(passphrase: &str, salt: &pwhash::Salt) -> Result<secretbox::Key> {
+    let mut key = secretbox::Key([0; secretbox::KEYBYTES]);
+    {
+        let secretbox::Key(ref mut kb) = key;
+        if pwhash::derive_key(kb,
+                              passphrase.as_bytes(),
+                              salt,
+                              pwhash::OPSLIMIT_INTERACTIVE,
+                              pwhash::MEMLIMIT_INTERACTIVE)
+            .is_err() {
+            return Err(Error::CryptoError("Can't derive a key from the passphrase".to_string()));
+        }
+    }
+    Ok(key)
+}
+
+// Asks on standard error, so a passphrase prompt doesn't end up in piped output, and reads from
+// standard input with echo turned off.
+fn ask(prompt: &str) -> Result<String> {
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } != 1 {
+        return Err(Error::CryptoError(format!("A passphrase is required, set {} or run \
+                                               from a terminal",
+                                              KEY_PASSPHRASE_ENV_VAR)));
+    }
+    let mut stderr = io::stderr();
+    try!(write!(stderr, "{}", prompt));
+    try!(stderr.flush());
+
+    let mut term: libc::termios = unsafe { mem::zeroed() };
+    let echo_off = unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut term) } == 0;
+    let original = term;
+    if echo_off {
+        term.c_lflag &= !libc::ECHO;
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term) };
+    }
+    let mut line = String::new();
+    let read = io::stdin().read_line(&mut line);
+    if echo_off {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original) };
+    }
+    try!(writeln!(stderr, ""));
+    try!(read);
+    Ok(line.trim_right_matches(|c| c == '\r' || c == '\n').to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let body = encrypt(b"not much of a secret", "hunter2").unwrap();
+        assert!(is_encrypted(&body));
+        assert_eq!(decrypt(&body, "hunter2").unwrap(), b"not much of a secret".to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "is the passphrase right?")]
+    fn decrypt_wrong_passphrase() {
+        let body = encrypt(b"not much of a secret", "hunter2").unwrap();
+        decrypt(&body, "hunter3").unwrap();
+    }
+
+    #[test]
+    fn encrypt_key_str_keeps_header() {
+        let content = "SYM-SEC-1\nbeyonce-20160504220722\n\n\
+                       RCFaO84j41GmrzWddxMdsXpGdn3iuIy7Mw3xYrjPLsE=";
+        let encrypted = encrypt_key_str(content, "hunter2").unwrap();
+        assert!(encrypted.starts_with("SYM-SEC-1\nbeyonce-20160504220722\n\nENCRYPTED-1:"));
+        assert_eq!(encrypt_key_str(&encrypted, "hunter2").unwrap(), encrypted);
+
+        let body = &encrypted[encrypted.find("\n\n").unwrap() + 2..];
+        let expected = "RCFaO84j41GmrzWddxMdsXpGdn3iuIy7Mw3xYrjPLsE=".as_bytes();
+        assert_eq!(decrypt(body, "hunter2").unwrap(),
+                   ::rustc_serialize::base64::FromBase64::from_base64(expected).unwrap());
+    }
+}
@@ -0,0 +1,401 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Revocation lists for origin keys.
+//!
+//! A revocation list names origin key revisions which must no longer be trusted, and is signed
+//! with a key of the same origin:
+//!
+//! ```text
+//! SIG-RVK-1
+//! core-20160810182414
+//!
+//! <Base64 signed message>
+//! ```
+//!
+//! The signed message holds the origin, the list's revision, and one revoked key name with
+//! revision per line. A list may only revoke revisions up to and including the one which signed
+//! it, so a leaked key can't be used to revoke the keys which replaced it.
+//!
+//! Lists are cached as `<origin>-<revision>.rvk` in the key cache path. Revocations are never
+//! undone: a key is revoked if any cached list for its origin names it, and a list signed by a
+//! revoked key is refused.
+
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use rustc_serialize::base64::{STANDARD, ToBase64, FromBase64};
+
+use error::{Error, Result};
+use super::{mk_key_filename, mk_revision_string, parse_name_with_rev, KEYFILE_RE};
+use super::sig_key_pair::SigKeyPair;
+use super::super::{REVOCATION_LIST_SUFFIX, REVOCATION_LIST_VERSION};
+
+/// A verified list of revoked origin key revisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevocationList {
+    /// The origin whose keys are revoked, ex: "core"
+    pub origin: String,
+    /// The revision of the list, which is a timestamp, ex: "20161018120000"
+    pub revision: String,
+    /// The name with revision of the key which signed the list
+    pub signer: String,
+    /// The names with revision of the revoked keys
+    pub revoked: Vec<String>,
+}
+
+impl RevocationList {
+    /// Signs a new revocation list for the pair's origin, returning its contents.
+    ///
+    /// # Errors
+    ///
+    /// * If the pair has no secret key
+    /// * If a revoked key belongs to another origin, or is newer than the pair
+    pub fn sign(pair: &SigKeyPair, revoked: &[String]) -> Result<String> {
+        try!(Self::check_revoked(&pair.name, &pair.rev, revoked));
+        let revision = try!(mk_revision_string());
+        let mut message = format!("{}\n{}\n", pair.name, revision);
+        for name_with_rev in revoked {
+            message.push_str(name_with_rev);
+            message.push('\n');
+        }
+        let signed = try!(pair.sign(message.as_bytes()));
+        Ok(format!("{}\n{}\n\n{}",
+                   REVOCATION_LIST_VERSION,
+                   pair.name_with_rev(),
+                   signed.to_base64(STANDARD)))
+    }
+
+    /// Parses a revocation list and verifies its signature with the signer's public key from the
+    /// key cache.
+    ///
+    /// # Errors
+    ///
+    /// * If the list is malformed, or its version is unsupported
+    /// * If the signer's public key isn't in the key cache, or the signature doesn't verify
+    /// * If the list revokes keys of another origin, or newer than its signer
+    pub fn parse_str<P: AsRef<Path> + ?Sized>(content: &str, cache_key_path: &P) -> Result<Self> {
+        let (signer, signed) = try!(Self::parse_header(content));
+        let (signer_name, signer_rev) = try!(parse_name_with_rev(&signer));
+        let pair = try!(SigKeyPair::get_public_pair_for(&signer, cache_key_path));
+        let message = match String::from_utf8(try!(pair.verify(&signed))) {
+            Ok(message) => message,
+            Err(_) => return Err(Error::CryptoError("Error parsing revocation list".to_string())),
+        };
+
+        let mut lines = message.lines();
+        let origin = lines.next().unwrap_or("").to_string();
+        let revision = lines.next().unwrap_or("").to_string();
+        if origin != signer_name {
+            return Err(Error::CryptoError(format!("Revocation list for {} is signed by {}",
+                                                  origin,
+                                                  signer)));
+        }
+        let revoked: Vec<String> = lines.filter(|l| !l.is_empty()).map(|l| l.to_string()).collect();
+        try!(Self::check_revoked(&origin, &signer_rev, &revoked));
+        Ok(RevocationList {
+            origin: origin,
+            revision: revision,
+            signer: signer,
+            revoked: revoked,
+        })
+    }
+
+    /// Returns the name with revision of the key which signed a revocation list, without verifying
+    /// it, so the signer's public key can be fetched first.
+    ///
+    /// # Errors
+    ///
+    /// * If the list is malformed, or its version is unsupported
+    pub fn signer_of(content: &str) -> Result<String> {
+        let (signer, _) = try!(Self::parse_header(content));
+        Ok(signer)
+    }
+
+    /// Verifies a revocation list and writes it to the key cache.
+    ///
+    /// # Errors
+    ///
+    /// * If the list doesn't verify, see `parse_str`
+    /// * If the list is signed by a key which is already revoked
+    /// * If a different list with the same revision is already cached
+    pub fn write_file_from_str<P: AsRef<Path> + ?Sized>(content: &str,
+                                                        cache_key_path: &P)
+                                                        -> Result<Self> {
+        let list = try!(Self::parse_str(content, cache_key_path));
+        if try!(Self::is_revoked(&list.signer, cache_key_path)) {
+            return Err(Error::CryptoError(format!("Revocation list is signed by revoked key {}",
+                                                  list.signer)));
+        }
+        let path = Self::get_list_path(&list.origin, &list.revision, cache_key_path);
+        if path.is_file() {
+            let mut existing = String::new();
+            try!(try!(File::open(&path)).read_to_string(&mut existing));
+            if existing != content {
+                return Err(Error::CryptoError(format!("Existing revocation list {} found but \
+                                                       its content is different",
+                                                      path.display())));
+            }
+            return Ok(list);
+        }
+        let tmp_path = path.with_file_name(format!(".{}-{}.{}.tmp",
+                                                   list.origin,
+                                                   list.revision,
+                                                   REVOCATION_LIST_SUFFIX));
+        {
+            let mut f = try!(File::create(&tmp_path));
+            try!(f.write_all(content.as_bytes()));
+            try!(f.sync_all());
+        }
+        try!(fs::rename(&tmp_path, &path));
+        Ok(list)
+    }
+
+    /// Returns every cached revocation list for an origin, oldest first.
+    pub fn get_lists_for<P: AsRef<Path> + ?Sized>(origin: &str,
+                                                  cache_key_path: &P)
+                                                  -> Result<Vec<Self>> {
+        let cache_key_path = cache_key_path.as_ref();
+        if !cache_key_path.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut paths = Vec::new();
+        for entry in try!(fs::read_dir(cache_key_path)) {
+            let entry = try!(entry);
+            let filename = entry.file_name().to_string_lossy().into_owned();
+            if let Some(caps) = KEYFILE_RE.captures(&filename) {
+                if caps.name("name") == Some(origin) &&
+                   caps.name("suffix") == Some(REVOCATION_LIST_SUFFIX) {
+                    paths.push(entry.path());
+                }
+            }
+        }
+        paths.sort();
+        let mut lists = Vec::new();
+        for path in paths {
+            // One unreadable list mustn't stop us checking keys against the others
+            match Self::read_file(&path, cache_key_path) {
+                Ok(list) => lists.push(list),
+                Err(e) => debug!("Skipping revocation list {}: {}", path.display(), e),
+            }
+        }
+        Ok(lists)
+    }
+
+    /// Returns every revoked revision of an origin's keys, sorted.
+    pub fn get_revoked_for<P: AsRef<Path> + ?Sized>(origin: &str,
+                                                    cache_key_path: &P)
+                                                    -> Result<Vec<String>> {
+        let mut revoked = Vec::new();
+        for list in try!(Self::get_lists_for(origin, cache_key_path)) {
+            revoked.extend(list.revoked);
+        }
+        revoked.sort();
+        revoked.dedup();
+        Ok(revoked)
+    }
+
+    /// True if a cached revocation list names the key.
+    pub fn is_revoked<P: AsRef<Path> + ?Sized>(name_with_rev: &str,
+                                               cache_key_path: &P)
+                                               -> Result<bool> {
+        let (name, _) = try!(parse_name_with_rev(name_with_rev));
+        let revoked = try!(Self::get_revoked_for(&name, cache_key_path));
+        Ok(revoked.iter().any(|r| r == name_with_rev))
+    }
+
+    pub fn get_list_path<P: AsRef<Path> + ?Sized>(origin: &str,
+                                                  revision: &str,
+                                                  cache_key_path: &P)
+                                                  -> PathBuf {
+        mk_key_filename(cache_key_path.as_ref(),
+                        &format!("{}-{}", origin, revision),
+                        REVOCATION_LIST_SUFFIX)
+    }
+
+    fn read_file(path: &Path, cache_key_path: &Path) -> Result<Self> {
+        let mut content = String::new();
+        try!(try!(File::open(path)).read_to_string(&mut content));
+        Self::parse_str(&content, cache_key_path)
+    }
+
+    /// Splits a revocation list into its signer and its signed message.
+    fn parse_header(content: &str) -> Result<(String, Vec<u8>)> {
+        let mut lines = content.lines();
+        match lines.next() {
+            Some(val) if val == REVOCATION_LIST_VERSION => (),
+            Some(val) => {
+                return Err(Error::CryptoError(format!("Unsupported revocation list version: {}",
+                                                      val)))
+            }
+            None => return Err(Error::CryptoError("Malformed revocation list".to_string())),
+        }
+        let signer = match lines.next() {
+            Some(val) => val.to_string(),
+            None => {
+                return Err(Error::CryptoError("Malformed revocation list, can't read signer"
+                    .to_string()))
+            }
+        };
+        let signed = match lines.nth(1).map(|val| val.as_bytes().from_base64()) {
+            Some(Ok(signed)) => signed,
+            Some(Err(e)) => {
+                return Err(Error::CryptoError(format!("Can't decode revocation list: {}", e)))
+            }
+            None => {
+                return Err(Error::CryptoError("Malformed revocation list, can't read body"
+                    .to_string()))
+            }
+        };
+        Ok((signer, signed))
+    }
+
+    fn check_revoked(origin: &str, signer_rev: &str, revoked: &[String]) -> Result<()> {
+        for name_with_rev in revoked {
+            let (name, rev) = try!(parse_name_with_rev(name_with_rev));
+            if name != origin {
+                return Err(Error::CryptoError(format!("Can't revoke {} in a revocation list \
+                                                       for {}",
+                                                      name_with_rev,
+                                                      origin)));
+            }
+            if rev.as_str() > signer_rev {
+                return Err(Error::CryptoError(format!("Can't revoke {}, it is newer than the \
+                                                       key signing the revocation list",
+                                                      name_with_rev)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+
+    use rustc_serialize::base64::{STANDARD, ToBase64};
+    use sodiumoxide::crypto::sign;
+    use tempdir::TempDir;
+
+    use super::RevocationList;
+    use super::super::sig_key_pair::SigKeyPair;
+
+    // Key revisions are timestamps to the second, so write keys with fixed revisions
+    fn pair_with_rev(name: &str, rev: &str, cache: &Path) -> SigKeyPair {
+        let (pk, sk) = sign::gen_keypair();
+        let public = format!("SIG-PUB-1\n{}-{}\n\n{}", name, rev, pk[..].to_base64(STANDARD));
+        let secret = format!("SIG-SEC-1\n{}-{}\n\n{}", name, rev, sk[..].to_base64(STANDARD));
+        SigKeyPair::write_file_from_str(&public, cache).unwrap();
+        SigKeyPair::write_file_from_str(&secret, cache).unwrap().0
+    }
+
+    #[test]
+    fn sign_write_and_check() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let old = pair_with_rev("unicorn", "20160101000000", cache.path());
+        let new = pair_with_rev("unicorn", "20160601000000", cache.path());
+        assert!(!RevocationList::is_revoked(&old.name_with_rev(), cache.path()).unwrap());
+
+        let content = RevocationList::sign(&new, &[old.name_with_rev()]).unwrap();
+        let list = RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+        assert_eq!(list.origin, "unicorn");
+        assert_eq!(list.signer, new.name_with_rev());
+        assert!(RevocationList::get_list_path("unicorn", &list.revision, cache.path()).is_file());
+        assert!(RevocationList::is_revoked(&old.name_with_rev(), cache.path()).unwrap());
+        assert!(!RevocationList::is_revoked(&new.name_with_rev(), cache.path()).unwrap());
+        // writing the same list again is fine
+        RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "newer than the key signing the revocation list")]
+    fn cannot_revoke_newer_keys() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let old = pair_with_rev("unicorn", "20160101000000", cache.path());
+        let new = pair_with_rev("unicorn", "20160601000000", cache.path());
+
+        RevocationList::sign(&old, &[new.name_with_rev()]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Can\\'t revoke")]
+    fn cannot_revoke_other_origins() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = pair_with_rev("unicorn", "20160601000000", cache.path());
+
+        RevocationList::sign(&pair, &["dragon-20160101000000".to_string()]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Revocation list is signed by revoked key")]
+    fn refuse_lists_signed_by_revoked_keys() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let old = pair_with_rev("unicorn", "20160101000000", cache.path());
+        let new = pair_with_rev("unicorn", "20160601000000", cache.path());
+        let content = RevocationList::sign(&new, &[new.name_with_rev()]).unwrap();
+        RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+
+        let content = RevocationList::sign(&new, &[old.name_with_rev()]).unwrap();
+        RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Verification failed")]
+    fn refuse_lists_signed_by_other_keys() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = pair_with_rev("unicorn", "20160601000000", cache.path());
+        let other = pair_with_rev("unicorn", "20160701000000", cache.path());
+        let content = RevocationList::sign(&other, &[]).unwrap();
+        let forged = content.replace(&other.name_with_rev(), &pair.name_with_rev());
+
+        RevocationList::write_file_from_str(&forged, cache.path()).unwrap();
+    }
+
+    #[test]
+    fn ignore_other_origins_lists() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = pair_with_rev("unicorn", "20160601000000", cache.path());
+        let mut f = File::create(cache.path().join("dragon-20160601000000.rvk")).unwrap();
+        f.write_all(b"garbage").unwrap();
+
+        assert!(!RevocationList::is_revoked(&pair.name_with_rev(), cache.path()).unwrap());
+    }
+
+    #[test]
+    fn skip_corrupt_cached_lists() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let old = pair_with_rev("unicorn", "20160101000000", cache.path());
+        let new = pair_with_rev("unicorn", "20160601000000", cache.path());
+        let content = RevocationList::sign(&new, &[old.name_with_rev()]).unwrap();
+        RevocationList::write_file_from_str(&content, cache.path()).unwrap();
+        let mut f = File::create(cache.path().join("unicorn-20160701000000.rvk")).unwrap();
+        f.write_all(b"garbage").unwrap();
+
+        assert!(RevocationList::is_revoked(&old.name_with_rev(), cache.path()).unwrap());
+        assert!(!RevocationList::is_revoked(&new.name_with_rev(), cache.path()).unwrap());
+    }
+
+    #[test]
+    fn signer_of() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = pair_with_rev("unicorn", "20160601000000", cache.path());
+        let content = RevocationList::sign(&pair, &[]).unwrap();
+
+        assert_eq!(RevocationList::signer_of(&content).unwrap(), pair.name_with_rev());
+        assert!(RevocationList::signer_of("garbage").is_err());
+    }
+}
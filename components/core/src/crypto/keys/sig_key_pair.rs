@@ -24,7 +24,8 @@ use sodiumoxide::randombytes::randombytes;
 
 use error::{Error, Result};
 use super::{get_key_revisions, mk_key_filename, mk_revision_string, parse_name_with_rev,
-            read_key_bytes, write_keypair_files, KeyPair, KeyType, PairType, TmpKeyfile};
+            passphrase, read_key_bytes, write_keypair_files, KeyPair, KeyType, PairType,
+            TmpKeyfile};
 use super::super::{PUBLIC_KEY_SUFFIX, PUBLIC_SIG_KEY_VERSION, SECRET_SIG_KEY_SUFFIX,
                    SECRET_SIG_KEY_VERSION, hash};
 
@@ -87,12 +88,14 @@ impl SigKeyPair {
         Ok(key_pairs)
     }
 
+    /// Loads a pair, with whichever of its keys are in the key cache. A key which is there but
+    /// can't be read, such as an encrypted secret key with the wrong passphrase, is an error.
     pub fn get_pair_for<P: AsRef<Path> + ?Sized>(name_with_rev: &str,
                                                  cache_key_path: &P)
                                                  -> Result<Self> {
         let (name, rev) = try!(parse_name_with_rev(&name_with_rev));
-        let pk = match Self::get_public_key(name_with_rev, cache_key_path.as_ref()) {
-            Ok(k) => Some(k),
+        let pk = match Self::get_public_key_path(name_with_rev, cache_key_path) {
+            Ok(_) => Some(try!(Self::get_public_key(name_with_rev, cache_key_path.as_ref()))),
             Err(e) => {
                 // Not an error, just continue
                 debug!("Can't find public key for name_with_rev {}: {}",
@@ -101,8 +104,8 @@ impl SigKeyPair {
                 None
             }
         };
-        let sk = match Self::get_secret_key(name_with_rev, cache_key_path.as_ref()) {
-            Ok(k) => Some(k),
+        let sk = match Self::get_secret_key_path(name_with_rev, cache_key_path) {
+            Ok(_) => Some(try!(Self::get_secret_key(name_with_rev, cache_key_path.as_ref()))),
             Err(e) => {
                 // Not an error, just continue
                 debug!("Can't find secret key for name_with_rev {}: {}",
@@ -119,19 +122,41 @@ impl SigKeyPair {
         Ok(SigKeyPair::new(name, rev, pk, sk))
     }
 
-    pub fn get_latest_pair_for<P: AsRef<Path> + ?Sized>(name: &str,
+    /// Loads only the public key of a pair, for verifying. Unlike `get_pair_for`, this never
+    /// reads the secret key, so it never asks for the passphrase of an encrypted one.
+    pub fn get_public_pair_for<P: AsRef<Path> + ?Sized>(name_with_rev: &str,
                                                         cache_key_path: &P)
                                                         -> Result<Self> {
-        let mut all = try!(Self::get_pairs_for(name, cache_key_path));
-        match all.len() {
-            0 => {
-                let msg = format!("No revisions found for {} sig key", name);
-                return Err(Error::CryptoError(msg));
+        let (name, rev) = try!(parse_name_with_rev(&name_with_rev));
+        match Self::get_public_key(name_with_rev, cache_key_path.as_ref()) {
+            Ok(pk) => Ok(SigKeyPair::new(name, rev, Some(pk), None)),
+            Err(e) => {
+                debug!("Can't find public key for name_with_rev {}: {}",
+                       name_with_rev,
+                       e);
+                let msg = format!("Public key is required but not present for {}",
+                                  name_with_rev);
+                Err(Error::CryptoError(msg))
             }
-            _ => Ok(all.remove(0)),
         }
     }
 
+    pub fn get_latest_pair_for<P: AsRef<Path> + ?Sized>(name: &str,
+                                                        cache_key_path: &P)
+                                                        -> Result<Self> {
+        let name_with_rev = try!(Self::get_latest_name_with_rev(name, cache_key_path.as_ref()));
+        Self::get_pair_for(&name_with_rev, cache_key_path)
+    }
+
+    /// Loads only the public key of the newest pair for a name, so it never asks for the
+    /// passphrase of an encrypted secret key.
+    pub fn get_latest_public_pair_for<P: AsRef<Path> + ?Sized>(name: &str,
+                                                               cache_key_path: &P)
+                                                               -> Result<Self> {
+        let name_with_rev = try!(Self::get_latest_name_with_rev(name, cache_key_path.as_ref()));
+        Self::get_public_pair_for(&name_with_rev, cache_key_path)
+    }
+
     pub fn get_public_key_path<P: AsRef<Path> + ?Sized>(key_with_rev: &str,
                                                         cache_key_path: &P)
                                                         -> Result<PathBuf> {
@@ -240,6 +265,16 @@ impl SigKeyPair {
             PairType::Public => PUBLIC_KEY_SUFFIX,
             PairType::Secret => SECRET_SIG_KEY_SUFFIX,
         };
+        // An encrypted secret key has to be readable before it goes in the key cache, so ask for
+        // its passphrase first
+        if pair_type == PairType::Secret && passphrase::is_encrypted(key_body) {
+            let prompt = format!("Passphrase for {}: ", name_with_rev);
+            let pass = try!(passphrase::get(&prompt));
+            try!(passphrase::decrypt(key_body, &pass).map_err(|e| {
+                passphrase::forget();
+                e
+            }));
+        }
         let keyfile = mk_key_filename(cache_key_path.as_ref(), &name_with_rev, &suffix);
         let tmpfile = {
             let mut t = keyfile.clone();
@@ -389,6 +424,42 @@ impl SigKeyPair {
         Ok((pair_type, name_with_rev.to_string(), key_body.to_string()))
     }
 
+    /// Signs a message with the secret key of this pair, returning the signed message.
+    ///
+    /// # Errors
+    ///
+    /// * If the pair has no secret key
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        Ok(sign::sign(data, try!(self.secret())))
+    }
+
+    /// Verifies a signed message with the public key of this pair, returning the original
+    /// message.
+    ///
+    /// # Errors
+    ///
+    /// * If the pair has no public key
+    /// * If the signature was not made by this pair
+    pub fn verify(&self, signed_data: &[u8]) -> Result<Vec<u8>> {
+        match sign::verify(signed_data, try!(self.public())) {
+            Ok(data) => Ok(data),
+            Err(_) => {
+                Err(Error::CryptoError(format!("Verification failed for {}",
+                                               self.name_with_rev())))
+            }
+        }
+    }
+
+    fn get_latest_name_with_rev(name: &str, cache_key_path: &Path) -> Result<String> {
+        match try!(get_key_revisions(name, cache_key_path)).into_iter().next() {
+            Some(name_with_rev) => Ok(name_with_rev),
+            None => {
+                let msg = format!("No revisions found for {} sig key", name);
+                Err(Error::CryptoError(msg))
+            }
+        }
+    }
+
     fn get_public_key(key_with_rev: &str, cache_key_path: &Path) -> Result<SigPublicKey> {
         let public_keyfile = mk_key_filename(cache_key_path, key_with_rev, PUBLIC_KEY_SUFFIX);
         let bytes = try!(read_key_bytes(&public_keyfile));
@@ -417,12 +488,12 @@ impl SigKeyPair {
 #[cfg(test)]
 mod test {
     use std::fs::{self, File};
-    use std::io::Read;
+    use std::io::{Read, Write};
 
     use tempdir::TempDir;
 
     use super::SigKeyPair;
-    use super::super::PairType;
+    use super::super::{passphrase, PairType};
     use super::super::super::test_support::*;
 
     static VALID_KEY: &'static str = "origin-key-valid-20160509190508.sig.key";
@@ -491,6 +562,25 @@ mod test {
         assert_eq!(pairs.len(), 2);
     }
 
+    #[test]
+    fn sign_and_verify() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let signed = pair.sign("I am the walrus".as_bytes()).unwrap();
+        let verified = pair.verify(&signed).unwrap();
+        assert_eq!(verified, "I am the walrus".as_bytes().to_vec());
+    }
+
+    #[test]
+    #[should_panic]
+    fn verify_with_wrong_pair() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let other = SigKeyPair::generate_pair_for_origin("narwhal", cache.path()).unwrap();
+        let signed = pair.sign("I am the walrus".as_bytes()).unwrap();
+        other.verify(&signed).unwrap();
+    }
+
     #[test]
     fn get_pair_for() {
         let cache = TempDir::new("key_cache").unwrap();
@@ -541,6 +631,23 @@ mod test {
         assert_eq!(latest.rev, p2.rev);
     }
 
+    #[test]
+    fn get_latest_public_pair_for_encrypted_secret() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let pair = SigKeyPair::generate_pair_for_origin("unicorn", cache.path()).unwrap();
+        let secret_keyfile = cache.path().join(format!("{}.sig.key", pair.name_with_rev()));
+        let mut content = String::new();
+        File::open(&secret_keyfile).unwrap().read_to_string(&mut content).unwrap();
+        let encrypted = passphrase::encrypt_key_str(&content, "hunter2").unwrap();
+        File::create(&secret_keyfile).unwrap().write_all(encrypted.as_bytes()).unwrap();
+
+        // Reading only the public key never asks for the passphrase
+        let latest = SigKeyPair::get_latest_public_pair_for("unicorn", cache.path()).unwrap();
+        assert_eq!(latest.name_with_rev(), pair.name_with_rev());
+        assert!(latest.public().is_ok());
+        assert!(latest.secret().is_err());
+    }
+
     #[test]
     #[should_panic(expected = "No revisions found for")]
     fn get_latest_pair_for_nonexistant() {
@@ -189,6 +189,39 @@
 //! is **not** a supported workflow for working with Habitat artifacts--they are signed for very
 //! important reasons.
 //!
+//! ## Countersigned Habitat artifacts
+//!
+//! An artifact can carry more signatures than the origin's, for example a countersignature from
+//! a release-approval key. Such an artifact uses the `HART-2` header, which has the hash type on
+//! its second line, then one line per signature holding the key name with revision and the Base64
+//! signed hash, separated by a space. The origin signature is always first, and an empty line
+//! ends the header:
+//!
+//! ```text
+//! HART-2
+//! BLAKE2b
+//! habitat-20160405144945 abc123...
+//! release-20160501120000 def456...
+//!
+//! <binary-blob>
+//! ```
+//!
+//! Every signature signs the same payload hash, so adding a signature only rewrites the header.
+//! An artifact with a single signature keeps the `HART-1` header.
+//!
+//! ## Passphrase encrypted secret keys
+//!
+//! The body of any secret key file may be encrypted with a passphrase, in which case it starts
+//! with `ENCRYPTED-1:`. Such keys are read like any other, once the passphrase is given through
+//! `HAB_KEY_PASSPHRASE` or on the terminal. See `keys::passphrase` for the format.
+//!
+//! ## Revocation lists
+//!
+//! A leaked origin key revision is revoked by a revocation list, signed with a newer key of the
+//! same origin, which names the revoked revisions. Lists are cached next to the keys, as
+//! `<origin>-<revision>.rvk`, and a signature by a revoked key never verifies. See
+//! `keys::revocation_list` for the format.
+//!
 //! ## Encrypted payloads
 //!
 //! The first 4 lines of an encrypted payload are as follows:
@@ -243,6 +276,9 @@ pub static SECRET_BOX_KEY_SUFFIX: &'static str = "box.key";
 /// The suffix on the end of a secret symmetric key file
 pub static SECRET_SYM_KEY_SUFFIX: &'static str = "sym.key";
 
+/// The suffix on the end of a cached origin key revocation list
+pub static REVOCATION_LIST_SUFFIX: &'static str = "rvk";
+
 /// The hashing function we're using during sign/verify
 /// See also: https://download.libsodium.org/doc/hashing/generic_hashing.html
 pub static SIG_HASH_TYPE: &'static str = "BLAKE2b";
@@ -251,11 +287,17 @@ pub static SIG_HASH_TYPE: &'static str = "BLAKE2b";
 /// at runtime. This is useful for testing.
 pub static CACHE_KEY_PATH_ENV_VAR: &'static str = "HAB_CACHE_KEY_PATH";
 
+/// This environment variable holds the passphrase for reading passphrase encrypted secret keys,
+/// and for encrypting them on export.
+pub static KEY_PASSPHRASE_ENV_VAR: &'static str = "HAB_KEY_PASSPHRASE";
+
 /// Create secret key files with these permissions
 static PUBLIC_KEY_PERMISSIONS: &'static str = "0400";
 static SECRET_KEY_PERMISSIONS: &'static str = "0400";
 
 pub static HART_FORMAT_VERSION: &'static str = "HART-1";
+/// The artifact format version for artifacts with more than one signature
+pub static HART_MULTISIG_FORMAT_VERSION: &'static str = "HART-2";
 pub static BOX_FORMAT_VERSION: &'static str = "BOX-1";
 
 pub const PUBLIC_SIG_KEY_VERSION: &'static str = "SIG-PUB-1";
@@ -263,8 +305,10 @@ pub const SECRET_SIG_KEY_VERSION: &'static str = "SIG-SEC-1";
 pub const PUBLIC_BOX_KEY_VERSION: &'static str = "BOX-PUB-1";
 pub const SECRET_BOX_KEY_VERSION: &'static str = "BOX-SEC-1";
 pub const SECRET_SYM_KEY_VERSION: &'static str = "SYM-SEC-1";
+pub const REVOCATION_LIST_VERSION: &'static str = "SIG-RVK-1";
 
 pub use self::keys::box_key_pair::BoxKeyPair;
+pub use self::keys::revocation_list::RevocationList;
 pub use self::keys::sym_key::SymKey;
 pub use self::keys::sig_key_pair::SigKeyPair;
 
@@ -53,6 +53,8 @@ pub enum Error {
     FileNotFound(String),
     /// Occurs when a package identifier string cannot be successfully parsed.
     InvalidPackageIdent(String),
+    /// Occurs when a package requirement string cannot be successfully parsed.
+    InvalidPackageIdentReq(String),
     /// Occurs when an improper http or https proxy value is given.
     InvalidProxyValue(String),
     /// Occurs when a service group string cannot be successfully parsed.
@@ -120,6 +122,12 @@ impl fmt::Display for Error {
                          origin/name (example: acme/redis)",
                         e)
             }
+            Error::InvalidPackageIdentReq(ref e) => {
+                format!("Invalid package requirement: {:?}. A valid requirement is an identifier \
+                         followed by comma separated version constraints (example: acme/redis \
+                         >= 3.0, < 3.2)",
+                        e)
+            }
             Error::InvalidProxyValue(ref e) => format!("Invalid proxy value: {:?}", e),
             Error::InvalidServiceGroup(ref e) => {
                 format!("Invalid service group: {:?}. A valid service group string is in the form \
@@ -176,6 +184,9 @@ impl error::Error for Error {
             Error::InvalidPackageIdent(_) => {
                 "Package identifiers must be in origin/name format (example: acme/redis)"
             }
+            Error::InvalidPackageIdentReq(_) => {
+                "Package requirements must be an identifier followed by version constraints"
+            }
             Error::InvalidProxyValue(_) => "Invalid proxy value",
             Error::InvalidServiceGroup(_) => {
                 "Service group strings must be in service.group format (example: redis.production)"
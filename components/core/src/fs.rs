@@ -35,8 +35,13 @@ pub const CACHE_SRC_PATH: &'static str = "hab/cache/src";
 pub const CACHE_SSL_PATH: &'static str = "hab/cache/ssl";
 /// The root path containing all locally installed packages
 pub const PKG_PATH: &'static str = "hab/pkgs";
+/// The path where package archives are unpacked before they are moved into place, inside
+/// `PKG_PATH` so that the move is a rename on the same filesystem
+pub const PKG_STAGING_PATH: &'static str = "hab/pkgs/.staging";
+/// The lock file held while a package is moved into place under `PKG_PATH`
+pub const PKG_INSTALL_LOCK_PATH: &'static str = "hab/pkgs/.install.lock";
 /// The root path containing all runtime service directories and files
-const SVC_PATH: &'static str = "hab/svc";
+pub const SVC_PATH: &'static str = "hab/svc";
 
 lazy_static! {
     static ref EUID: u32 = users::get_effective_uid();
@@ -147,6 +152,17 @@ pub fn svc_path(service_name: &str) -> PathBuf {
     Path::new("/").join(SVC_PATH).join(service_name)
 }
 
+/// Returns the path to the file holding the process id of a given service while it runs.
+pub fn svc_pid_file(service_name: &str) -> PathBuf {
+    svc_path(service_name).join("PID")
+}
+
+/// Returns the path to the file naming the package release a given service was started from,
+/// while it runs.
+pub fn svc_pkg_ident_file(service_name: &str) -> PathBuf {
+    svc_path(service_name).join("PKG_IDENT")
+}
+
 /// Returns the path to a given service's configuration.
 pub fn svc_config_path(service_name: &str) -> PathBuf {
     svc_path(service_name).join("config")
@@ -12,24 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::error;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::result;
 use std::str::{self, FromStr};
 
 use libarchive::writer;
 use libarchive::reader::{self, Reader};
-use libarchive::archive::{Entry, ReadFilter, ReadFormat};
+use libarchive::archive::{Entry, ExtractOption, ExtractOptions, ReadFilter, ReadFormat};
 use regex::Regex;
+use rustc_serialize::hex::ToHex;
+use sodiumoxide::randombytes::randombytes;
 
 use error::{Error, Result};
 use crypto::{artifact, hash};
-use package::{Identifiable, PackageIdent, MetaFile};
+use crypto::artifact::SignaturePolicy;
+use fs::{PKG_PATH, PKG_STAGING_PATH};
+use package::{split_lines, Identifiable, PackageIdent, MetaFile};
+use package::install::InstallLock;
 
 lazy_static! {
     static ref METAFILE_REGXS: HashMap<MetaFile, Regex> = {
         let mut map = HashMap::new();
+        map.insert(MetaFile::BuildHost, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::BuildHost)).unwrap());
+        map.insert(MetaFile::BuilderVersion, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::BuilderVersion)).unwrap());
         map.insert(MetaFile::CFlags, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::CFlags)).unwrap());
         map.insert(MetaFile::Config, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Config)).unwrap());
         map.insert(MetaFile::Deps, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Deps)).unwrap());
@@ -38,8 +48,12 @@ lazy_static! {
         map.insert(MetaFile::Ident, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Ident)).unwrap());
         map.insert(MetaFile::LdRunPath, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::LdRunPath)).unwrap());
         map.insert(MetaFile::LdFlags, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::LdFlags)).unwrap());
+        map.insert(MetaFile::Licenses, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Licenses)).unwrap());
         map.insert(MetaFile::Manifest, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Manifest)).unwrap());
         map.insert(MetaFile::Path, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Path)).unwrap());
+        map.insert(MetaFile::PlanCommit, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::PlanCommit)).unwrap());
+        map.insert(MetaFile::Shasum, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Shasum)).unwrap());
+        map.insert(MetaFile::Source, Regex::new(&format!(r"^hab/pkgs/([^/]+)/([^/]+)/([^/]+)/([^/]+)/{}$", MetaFile::Source)).unwrap());
         map
     };
 }
@@ -69,6 +83,22 @@ impl PackageArchive {
         hash::hash_file(&self.path)
     }
 
+    /// The host name of the machine the package was built on, if recorded.
+    pub fn build_host(&mut self) -> Result<Option<String>> {
+        match self.read_metadata(MetaFile::BuildHost) {
+            Ok(data) => Ok(data.cloned()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The version of the build program which built the package, if recorded.
+    pub fn builder_version(&mut self) -> Result<Option<String>> {
+        match self.read_metadata(MetaFile::BuilderVersion) {
+            Ok(data) => Ok(data.cloned()),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn cflags(&mut self) -> Result<Option<String>> {
         match self.read_metadata(MetaFile::CFlags) {
             Ok(data) => Ok(data.cloned()),
@@ -142,6 +172,20 @@ impl PackageArchive {
         }
     }
 
+    /// Returns the license identifiers of the packaged software, as declared in its plan. A
+    /// package without a `LICENSES` metafile has none.
+    ///
+    /// # Failures
+    ///
+    /// * If the archive cannot be read
+    pub fn licenses(&mut self) -> Result<Vec<String>> {
+        match self.read_metadata(MetaFile::Licenses) {
+            Ok(Some(data)) => Ok(split_lines(data)),
+            Ok(None) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn manifest(&mut self) -> Result<String> {
         match self.read_metadata(MetaFile::Manifest) {
             Ok(None) => Err(Error::MetaFileNotFound(MetaFile::Manifest)),
@@ -157,6 +201,31 @@ impl PackageArchive {
         }
     }
 
+    /// The git commit of the plan the package was built from, if it was built from a git
+    /// checkout.
+    pub fn plan_commit(&mut self) -> Result<Option<String>> {
+        match self.read_metadata(MetaFile::PlanCommit) {
+            Ok(data) => Ok(data.cloned()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The SHA-256 checksum of the packaged software's source, if recorded.
+    pub fn shasum(&mut self) -> Result<Option<String>> {
+        match self.read_metadata(MetaFile::Shasum) {
+            Ok(data) => Ok(data.cloned()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The URL the packaged software's source was downloaded from, if recorded.
+    pub fn source(&mut self) -> Result<Option<String>> {
+        match self.read_metadata(MetaFile::Source) {
+            Ok(data) => Ok(data.cloned()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// A plain string representation of the archive's file name.
     pub fn file_name(&self) -> String {
         self.path.file_name().unwrap().to_string_lossy().into_owned()
@@ -172,24 +241,76 @@ impl PackageArchive {
         artifact::verify(&self.path, cache_key_path)
     }
 
+    /// Verify the archive's signatures against a policy, returning the names with revision of
+    /// the keys the policy relied on, and the payload's hash.
+    ///
+    /// # Failures
+    ///
+    /// * Fails if the signatures don't meet the policy, or one it relies on doesn't verify
+    pub fn verify_with_policy<P: AsRef<Path>>(&self,
+                                              cache_key_path: &P,
+                                              policy: &SignaturePolicy)
+                                              -> Result<(Vec<String>, String)> {
+        artifact::verify_with_policy(&self.path, cache_key_path, policy)
+    }
+
     /// Given a package name and a path to a file as an `&str`, unpack
     /// the package.
     ///
+    /// The package is unpacked into a staging directory and only moved into place once its
+    /// `IDENT` and `MANIFEST` check out, so an interrupted unpack never leaves a partial package
+    /// behind. If the package is already installed, it is left as it is.
+    ///
     /// # Failures
     ///
     /// * If the package cannot be unpacked
+    /// * If the unpacked package is missing its `IDENT` or `MANIFEST`, or its `IDENT` doesn't
+    /// match where it was unpacked
     pub fn unpack(&self, fs_root_path: Option<&Path>) -> Result<()> {
         let root = fs_root_path.unwrap_or(Path::new("/"));
-        let tar_reader = try!(artifact::get_archive_reader(&self.path));
-        let mut builder = reader::Builder::new();
-        try!(builder.support_format(ReadFormat::Gnutar));
-        try!(builder.support_filter(ReadFilter::Xz));
-        let mut reader = try!(builder.open_stream(tar_reader));
-        let writer = writer::Disk::new();
-        try!(writer.set_standard_lookup());
-        try!(writer.write(&mut reader, Some(root.to_string_lossy().as_ref())));
-        try!(writer.close());
-        Ok(())
+        let staging = try!(StagingDir::new(root));
+        try!(extract(try!(artifact::get_archive_reader(&self.path)), &staging.path));
+        let (pkg_path, _) = try!(read_staged_metadata(&staging.path));
+        install_staged(root, &staging.path, &pkg_path)
+    }
+
+    /// Verify the archive's signatures against a policy and unpack it, returning the names with
+    /// revision of the keys the policy relied on.
+    ///
+    /// The signatures are checked against a hash of the payload before anything is written, and
+    /// the payload is then unpacked into a staging directory from the same open file, so what's
+    /// unpacked is what was verified. The payload is only decompressed once. The archive's
+    /// metadata is read from the unpacked files rather than from the archive again. The package
+    /// is only moved into place once its `IDENT` and `MANIFEST` check out; otherwise nothing is
+    /// left behind. If the package is already installed, it is left as it is.
+    ///
+    /// # Failures
+    ///
+    /// * If the signatures don't meet the policy, or one it relies on doesn't verify
+    /// * If the package cannot be unpacked
+    /// * If the unpacked package is missing its `IDENT` or `MANIFEST`, or its `IDENT` doesn't
+    /// match where it was unpacked
+    pub fn verify_and_unpack<P: AsRef<Path>>(&mut self,
+                                             fs_root_path: Option<&Path>,
+                                             cache_key_path: &P,
+                                             policy: &SignaturePolicy)
+                                             -> Result<Vec<String>> {
+        let root = fs_root_path.unwrap_or(Path::new("/"));
+        // Verify and extract from the one open file, so it can't be swapped in between
+        let (header, mut reader) = try!(artifact::get_header_and_archive_reader(&self.path));
+        let payload = try!(reader.seek(SeekFrom::Current(0)));
+        let computed_hash = try!(hash::hash_reader(&mut reader));
+        let signers = try!(artifact::verify_hash_with_policy(&header,
+                                                             &computed_hash,
+                                                             cache_key_path,
+                                                             policy));
+        try!(reader.seek(SeekFrom::Start(payload)));
+        let staging = try!(StagingDir::new(root));
+        try!(extract(reader, &staging.path));
+        let (pkg_path, metadata) = try!(read_staged_metadata(&staging.path));
+        try!(install_staged(root, &staging.path, &pkg_path));
+        self.metadata = Some(metadata);
+        Ok(signers)
     }
 
     fn read_deps(&mut self, file: MetaFile) -> Result<Vec<PackageIdent>> {
@@ -266,6 +387,143 @@ impl PackageArchive {
     }
 }
 
+// A directory a package is unpacked into before it's moved into place, kept on the same
+// filesystem as the installed packages so the move is a rename. It's removed when dropped,
+// along with whatever was left in it.
+struct StagingDir {
+    path: PathBuf,
+}
+
+impl StagingDir {
+    fn new(fs_root_path: &Path) -> Result<Self> {
+        let path = fs_root_path.join(PKG_STAGING_PATH).join(randombytes(6).as_slice().to_hex());
+        try!(fs::create_dir_all(&path));
+        Ok(StagingDir { path: path })
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            debug!("Failed to remove staging directory {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Unpacks a tarball stream into a directory. Entries which would land outside the directory,
+/// through `..`, an absolute path or a symlink, are refused.
+fn extract<R: Read + Any>(tar_reader: R, dst: &Path) -> Result<()> {
+    let mut builder = reader::Builder::new();
+    try!(builder.support_format(ReadFormat::Gnutar));
+    try!(builder.support_filter(ReadFilter::Xz));
+    let mut reader = try!(builder.open_stream(tar_reader));
+    let writer = writer::Disk::new();
+    let mut extract_options = ExtractOptions::new();
+    extract_options.add(ExtractOption::SecureNoDotDot);
+    extract_options.add(ExtractOption::SecureSymlinks);
+    extract_options.add(ExtractOption::SecureNoAbsolutePaths);
+    try!(writer.set_options(&extract_options));
+    try!(writer.set_standard_lookup());
+    try!(writer.write(&mut reader, Some(dst.to_string_lossy().as_ref())));
+    try!(writer.close());
+    Ok(())
+}
+
+/// Reads the metadata of the one package unpacked under `staging`, returning the package's path
+/// relative to `staging` with its metadata. The `IDENT` must be fully qualified and match the
+/// package's path, and a `MANIFEST` must be present.
+fn read_staged_metadata(staging: &Path) -> Result<(PathBuf, Metadata)> {
+    let pkg_path = try!(find_staged_package(staging));
+    let mut metadata = Metadata::new();
+    for file in METAFILE_REGXS.keys() {
+        let path = staging.join(&pkg_path).join(file.to_string());
+        if !path.is_file() {
+            continue;
+        }
+        let mut content = String::new();
+        if try!(File::open(&path)).read_to_string(&mut content).is_err() {
+            return Err(Error::MetaFileMalformed(file.clone()));
+        }
+        metadata.insert(file.clone(), content.trim().to_string());
+    }
+    let ident = match metadata.get(&MetaFile::Ident) {
+        Some(ident) => try!(PackageIdent::from_str(ident)),
+        None => return Err(Error::MetaFileNotFound(MetaFile::Ident)),
+    };
+    if !ident.fully_qualified() ||
+       Path::new(PKG_PATH).join(ident.to_string()) != pkg_path {
+        return Err(Error::MetaFileMalformed(MetaFile::Ident));
+    }
+    if !metadata.contains_key(&MetaFile::Manifest) {
+        return Err(Error::MetaFileNotFound(MetaFile::Manifest));
+    }
+    Ok((pkg_path, metadata))
+}
+
+/// Moves a package unpacked under `staging` into place under `fs_root_path`, unless another
+/// install got there first. A directory left in its place without a valid `IDENT`, such as by an
+/// install from before packages were staged that was interrupted, is replaced.
+fn install_staged(fs_root_path: &Path, staging: &Path, pkg_path: &Path) -> Result<()> {
+    let install_path = fs_root_path.join(pkg_path);
+    let _lock = try!(InstallLock::acquire(fs_root_path));
+    if install_path.exists() {
+        if has_valid_ident(&install_path, pkg_path) {
+            debug!("{} is already installed, discarding the staged copy",
+                   install_path.display());
+            return Ok(());
+        }
+        info!("Replacing {}, it has no valid IDENT", install_path.display());
+        try!(fs::remove_dir_all(&install_path));
+    }
+    if let Some(parent) = install_path.parent() {
+        try!(fs::create_dir_all(parent));
+    }
+    try!(fs::rename(staging.join(pkg_path), &install_path));
+    Ok(())
+}
+
+/// True if the installed package at `install_path` has an `IDENT` naming the package `pkg_path`
+/// is the path of.
+fn has_valid_ident(install_path: &Path, pkg_path: &Path) -> bool {
+    let mut content = String::new();
+    match File::open(install_path.join(MetaFile::Ident.to_string())) {
+        Ok(mut file) => {
+            if file.read_to_string(&mut content).is_err() {
+                return false;
+            }
+        }
+        Err(_) => return false,
+    }
+    match PackageIdent::from_str(content.trim()) {
+        Ok(ident) => {
+            ident.fully_qualified() && Path::new(PKG_PATH).join(ident.to_string()) == pkg_path
+        }
+        Err(_) => false,
+    }
+}
+
+/// Returns the path, relative to `staging`, of the one package release directory unpacked there.
+fn find_staged_package(staging: &Path) -> Result<PathBuf> {
+    let mut pkg_path = PathBuf::from(PKG_PATH);
+    // origin, name, version and release
+    for _ in 0..4 {
+        let mut entries = match fs::read_dir(staging.join(&pkg_path)) {
+            Ok(entries) => entries,
+            Err(_) => return Err(Error::MetaFileNotFound(MetaFile::Ident)),
+        };
+        let entry = match entries.next() {
+            Some(entry) => try!(entry),
+            None => return Err(Error::MetaFileNotFound(MetaFile::Ident)),
+        };
+        // More than one package would leave us guessing which IDENT is the archive's
+        if entries.next().is_some() {
+            return Err(Error::MetaFileMalformed(MetaFile::Ident));
+        }
+        pkg_path.push(entry.file_name());
+    }
+    Ok(pkg_path)
+}
+
 pub trait FromArchive: Sized {
     type Error: error::Error;
 
@@ -275,7 +533,14 @@ pub trait FromArchive: Sized {
 #[cfg(test)]
 mod test {
     use std::env;
+    use std::fs::{self, File};
     use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use tempdir::TempDir;
+
+    use fs::PKG_STAGING_PATH;
+    use package::{PackageIdent, PackageInstall};
     use super::*;
 
     #[test]
@@ -289,6 +554,16 @@ mod test {
         assert_eq!(ident.release, Some("20160427165340".to_string()));
     }
 
+    #[test]
+    fn reading_artifact_without_provenance() {
+        let mut hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        assert!(hart.licenses().unwrap().is_empty());
+        assert_eq!(hart.source().unwrap(), None);
+        assert_eq!(hart.shasum().unwrap(), None);
+        assert_eq!(hart.plan_commit().unwrap(), None);
+    }
+
     pub fn exe_path() -> PathBuf {
         env::current_exe().unwrap()
     }
@@ -297,6 +572,92 @@ mod test {
         exe_path().parent().unwrap().parent().unwrap().parent().unwrap().join("tests")
     }
 
+    #[test]
+    fn verify_and_unpack_artifact() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let root = TempDir::new("fs_root").unwrap();
+        fs::copy(fixtures().join("happyhumans-20160424223347.pub"),
+                 cache.path().join("happyhumans-20160424223347.pub"))
+            .unwrap();
+        let mut hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        let signers = hart.verify_and_unpack(Some(root.path()), &cache.path(), &Default::default())
+            .unwrap();
+        assert_eq!(signers, vec!["happyhumans-20160424223347".to_string()]);
+        assert_eq!(hart.ident().unwrap().to_string(),
+                   "happyhumans/possums/8.1.4/20160427165340");
+        assert!(root.path()
+            .join("hab/pkgs/happyhumans/possums/8.1.4/20160427165340/IDENT")
+            .is_file());
+        assert_eq!(fs::read_dir(root.path().join(PKG_STAGING_PATH)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn verify_and_unpack_unverified_artifact() {
+        let cache = TempDir::new("key_cache").unwrap();
+        let root = TempDir::new("fs_root").unwrap();
+        let mut hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        assert!(hart.verify_and_unpack(Some(root.path()), &cache.path(), &Default::default())
+            .is_err());
+        assert!(!root.path().join("hab/pkgs/happyhumans").exists());
+        assert_eq!(fs::read_dir(root.path().join(PKG_STAGING_PATH)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn extract_refuses_paths_outside_the_directory() {
+        let root = TempDir::new("fs_root").unwrap();
+        let dst = root.path().join("staging");
+        fs::create_dir_all(&dst).unwrap();
+        let _ = extract(File::open(fixtures().join("traversal.tar.xz")).unwrap(), &dst);
+        assert!(!root.path().join("escaped").exists());
+        assert!(!root.path().join("through-symlink").exists());
+    }
+
+    #[test]
+    fn unpack_into_place() {
+        let root = TempDir::new("fs_root").unwrap();
+        let hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        hart.unpack(Some(root.path())).unwrap();
+        // A second unpack leaves the installed package as it is
+        hart.unpack(Some(root.path())).unwrap();
+        let ident = PackageIdent::from_str("happyhumans/possums").unwrap();
+        let installed = PackageInstall::load(&ident, Some(root.path())).unwrap();
+        assert_eq!(installed.ident().to_string(),
+                   "happyhumans/possums/8.1.4/20160427165340");
+        assert_eq!(fs::read_dir(root.path().join(PKG_STAGING_PATH)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn unpack_replaces_partial_install() {
+        let root = TempDir::new("fs_root").unwrap();
+        let install_path = root.path()
+            .join("hab/pkgs/happyhumans/possums/8.1.4/20160427165340");
+        fs::create_dir_all(install_path.join("bin")).unwrap();
+        let hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        hart.unpack(Some(root.path())).unwrap();
+        assert!(install_path.join("IDENT").is_file());
+        assert!(install_path.join("MANIFEST").is_file());
+        assert_eq!(fs::read_dir(root.path().join(PKG_STAGING_PATH)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn uninstall_unpacked_package() {
+        let root = TempDir::new("fs_root").unwrap();
+        let hart = PackageArchive::new(fixtures()
+            .join("happyhumans-possums-8.1.4-20160427165340-x86_64-linux.hart"));
+        hart.unpack(Some(root.path())).unwrap();
+        let ident = PackageIdent::from_str("happyhumans/possums/8.1.4/20160427165340").unwrap();
+        assert_eq!(PackageInstall::installed(Some(root.path())).unwrap(),
+                   vec![ident.clone()]);
+
+        PackageInstall::load(&ident, Some(root.path())).unwrap().uninstall().unwrap();
+        assert!(PackageInstall::installed(Some(root.path())).unwrap().is_empty());
+        assert!(!root.path().join("hab/pkgs/happyhumans").exists());
+    }
+
     pub fn fixtures() -> PathBuf {
         root().join("fixtures")
     }
@@ -0,0 +1,379 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Package requirements: an origin and name, plus constraints on the version.
+//!
+//! A requirement is written as the origin and name, followed by a comma separated list of
+//! version constraints:
+//!
+//! ```text
+//! core/openssl >= 1.0.2, < 1.1
+//! core/redis ~> 3.0
+//! core/glibc ^2.22
+//! core/zlib 1.2.*
+//! ```
+//!
+//! Each constraint is one of:
+//!
+//! * `= 1.0.2`, `> 1.0.2`, `>= 1.0.2`, `< 1.1` or `<= 1.1`, compared with `version_sort`
+//! * `~> 1.2.3`, any version from `1.2.3` up to, but not including, `1.3`. `~> 1.2` allows
+//!   anything below `2`.
+//! * `^1.2.3`, any version from `1.2.3` that doesn't change the leftmost non-zero component, so
+//!   below `2` here, and below `0.3` for `^0.2.3`.
+//! * `1.2.*`, any version starting with `1.2`. A lone `*` allows any version.
+//! * A bare version, like `1.0.2`, which is the same as `= 1.0.2`.
+//!
+//! The fully qualified forms `core/openssl/1.0.2` and `core/openssl/1.0.2/20160612081125` are
+//! accepted too, and pin the version, and the release.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::result;
+use std::str::FromStr;
+
+use error::{Error, Result};
+use package::ident::{version_sort, Identifiable, PackageIdent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Constraint {
+    op: Op,
+    version: String,
+}
+
+impl Constraint {
+    fn new(op: Op, version: String) -> Constraint {
+        Constraint {
+            op: op,
+            version: version,
+        }
+    }
+
+    fn matches(&self, version: &str) -> bool {
+        let ord = match version_sort(version, &self.version) {
+            Ok(ord) => ord,
+            Err(_) => return false,
+        };
+        match self.op {
+            Op::Eq => ord == Ordering::Equal,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+        }
+    }
+}
+
+/// A requirement on a package: its origin and name, and constraints on its version and release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageIdentReq {
+    pub origin: String,
+    pub name: String,
+    pub release: Option<String>,
+    constraints: Vec<Constraint>,
+    // The requirement as written, for display
+    expr: String,
+}
+
+impl PackageIdentReq {
+    /// True if the ident is the package we require, and its version, and release, meet our
+    /// constraints. Idents without a version only meet a requirement without constraints.
+    pub fn matches<I: Identifiable>(&self, ident: &I) -> bool {
+        if ident.origin() != self.origin || ident.name() != self.name {
+            return false;
+        }
+        if let Some(ref release) = self.release {
+            if ident.release() != Some(release.as_str()) {
+                return false;
+            }
+        }
+        match ident.version() {
+            Some(version) => self.constraints.iter().all(|c| c.matches(version)),
+            None => self.constraints.is_empty(),
+        }
+    }
+
+    /// The newest fully qualified ident that meets this requirement.
+    pub fn newest<'a, I>(&self, idents: I) -> Option<&'a PackageIdent>
+        where I: IntoIterator<Item = &'a PackageIdent>
+    {
+        idents.into_iter()
+            .filter(|i| i.fully_qualified() && self.matches(*i))
+            .fold(None, |newest, ident| {
+                match newest {
+                    Some(n) if n.partial_cmp(ident) != Some(Ordering::Less) => Some(n),
+                    _ => Some(ident),
+                }
+            })
+    }
+
+    /// True if this requirement has no constraints, so it is the same as a plain ident.
+    pub fn is_unconstrained(&self) -> bool {
+        self.constraints.is_empty() && self.release.is_none()
+    }
+}
+
+impl fmt::Display for PackageIdentReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expr)
+    }
+}
+
+impl FromStr for PackageIdentReq {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        let value = value.trim();
+        let (ident, expr) = match value.find(|c: char| c.is_whitespace()) {
+            Some(i) => (&value[..i], value[i..].trim()),
+            None => (value, ""),
+        };
+        let ident = try!(PackageIdent::from_str(ident));
+        if ident.origin.is_empty() || ident.name.is_empty() {
+            return Err(Error::InvalidPackageIdent(value.to_string()));
+        }
+        let mut constraints = Vec::new();
+        if let Some(ref version) = ident.version {
+            if !expr.is_empty() {
+                return Err(Error::InvalidPackageIdentReq(value.to_string()));
+            }
+            try!(parse_constraint(version, &mut constraints)
+                .map_err(|_| Error::InvalidPackageIdentReq(value.to_string())));
+        }
+        if !expr.is_empty() {
+            for c in expr.split(',') {
+                try!(parse_constraint(c.trim(), &mut constraints)
+                    .map_err(|_| Error::InvalidPackageIdentReq(value.to_string())));
+            }
+        }
+        Ok(PackageIdentReq {
+            origin: ident.origin,
+            name: ident.name,
+            release: ident.release,
+            constraints: constraints,
+            expr: value.to_string(),
+        })
+    }
+}
+
+/// Parse one constraint, adding the comparisons it stands for.
+fn parse_constraint(constraint: &str, constraints: &mut Vec<Constraint>) -> Result<()> {
+    let ops = [("~>", None), (">=", Some(Op::Ge)), ("<=", Some(Op::Le)), (">", Some(Op::Gt)),
+               ("<", Some(Op::Lt)), ("=", Some(Op::Eq)), ("^", None)];
+    for &(prefix, op) in ops.iter() {
+        if constraint.starts_with(prefix) {
+            let version = try!(parse_version(constraint[prefix.len()..].trim()));
+            match op {
+                Some(op) => constraints.push(Constraint::new(op, version)),
+                None if prefix == "~>" => {
+                    let upper = try!(pessimistic_upper(&version));
+                    constraints.push(Constraint::new(Op::Ge, version));
+                    constraints.push(Constraint::new(Op::Lt, upper));
+                }
+                None => {
+                    let upper = try!(caret_upper(&version));
+                    constraints.push(Constraint::new(Op::Ge, version));
+                    constraints.push(Constraint::new(Op::Lt, upper));
+                }
+            }
+            return Ok(());
+        }
+    }
+    if constraint == "*" {
+        return Ok(());
+    }
+    if constraint.ends_with(".*") {
+        let prefix = try!(parse_version(&constraint[..constraint.len() - 2]));
+        let upper = try!(wildcard_upper(&prefix));
+        constraints.push(Constraint::new(Op::Ge, prefix));
+        constraints.push(Constraint::new(Op::Lt, upper));
+        return Ok(());
+    }
+    let version = try!(parse_version(constraint));
+    constraints.push(Constraint::new(Op::Eq, version));
+    Ok(())
+}
+
+/// Check a version is one `version_sort` understands.
+fn parse_version(version: &str) -> Result<String> {
+    if version.is_empty() || !version.chars().next().unwrap().is_digit(10) {
+        return Err(Error::InvalidPackageIdentReq(version.to_string()));
+    }
+    try!(version_sort(version, version));
+    Ok(version.to_string())
+}
+
+/// The numeric components of a version, ignoring any extension. A component may carry a suffix,
+/// as in `1.0.2k`, which is ignored too; a component without a leading number is an error.
+fn components(version: &str) -> Result<Vec<u64>> {
+    let mut parts = Vec::new();
+    for c in version.split('-').next().unwrap().split('.') {
+        let digits: String = c.chars().take_while(|c| c.is_digit(10)).collect();
+        match digits.parse() {
+            Ok(part) => parts.push(part),
+            Err(_) => return Err(Error::InvalidPackageIdentReq(version.to_string())),
+        }
+    }
+    Ok(parts)
+}
+
+fn join(components: &[u64]) -> String {
+    components.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(".")
+}
+
+/// `~> 1.2.3` allows up to `1.3`; `~> 1.2` up to `2`; `~> 1` up to `2`.
+fn pessimistic_upper(version: &str) -> Result<String> {
+    let mut parts = try!(components(version));
+    if parts.len() > 1 {
+        parts.pop();
+    }
+    increment_last(&mut parts);
+    Ok(join(&parts))
+}
+
+/// `^1.2.3` allows up to `2`, `^0.2.3` up to `0.3`, and `^0.0.3` up to `0.0.4`.
+fn caret_upper(version: &str) -> Result<String> {
+    let parts = try!(components(version));
+    let i = match parts.iter().position(|&c| c != 0) {
+        Some(i) => i,
+        None => parts.len().saturating_sub(1),
+    };
+    let mut upper: Vec<u64> = parts.iter().take(i + 1).cloned().collect();
+    increment_last(&mut upper);
+    Ok(join(&upper))
+}
+
+/// `1.2.*` allows up to `1.3`.
+fn wildcard_upper(prefix: &str) -> Result<String> {
+    let mut parts = try!(components(prefix));
+    increment_last(&mut parts);
+    Ok(join(&parts))
+}
+
+fn increment_last(parts: &mut [u64]) {
+    if let Some(last) = parts.last_mut() {
+        *last += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use package::PackageIdent;
+    use super::PackageIdentReq;
+
+    fn req(value: &str) -> PackageIdentReq {
+        PackageIdentReq::from_str(value).unwrap()
+    }
+
+    fn ident(version: &str) -> PackageIdent {
+        PackageIdent::new("core", "openssl", Some(version), Some("20160612081125"))
+    }
+
+    #[test]
+    fn comparisons() {
+        let r = req("core/openssl >= 1.0.2, < 1.1");
+        assert!(r.matches(&ident("1.0.2")));
+        assert!(r.matches(&ident("1.0.9")));
+        assert!(!r.matches(&ident("1.0.1")));
+        assert!(!r.matches(&ident("1.1.0")));
+        assert!(req("core/openssl > 1.0.2").matches(&ident("1.0.3")));
+        assert!(!req("core/openssl > 1.0.2").matches(&ident("1.0.2")));
+        assert!(req("core/openssl <= 1.0.2").matches(&ident("1.0.2")));
+        assert!(req("core/openssl = 1.0.2").matches(&ident("1.0.2")));
+        assert!(req("core/openssl 1.0.2").matches(&ident("1.0.2")));
+    }
+
+    #[test]
+    fn pessimistic() {
+        let r = req("core/openssl ~> 1.0.2");
+        assert!(r.matches(&ident("1.0.2")));
+        assert!(r.matches(&ident("1.0.20")));
+        assert!(!r.matches(&ident("1.1.0")));
+        let r = req("core/openssl ~> 1.0");
+        assert!(r.matches(&ident("1.9.0")));
+        assert!(!r.matches(&ident("2.0.0")));
+    }
+
+    #[test]
+    fn suffixed_versions() {
+        let r = req("core/openssl ~> 1.0.2k");
+        assert!(r.matches(&ident("1.0.2k")));
+        assert!(r.matches(&ident("1.0.9")));
+        assert!(!r.matches(&ident("1.1.0")));
+        let r = req("core/foo ^1x");
+        assert!(r.matches(&ident("1.5.0")));
+        assert!(!r.matches(&ident("2.0.0")));
+        assert!(PackageIdentReq::from_str("core/foo ~> 1.x").is_err());
+        assert!(PackageIdentReq::from_str("core/foo ^1.x.*").is_err());
+    }
+
+    #[test]
+    fn caret() {
+        let r = req("core/openssl ^1.0.2");
+        assert!(r.matches(&ident("1.9.0")));
+        assert!(!r.matches(&ident("2.0.0")));
+        assert!(!r.matches(&ident("1.0.1")));
+        let r = req("core/openssl ^0.2.3");
+        assert!(r.matches(&ident("0.2.9")));
+        assert!(!r.matches(&ident("0.3.0")));
+    }
+
+    #[test]
+    fn wildcards() {
+        let r = req("core/openssl 1.0.*");
+        assert!(r.matches(&ident("1.0.0")));
+        assert!(r.matches(&ident("1.0.2")));
+        assert!(!r.matches(&ident("1.1.0")));
+        assert!(req("core/openssl *").matches(&ident("3.0.0")));
+        assert!(req("core/openssl").matches(&ident("3.0.0")));
+    }
+
+    #[test]
+    fn fully_qualified() {
+        let r = req("core/openssl/1.0.2");
+        assert!(r.matches(&ident("1.0.2")));
+        assert!(!r.matches(&ident("1.0.3")));
+        let r = req("core/openssl/1.0.2/20160612081125");
+        assert!(r.matches(&ident("1.0.2")));
+        assert!(!r.matches(&PackageIdent::new("core", "openssl", Some("1.0.2"), Some("1"))));
+        assert!(!req("core/openssl").matches(&PackageIdent::new("core", "zlib", None, None)));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(PackageIdentReq::from_str("core/openssl >= ").is_err());
+        assert!(PackageIdentReq::from_str("core/openssl ~> latest").is_err());
+        assert!(PackageIdentReq::from_str("core/openssl/1.0.2 < 1.1").is_err());
+        assert!(PackageIdentReq::from_str("openssl").is_err());
+    }
+
+    #[test]
+    fn newest() {
+        let idents = vec![ident("1.0.1"), ident("1.0.2"), ident("1.1.0"),
+                          PackageIdent::new("core", "openssl", None, None)];
+        let r = req("core/openssl >= 1.0, < 1.1");
+        assert_eq!(r.newest(&idents), Some(&idents[1]));
+        assert_eq!(req("core/openssl > 2").newest(&idents), None);
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use error::Result;
+use package::{Identifiable, PackageIdent, PackageInstall};
+
+/// An index of the installed packages and their dependencies, for answering which installed
+/// packages depend on a given one.
+///
+/// The index is a snapshot, read from the package path when it's loaded.
+#[derive(Debug)]
+pub struct PackageIndex {
+    deps: HashMap<PackageIdent, Vec<PackageIdent>>,
+    tdeps: HashMap<PackageIdent, Vec<PackageIdent>>,
+}
+
+impl PackageIndex {
+    /// Reads the `DEPS` and `TDEPS` of every installed package.
+    ///
+    /// An optional `fs_root` path may be provided to index packages on a filesystem not
+    /// currently rooted at `/`.
+    ///
+    /// # Failures
+    ///
+    /// * If an installed package's dependencies can't be read
+    pub fn load(fs_root_path: Option<&Path>) -> Result<Self> {
+        let mut index = PackageIndex {
+            deps: HashMap::new(),
+            tdeps: HashMap::new(),
+        };
+        for pkg_install in try!(PackageInstall::installed_packages(fs_root_path)) {
+            index.deps.insert(pkg_install.ident().clone(), try!(pkg_install.deps()));
+            index.tdeps.insert(pkg_install.ident().clone(), try!(pkg_install.tdeps()));
+        }
+        Ok(index)
+    }
+
+    /// Every installed package, sorted.
+    pub fn installed(&self) -> Vec<&PackageIdent> {
+        sorted(self.deps.keys().collect())
+    }
+
+    /// The direct dependencies of an installed package, or `None` if it isn't installed.
+    pub fn deps(&self, ident: &PackageIdent) -> Option<&[PackageIdent]> {
+        self.deps.get(ident).map(|deps| deps.as_slice())
+    }
+
+    /// The transitive dependencies of an installed package, or `None` if it isn't installed.
+    pub fn tdeps(&self, ident: &PackageIdent) -> Option<&[PackageIdent]> {
+        self.tdeps.get(ident).map(|tdeps| tdeps.as_slice())
+    }
+
+    /// The installed packages which depend directly on a package, sorted. The identifier may
+    /// leave out the version or release, to find dependents of any matching release.
+    pub fn dependents<I: Identifiable>(&self, ident: &I) -> Vec<&PackageIdent> {
+        Self::depending_on(&self.deps, ident)
+    }
+
+    /// The installed packages which depend on a package, directly or through other packages,
+    /// sorted. The identifier may leave out the version or release, to find dependents of any
+    /// matching release.
+    pub fn transitive_dependents<I: Identifiable>(&self, ident: &I) -> Vec<&PackageIdent> {
+        Self::depending_on(&self.tdeps, ident)
+    }
+
+    fn depending_on<'a, I: Identifiable>(deps: &'a HashMap<PackageIdent, Vec<PackageIdent>>,
+                                         ident: &I)
+                                         -> Vec<&'a PackageIdent> {
+        sorted(deps.iter()
+            .filter(|&(_, deps)| deps.iter().any(|dep| dep.satisfies(ident)))
+            .map(|(pkg, _)| pkg)
+            .collect())
+    }
+}
+
+fn sorted(mut idents: Vec<&PackageIdent>) -> Vec<&PackageIdent> {
+    idents.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+    idents
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use tempdir::TempDir;
+
+    use package::PackageIdent;
+    use super::*;
+
+    fn install(root: &Path, ident: &str, deps: &[&str], tdeps: &[&str]) {
+        let path = root.join("hab/pkgs").join(ident);
+        fs::create_dir_all(&path).unwrap();
+        File::create(path.join("IDENT")).unwrap().write_all(ident.as_bytes()).unwrap();
+        if !deps.is_empty() {
+            File::create(path.join("DEPS")).unwrap().write_all(deps.join("\n").as_bytes()).unwrap();
+        }
+        if !tdeps.is_empty() {
+            File::create(path.join("TDEPS"))
+                .unwrap()
+                .write_all(tdeps.join("\n").as_bytes())
+                .unwrap();
+        }
+    }
+
+    fn idents(idents: Vec<&PackageIdent>) -> Vec<String> {
+        idents.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn reverse_dependencies() {
+        let root = TempDir::new("fs_root").unwrap();
+        install(root.path(), "core/openssl/1.0.2h/20160612081539", &[], &[]);
+        install(root.path(), "core/openssl/1.0.2j/20161001000000", &[], &[]);
+        install(root.path(),
+                "core/curl/7.49.1/20160612080006",
+                &["core/openssl/1.0.2h/20160612081539"],
+                &["core/openssl/1.0.2h/20160612081539"]);
+        install(root.path(),
+                "core/git/2.9.0/20160612090032",
+                &["core/curl/7.49.1/20160612080006"],
+                &["core/curl/7.49.1/20160612080006", "core/openssl/1.0.2h/20160612081539"]);
+        let index = PackageIndex::load(Some(root.path())).unwrap();
+        assert_eq!(index.installed().len(), 4);
+
+        let openssl = PackageIdent::from_str("core/openssl").unwrap();
+        assert_eq!(idents(index.dependents(&openssl)),
+                   vec!["core/curl/7.49.1/20160612080006"]);
+        assert_eq!(idents(index.transitive_dependents(&openssl)),
+                   vec!["core/curl/7.49.1/20160612080006", "core/git/2.9.0/20160612090032"]);
+
+        let new_openssl = PackageIdent::from_str("core/openssl/1.0.2j").unwrap();
+        assert!(index.transitive_dependents(&new_openssl).is_empty());
+
+        let git = PackageIdent::from_str("core/git/2.9.0/20160612090032").unwrap();
+        assert_eq!(index.tdeps(&git).unwrap().len(), 2);
+        assert!(index.dependents(&git).is_empty());
+    }
+}
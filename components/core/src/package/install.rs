@@ -16,14 +16,39 @@ use std;
 use std::collections::HashSet;
 use std::cmp::{Ordering, PartialOrd};
 use std::env;
-use std::fs::{DirEntry, File};
+use std::fs::{DirEntry, File, OpenOptions};
+use std::io;
 use std::io::prelude::*;
+use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use libc;
+
 use error::{Error, Result};
-use fs::{self, PKG_PATH};
-use package::{Identifiable, MetaFile, PackageIdent};
+use fs::{self, PKG_INSTALL_LOCK_PATH, PKG_PATH};
+use package::{split_lines, Identifiable, MetaFile, PackageIdent, PackageIdentReq};
+
+/// An exclusive lock on the package path, held while a package is moved into or out of place so
+/// that concurrent installs and uninstalls don't clobber each other. The lock is released when
+/// it's dropped.
+pub struct InstallLock {
+    _file: File,
+}
+
+impl InstallLock {
+    pub fn acquire(fs_root_path: &Path) -> Result<Self> {
+        let path = fs_root_path.join(PKG_INSTALL_LOCK_PATH);
+        if let Some(parent) = path.parent() {
+            try!(std::fs::create_dir_all(parent));
+        }
+        let file = try!(OpenOptions::new().write(true).create(true).open(&path));
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(Error::IO(io::Error::last_os_error()));
+        }
+        Ok(InstallLock { _file: file })
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct PackageInstall {
@@ -90,6 +115,104 @@ impl PackageInstall {
         }
     }
 
+    /// Returns the newest installed package that meets a requirement.
+    ///
+    /// An optional `fs_root` path may be provided to search for a package that is mounted on a
+    /// filesystem not currently rooted at `/`.
+    ///
+    /// # Failures
+    ///
+    /// * No installed package meets the requirement
+    pub fn load_req(req: &PackageIdentReq,
+                    fs_root_path: Option<&Path>)
+                    -> Result<PackageInstall> {
+        let fs_root_path = fs_root_path.unwrap_or(Path::new("/"));
+        let package_root_path = fs_root_path.join(PKG_PATH);
+        let not_found = || {
+            Error::PackageNotFound(PackageIdent::new(req.origin.clone(),
+                                                     req.name.clone(),
+                                                     None,
+                                                     None))
+        };
+        if !package_root_path.exists() {
+            return Err(not_found());
+        }
+        let pl = try!(Self::package_list(&package_root_path));
+        match req.newest(&pl) {
+            Some(ident) => {
+                Ok(PackageInstall {
+                    ident: ident.clone(),
+                    fs_root_path: PathBuf::from(fs_root_path),
+                    package_root_path: package_root_path.clone(),
+                    installed_path: try!(Self::calc_installed_path(ident, &package_root_path)),
+                })
+            }
+            None => Err(not_found()),
+        }
+    }
+
+    /// Returns the identifiers of every installed package.
+    ///
+    /// An optional `fs_root` path may be provided to search for packages on a filesystem not
+    /// currently rooted at `/`.
+    pub fn installed(fs_root_path: Option<&Path>) -> Result<Vec<PackageIdent>> {
+        let package_root_path = fs_root_path.unwrap_or(Path::new("/")).join(PKG_PATH);
+        if !package_root_path.exists() {
+            return Ok(vec![]);
+        }
+        Self::package_list(&package_root_path)
+    }
+
+    /// Returns every installed package, found with a single walk of the package path rather than
+    /// a `load` for each one.
+    ///
+    /// An optional `fs_root` path may be provided to search for packages on a filesystem not
+    /// currently rooted at `/`.
+    pub fn installed_packages(fs_root_path: Option<&Path>) -> Result<Vec<PackageInstall>> {
+        let fs_root_path = fs_root_path.unwrap_or(Path::new("/"));
+        let package_root_path = fs_root_path.join(PKG_PATH);
+        let mut packages = vec![];
+        for ident in try!(Self::installed(Some(fs_root_path))) {
+            let installed_path = try!(Self::calc_installed_path(&ident, &package_root_path));
+            packages.push(PackageInstall {
+                ident: ident,
+                fs_root_path: PathBuf::from(fs_root_path),
+                package_root_path: package_root_path.clone(),
+                installed_path: installed_path,
+            });
+        }
+        Ok(packages)
+    }
+
+    /// Removes the package from disk, along with any version, name and origin directories it
+    /// leaves empty.
+    ///
+    /// The package is moved aside under the install lock before it's deleted, so it's never seen
+    /// half removed. Whether other packages or services still need it is up to the caller.
+    pub fn uninstall(self) -> Result<()> {
+        let lock = try!(InstallLock::acquire(&self.fs_root_path));
+        self.uninstall_locked(&lock)
+    }
+
+    /// Removes the package from disk like `uninstall`, for callers that already hold the install
+    /// lock so they can check whether the package is still needed without racing an install.
+    pub fn uninstall_locked(self, _lock: &InstallLock) -> Result<()> {
+        let removing = {
+            let release = self.installed_path.file_name().unwrap().to_string_lossy();
+            self.installed_path.with_file_name(format!(".{}.removing", release))
+        };
+        try!(std::fs::rename(&self.installed_path, &removing));
+        try!(std::fs::remove_dir_all(&removing));
+        let mut dir = self.installed_path.parent();
+        while let Some(path) = dir {
+            if path == self.package_root_path || std::fs::remove_dir(path).is_err() {
+                break;
+            }
+            dir = path.parent();
+        }
+        Ok(())
+    }
+
     pub fn new_from_parts(ident: PackageIdent,
                           fs_root_path: PathBuf,
                           package_root_path: PathBuf,
@@ -128,6 +251,137 @@ impl PackageInstall {
         }
     }
 
+    /// The action to take when each named config file changes, as declared by the package.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains a ConfigActions metafile but it could not be read or it was
+    ///   malformed
+    pub fn config_actions(&self) -> Result<Vec<(String, String)>> {
+        self.read_file_pairs(MetaFile::ConfigActions)
+    }
+
+    /// The mode, and optionally the owner, to give each named rendered config file, as declared
+    /// by the package.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains a ConfigPerms metafile but it could not be read or it was
+    ///   malformed
+    pub fn config_perms(&self) -> Result<Vec<(String, String)>> {
+        self.read_file_pairs(MetaFile::ConfigPerms)
+    }
+
+    /// The binds the package requires, each with the names of the values the bound service must
+    /// export.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains a Binds metafile but it could not be read or it was malformed
+    pub fn binds(&self) -> Result<Vec<(String, Vec<String>)>> {
+        self.read_binds(MetaFile::Binds)
+    }
+
+    /// The binds the package can use but doesn't require, each with the names of the values the
+    /// bound service must export.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains a BindsOptional metafile but it could not be read or it was
+    ///   malformed
+    pub fn binds_optional(&self) -> Result<Vec<(String, Vec<String>)>> {
+        self.read_binds(MetaFile::BindsOptional)
+    }
+
+    /// The values the package exports to services that bind to it, each with the config key it
+    /// is read from.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains an Exports metafile but it could not be read or it was malformed
+    pub fn exports(&self) -> Result<Vec<(String, String)>> {
+        self.read_file_pairs(MetaFile::Exports)
+    }
+
+    /// The license identifiers of the packaged software, as declared in its plan. A package
+    /// without a `LICENSES` metafile has none.
+    ///
+    /// # Failures
+    ///
+    /// * The package contains a Licenses metafile but it could not be read
+    pub fn licenses(&self) -> Result<Vec<String>> {
+        match self.read_metafile(MetaFile::Licenses) {
+            Ok(body) => Ok(split_lines(&body)),
+            Err(Error::MetaFileNotFound(MetaFile::Licenses)) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The package's manifest, a Markdown summary of its plan written when it was built.
+    ///
+    /// # Failures
+    ///
+    /// * The package has no Manifest metafile, or it could not be read
+    pub fn manifest(&self) -> Result<String> {
+        self.read_metafile(MetaFile::Manifest)
+    }
+
+    /// The URL the packaged software's source was downloaded from, if recorded.
+    pub fn source(&self) -> Result<Option<String>> {
+        self.read_optional_metafile(MetaFile::Source)
+    }
+
+    /// The SHA-256 checksum of the packaged software's source, if recorded.
+    pub fn shasum(&self) -> Result<Option<String>> {
+        self.read_optional_metafile(MetaFile::Shasum)
+    }
+
+    /// The host name of the machine the package was built on, if recorded.
+    pub fn build_host(&self) -> Result<Option<String>> {
+        self.read_optional_metafile(MetaFile::BuildHost)
+    }
+
+    /// The version of the build program which built the package, if recorded.
+    pub fn builder_version(&self) -> Result<Option<String>> {
+        self.read_optional_metafile(MetaFile::BuilderVersion)
+    }
+
+    /// The git commit of the plan the package was built from, if it was built from a git
+    /// checkout.
+    pub fn plan_commit(&self) -> Result<Option<String>> {
+        self.read_optional_metafile(MetaFile::PlanCommit)
+    }
+
+    fn read_binds(&self, file: MetaFile) -> Result<Vec<(String, Vec<String>)>> {
+        let pairs = try!(self.read_file_pairs(file));
+        Ok(pairs.into_iter()
+            .map(|(name, exports)| {
+                (name, exports.split_whitespace().map(|e| e.to_string()).collect())
+            })
+            .collect())
+    }
+
+    /// Read a metafile of `name=value` lines. A missing metafile has no pairs.
+    fn read_file_pairs(&self, file: MetaFile) -> Result<Vec<(String, String)>> {
+        match self.read_metafile(file.clone()) {
+            Ok(body) => {
+                let mut pairs = Vec::new();
+                for line in body.lines().filter(|l| !l.trim().is_empty()) {
+                    let mut parts = line.splitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(name), Some(value)) => {
+                            pairs.push((name.trim().to_string(), value.trim().to_string()))
+                        }
+                        _ => return Err(Error::MetaFileMalformed(file)),
+                    }
+                }
+                Ok(pairs)
+            }
+            Err(Error::MetaFileNotFound(ref f)) if *f == file => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn ident(&self) -> &PackageIdent {
         &self.ident
     }
@@ -249,6 +503,17 @@ impl PackageInstall {
         }
     }
 
+    /// Read a single value metafile, which packages built before it was recorded won't have.
+    /// An empty metafile has no value.
+    fn read_optional_metafile(&self, file: MetaFile) -> Result<Option<String>> {
+        match self.read_metafile(file.clone()) {
+            Ok(ref body) if body.is_empty() => Ok(None),
+            Ok(body) => Ok(Some(body)),
+            Err(Error::MetaFileNotFound(ref f)) if *f == file => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Reads metafiles containing dependencies represented by package identifiers separated by new
     /// lines.
     ///
@@ -336,6 +601,9 @@ impl PackageInstall {
     fn walk_origins(path: &Path, packages: &mut Vec<PackageIdent>) -> Result<()> {
         for entry in try!(std::fs::read_dir(path)) {
             let origin = try!(entry);
+            if is_hidden(&origin) {
+                continue;
+            }
             if try!(std::fs::metadata(origin.path())).is_dir() {
                 try!(Self::walk_names(&origin, packages));
             }
@@ -348,6 +616,9 @@ impl PackageInstall {
     fn walk_names(origin: &DirEntry, packages: &mut Vec<PackageIdent>) -> Result<()> {
         for name in try!(std::fs::read_dir(origin.path())) {
             let name = try!(name);
+            if is_hidden(&name) {
+                continue;
+            }
             let origin = origin.file_name().to_string_lossy().into_owned().to_string();
             if try!(std::fs::metadata(name.path())).is_dir() {
                 try!(Self::walk_versions(&origin, &name, packages));
@@ -364,6 +635,9 @@ impl PackageInstall {
                      -> Result<()> {
         for version in try!(std::fs::read_dir(name.path())) {
             let version = try!(version);
+            if is_hidden(&version) {
+                continue;
+            }
             let name = name.file_name().to_string_lossy().into_owned().to_string();
             if try!(std::fs::metadata(version.path())).is_dir() {
                 try!(Self::walk_releases(origin, &name, &version, packages));
@@ -375,13 +649,20 @@ impl PackageInstall {
     /// Helper function for walk_versions. Walks the given release DirEntry for directories and recurses
     /// into them to find version directories. Finally, a Package struct is built and concatenated onto
     /// the given packages vector with the origin, name, version, and release of each.
+    ///
+    /// Release directories without an `IDENT` are skipped, as they were left behind by an install
+    /// which didn't finish.
     fn walk_releases(origin: &String,
                      name: &String,
                      version: &DirEntry,
                      packages: &mut Vec<PackageIdent>)
                      -> Result<()> {
         for release in try!(std::fs::read_dir(version.path())) {
-            let release = try!(release).file_name().to_string_lossy().into_owned().to_string();
+            let release = try!(release);
+            if is_hidden(&release) || !release.path().join(MetaFile::Ident.to_string()).is_file() {
+                continue;
+            }
+            let release = release.file_name().to_string_lossy().into_owned().to_string();
             let version = version.file_name().to_string_lossy().into_owned().to_string();
             let ident =
                 PackageIdent::new(origin.clone(), name.clone(), Some(version), Some(release));
@@ -390,3 +671,98 @@ impl PackageInstall {
         Ok(())
     }
 }
+
+/// Is the directory entry hidden? Staging directories and lock files in the package path are,
+/// and are never packages.
+fn is_hidden(entry: &DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::prelude::*;
+    use std::str::FromStr;
+
+    use tempdir::TempDir;
+
+    use error::Error;
+    use package::{MetaFile, PackageIdent};
+    use super::PackageInstall;
+
+    fn package_install(dir: &TempDir) -> PackageInstall {
+        PackageInstall::new_from_parts(PackageIdent::from_str("core/redis/3.2.4/20161215000000")
+                                           .unwrap(),
+                                       dir.path().to_path_buf(),
+                                       dir.path().to_path_buf(),
+                                       dir.path().to_path_buf())
+    }
+
+    fn write_metafile(dir: &TempDir, file: MetaFile, body: &str) {
+        let mut f = File::create(dir.path().join(file.to_string())).unwrap();
+        f.write_all(body.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn config_actions() {
+        let dir = TempDir::new("pkg").unwrap();
+        write_metafile(&dir,
+                       MetaFile::ConfigActions,
+                       "redis.conf=restart\n\n tls.conf = reconfigure \nsentinel.conf=none\n");
+        assert_eq!(package_install(&dir).config_actions().unwrap(),
+                   vec![("redis.conf".to_string(), "restart".to_string()),
+                        ("tls.conf".to_string(), "reconfigure".to_string()),
+                        ("sentinel.conf".to_string(), "none".to_string())]);
+    }
+
+    #[test]
+    fn config_actions_missing() {
+        let dir = TempDir::new("pkg").unwrap();
+        assert!(package_install(&dir).config_actions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn config_actions_malformed() {
+        let dir = TempDir::new("pkg").unwrap();
+        write_metafile(&dir, MetaFile::ConfigActions, "redis.conf=restart\ntls.conf\n");
+        match package_install(&dir).config_actions() {
+            Err(Error::MetaFileMalformed(MetaFile::ConfigActions)) => (),
+            r => panic!("Expected a malformed metafile, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn optional_metafile() {
+        let dir = TempDir::new("pkg").unwrap();
+        write_metafile(&dir, MetaFile::Source, "http://download.redis.io/redis-3.2.4.tar.gz\n");
+        assert_eq!(package_install(&dir).source().unwrap(),
+                   Some("http://download.redis.io/redis-3.2.4.tar.gz".to_string()));
+    }
+
+    #[test]
+    fn optional_metafile_missing() {
+        let dir = TempDir::new("pkg").unwrap();
+        assert_eq!(package_install(&dir).source().unwrap(), None);
+    }
+
+    #[test]
+    fn optional_metafile_empty() {
+        let dir = TempDir::new("pkg").unwrap();
+        write_metafile(&dir, MetaFile::Source, "\n");
+        assert_eq!(package_install(&dir).source().unwrap(), None);
+    }
+
+    #[test]
+    fn licenses() {
+        let dir = TempDir::new("pkg").unwrap();
+        write_metafile(&dir, MetaFile::Licenses, "BSD-3-Clause\n\n Apache-2.0 \n");
+        assert_eq!(package_install(&dir).licenses().unwrap(),
+                   vec!["BSD-3-Clause".to_string(), "Apache-2.0".to_string()]);
+    }
+
+    #[test]
+    fn licenses_missing() {
+        let dir = TempDir::new("pkg").unwrap();
+        assert!(package_install(&dir).licenses().unwrap().is_empty());
+    }
+}
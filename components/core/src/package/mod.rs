@@ -14,42 +14,73 @@
 
 pub mod archive;
 pub mod ident;
+pub mod ident_req;
+pub mod index;
 pub mod install;
 
 pub use self::archive::{FromArchive, PackageArchive};
 pub use self::ident::{Identifiable, PackageIdent};
+pub use self::ident_req::PackageIdentReq;
+pub use self::index::PackageIndex;
 pub use self::install::PackageInstall;
 
 use std::fmt;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum MetaFile {
+    Binds,
+    BindsOptional,
+    BuildHost,
+    BuilderVersion,
     CFlags,
     Config,
+    ConfigActions,
+    ConfigPerms,
     Deps,
     TDeps,
     Exposes,
+    Exports,
     Ident,
     LdRunPath,
     LdFlags,
+    Licenses,
     Manifest,
     Path,
+    PlanCommit,
+    Shasum,
+    Source,
 }
 
 impl fmt::Display for MetaFile {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let id = match *self {
+            MetaFile::Binds => "BINDS",
+            MetaFile::BindsOptional => "BINDS_OPTIONAL",
+            MetaFile::BuildHost => "BUILD_HOST",
+            MetaFile::BuilderVersion => "BUILDER_VERSION",
             MetaFile::CFlags => "CFLAGS",
             MetaFile::Config => "default.toml",
+            MetaFile::ConfigActions => "CONFIG_ACTIONS",
+            MetaFile::ConfigPerms => "CONFIG_PERMS",
             MetaFile::Deps => "DEPS",
             MetaFile::TDeps => "TDEPS",
             MetaFile::Exposes => "EXPOSES",
+            MetaFile::Exports => "EXPORTS",
             MetaFile::Ident => "IDENT",
             MetaFile::LdRunPath => "LD_RUN_PATH",
             MetaFile::LdFlags => "LDFLAGS",
+            MetaFile::Licenses => "LICENSES",
             MetaFile::Manifest => "MANIFEST",
             MetaFile::Path => "PATH",
+            MetaFile::PlanCommit => "PLAN_COMMIT",
+            MetaFile::Shasum => "SHASUM",
+            MetaFile::Source => "SOURCE",
         };
         write!(f, "{}", id)
     }
 }
+
+/// Splits a metafile holding one entry per line into its entries, skipping blank lines.
+fn split_lines(data: &str) -> Vec<String> {
+    data.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).map(|l| l.to_string()).collect()
+}
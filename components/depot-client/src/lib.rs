@@ -34,7 +34,9 @@ use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
 use broadcast::BroadcastWriter;
-use hab_core::package::{Identifiable, PackageArchive};
+use hab_core::crypto::{RevocationList, SigKeyPair};
+use hab_core::crypto::keys::parse_name_with_rev;
+use hab_core::package::{Identifiable, PackageArchive, PackageIdent};
 use hab_http::new_hyper_client;
 use hyper::client::{Body, IntoUrl, Response};
 use hyper::status::StatusCode;
@@ -144,6 +146,59 @@ impl Client {
         }
     }
 
+    /// Download the latest revocation list for an origin from a remote Depot, and add it to the
+    /// revocation lists in the key cache. The public key which signed the list is downloaded too
+    /// if it isn't in the key cache yet. Returns `None` if the origin has never revoked a key.
+    ///
+    /// # Failures
+    ///
+    /// * Remote Depot is not available
+    /// * The signer's public key can't be downloaded
+    /// * The list doesn't verify with the signer's public key, which is a `HabitatCore` error
+    pub fn fetch_revocation_list<P: AsRef<Path> + ?Sized>(&self,
+                                                          origin: &str,
+                                                          cache_key_path: &P)
+                                                          -> Result<Option<RevocationList>> {
+        let url = try!(self.url_join(&format!("origins/{}/revocations", origin)));
+        debug!("GET {} with {:?}", &url, &self.client);
+        let mut res = try!(self.client.get(url).send());
+        match res.status {
+            StatusCode::Ok => (),
+            StatusCode::NotFound => return Ok(None),
+            code => return Err(Error::HTTP(code)),
+        }
+        let mut content = String::new();
+        try!(res.read_to_string(&mut content));
+        let signer = try!(RevocationList::signer_of(&content));
+        if SigKeyPair::get_public_key_path(&signer, cache_key_path).is_err() {
+            let (name, rev) = try!(parse_name_with_rev(&signer));
+            try!(self.fetch_origin_key(&name, &rev, cache_key_path, None));
+        }
+        let list = try!(RevocationList::write_file_from_str(&content, cache_key_path));
+        Ok(Some(list))
+    }
+
+    /// Upload a revocation list for an origin to a remote Depot, replacing the one it serves.
+    ///
+    /// # Failures
+    ///
+    /// * Remote Depot is not available
+    /// * The list isn't signed with a key of the origin
+    pub fn put_revocation_list(&self, origin: &str, content: &str, token: &str) -> Result<()> {
+        let mut headers = Headers::new();
+        headers.set(Authorization(Bearer { token: token.to_string() }));
+        let url = try!(self.url_join(&format!("origins/{}/revocations", origin)));
+        let result = self.client
+            .post(url)
+            .headers(headers)
+            .body(content)
+            .send();
+        match result {
+            Ok(Response { status: StatusCode::Created, .. }) => Ok(()),
+            Ok(Response { status: code, .. }) => Err(Error::HTTP(code)),
+            Err(e) => Err(Error::from(e)),
+        }
+    }
 
     /// Upload a secret origin key to a remote Depot.
     ///
@@ -245,6 +300,53 @@ impl Client {
         Ok(package)
     }
 
+    /// Returns every release of a package in a remote Depot, following the Depot's pagination.
+    ///
+    /// # Failures
+    ///
+    /// * Package cannot be found
+    /// * Remote Depot is not available
+    pub fn show_package_versions(&self, origin: &str, name: &str) -> Result<Vec<PackageIdent>> {
+        let url = try!(self.url_join(&format!("pkgs/{}/{}", origin, name)));
+        let mut idents = Vec::new();
+        let mut range = 0;
+        loop {
+            let mut headers = Headers::new();
+            headers.set_raw("range", vec![range.to_string().into_bytes()]);
+            debug!("GET {} with {:?}", &url, &self.client);
+            let mut res = try!(self.client.get(url.clone()).headers(headers).send());
+            let partial = match res.status {
+                StatusCode::Ok => false,
+                StatusCode::PartialContent => true,
+                StatusCode::NotFound => {
+                    return Err(Error::RemotePackageNotFound(PackageIdent::new(origin,
+                                                                              name,
+                                                                              None,
+                                                                              None)))
+                }
+                code => return Err(Error::HTTP(code)),
+            };
+            let mut encoded = String::new();
+            try!(res.read_to_string(&mut encoded));
+            debug!("Body: {:?}", encoded);
+            let page: Vec<depotsrv::PackageIdent> = json::decode(&encoded).unwrap();
+            idents.extend(page.into_iter().map(|i| i.into()));
+            if !partial {
+                break;
+            }
+            let next = res.headers
+                .get_raw("Next-Range")
+                .and_then(|v| v.first())
+                .and_then(|v| String::from_utf8(v.clone()).ok())
+                .and_then(|v| v.parse::<usize>().ok());
+            match next {
+                Some(next) if next > range => range = next,
+                _ => break,
+            }
+        }
+        Ok(idents)
+    }
+
     /// Upload a package to a remote Depot.
     ///
     /// # Failures
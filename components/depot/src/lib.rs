@@ -123,6 +123,10 @@ impl Depot {
             .join(format!("{}-{}.pub", key, rev))
     }
 
+    fn revocation_list_path(&self, origin: &str) -> PathBuf {
+        Path::new(&self.config.path).join("revocations").join(format!("{}.rvk", origin))
+    }
+
     fn keys_path(&self) -> PathBuf {
         Path::new(&self.config.path).join("keys")
     }
@@ -23,7 +23,7 @@ use bodyparser;
 use dbcache::{self, BasicSet, IndexSet};
 use hab_core::package::{Identifiable, FromArchive, PackageArchive};
 use hab_core::crypto::keys::{self, PairType};
-use hab_core::crypto::SigKeyPair;
+use hab_core::crypto::{REVOCATION_LIST_VERSION, SigKeyPair};
 use hab_net;
 use hab_net::config::RouteAddrs;
 use hab_net::routing::{Broker, BrokerContext};
@@ -619,6 +619,66 @@ fn upload_origin_key(depot: &Depot, req: &mut Request) -> IronResult<Response> {
     Ok(response)
 }
 
+fn upload_revocation_list(depot: &Depot, req: &mut Request) -> IronResult<Response> {
+    debug!("Upload revocation list {:?}", req);
+    let origin = match req.extensions.get::<Router>().unwrap().find("origin") {
+        Some(origin) => origin.to_string(),
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+
+    if !depot.config.insecure {
+        let session = match authenticate(depot, req) {
+            Ok(session) => session,
+            Err(response) => return Ok(response),
+        };
+
+        if !check_origin_access(&depot, session.get_id(), &origin) {
+            return Ok(Response::with(status::Forbidden));
+        }
+    }
+
+    let mut content = String::new();
+    if let Err(e) = req.body.read_to_string(&mut content) {
+        debug!("Can't read revocation list upload content: {}", e);
+        return Ok(Response::with(status::BadRequest));
+    }
+
+    // The signature is checked by every client with the signer's public key; here we only make
+    // sure the list is one, and is signed by a key of this origin.
+    let mut lines = content.lines();
+    let signer_origin = match (lines.next(), lines.next().map(keys::parse_name_with_rev)) {
+        (Some(REVOCATION_LIST_VERSION), Some(Ok((name, _)))) => name,
+        _ => {
+            debug!("Invalid revocation list content");
+            return Ok(Response::with(status::BadRequest));
+        }
+    };
+    if signer_origin != origin {
+        debug!("Revocation list for {} is signed by {}", &origin, &signer_origin);
+        return Ok(Response::with(status::BadRequest));
+    }
+
+    try!(write_string_to_file(&depot.revocation_list_path(&origin), content));
+    Ok(Response::with((status::Created, format!("/origins/{}/revocations", &origin))))
+}
+
+fn download_revocation_list(depot: &Depot, req: &mut Request) -> IronResult<Response> {
+    debug!("Download revocation list {:?}", req);
+    let params = req.extensions.get::<Router>().unwrap();
+
+    let origin = match params.find("origin") {
+        Some(origin) => origin,
+        None => return Ok(Response::with(status::BadRequest)),
+    };
+    let list_path = depot.revocation_list_path(&origin);
+    if !list_path.is_file() {
+        return Ok(Response::with(status::NotFound));
+    }
+    let mut response = Response::with((status::Ok, list_path));
+    dont_cache_response(&mut response);
+    Ok(response)
+}
+
 fn upload_origin_secret_key(depot: &Depot, req: &mut Request) -> IronResult<Response> {
     debug!("Upload Origin Secret Key {:?}", req);
     let session = match authenticate(depot, req) {
@@ -1266,6 +1326,8 @@ pub fn router(depot: Arc<Depot>) -> Result<Chain> {
     let depot25 = depot.clone();
     let depot26 = depot.clone();
     let depot27 = depot.clone();
+    let depot28 = depot.clone();
+    let depot29 = depot.clone();
 
     let router = router!(
         get "/views" => move |r: &mut Request| list_views(&depot1, r),
@@ -1322,6 +1384,12 @@ pub fn router(depot: Arc<Depot>) -> Result<Chain> {
         post "/origins/:origin/keys/:revision" => {
             move |r: &mut Request| upload_origin_key(&depot23, r)
         },
+        get "/origins/:origin/revocations" => {
+            move |r: &mut Request| download_revocation_list(&depot28, r)
+        },
+        post "/origins/:origin/revocations" => {
+            move |r: &mut Request| upload_revocation_list(&depot29, r)
+        },
         post "/origins/:origin/secret_keys/:revision" => {
             move |r: &mut Request| upload_origin_secret_key(&depot24, r)
         },
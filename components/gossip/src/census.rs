@@ -0,0 +1,298 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Census entries.
+//!
+//! A `CensusEntry` is a single supervisor's entry in the census of its service group. Entries are
+//! shared around the ring as rumors; the census built from them lives in the Supervisor.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use hcore::util::sys;
+use uuid::Uuid;
+
+use lamport_clock::LamportClock;
+use member::MemberId;
+
+pub type CensusEntryId = Uuid;
+
+/// A CensusEntry. Manages all the data about a given member of the census.
+#[derive(Debug, Clone, RustcDecodable, RustcEncodable, Eq)]
+pub struct CensusEntry {
+    pub id: CensusEntryId,
+    pub member_id: MemberId,
+    pub hostname: String,
+    pub ip: String,
+    pub suitability: u64,
+    pub port: Option<String>,
+    pub exposes: Option<Vec<String>>,
+    pub exports: Option<Vec<String>>,
+    pub cfg: Option<BTreeMap<String, String>>,
+    pub zone: Option<String>,
+    pub leader: bool,
+    pub follower: bool,
+    pub data_init: bool,
+    pub vote: Option<String>,
+    pub election: Option<bool>,
+    pub needs_write: Option<bool>,
+    pub initialized: bool,
+    keep_me: bool,
+    pub service: String,
+    pub group: String,
+    pub alive: bool,
+    pub suspect: bool,
+    pub confirmed: bool,
+    pub detached: bool,
+    pub incarnation: LamportClock,
+}
+
+impl CensusEntry {
+    /// Create a new CensusEntry for this supervisor.
+    pub fn new<S>(service: S, group: S, member_id: MemberId) -> CensusEntry
+        where S: Into<String>
+    {
+        CensusEntry {
+            id: Uuid::new_v4(),
+            member_id: member_id,
+            hostname: sys::uname().map(|u| u.node_name).unwrap_or(String::from("unknown")),
+            ip: sys::ip(None).unwrap_or(String::from("127.0.0.1")),
+            suitability: 0,
+            port: None,
+            exposes: None,
+            exports: None,
+            cfg: None,
+            zone: None,
+            leader: false,
+            follower: false,
+            data_init: false,
+            vote: None,
+            election: None,
+            needs_write: None,
+            initialized: false,
+            keep_me: true,
+            alive: true,
+            suspect: false,
+            confirmed: false,
+            detached: false,
+            service: service.into(),
+            group: group.into(),
+            incarnation: LamportClock::new(),
+        }
+    }
+
+    pub fn needs_write(&self) -> bool {
+        self.needs_write.is_some()
+    }
+
+    /// Set our suitability number. This is an arbitrary determination of our 'suitability' to a
+    /// task; most likely, being the leader in an election.
+    pub fn suitability(&mut self, suitability: u64) {
+        self.suitability = suitability;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set a port number; often used as the default for watches
+    pub fn port(&mut self, port: Option<String>) {
+        self.port = port;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set an array of port numbers we expose.
+    pub fn exposes(&mut self, exposes: Option<Vec<String>>) {
+        self.exposes = exposes;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set the names of the config values we export to services that bind to us.
+    pub fn exports(&mut self, exports: Option<Vec<String>>) {
+        self.exports = exports;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set the values of the config we export to services that bind to us.
+    pub fn cfg(&mut self, cfg: Option<BTreeMap<String, String>>) {
+        if self.cfg != cfg {
+            self.cfg = cfg;
+            self.incarnation.increment();
+            self.needs_write = Some(true);
+        }
+    }
+
+    /// Set our status at the leader.
+    pub fn leader(&mut self, leader: bool) {
+        if self.leader != leader {
+            self.leader = leader;
+            self.incarnation.increment();
+            self.needs_write = Some(true);
+        }
+    }
+
+    /// Set our status as a follower.
+    pub fn follower(&mut self, follower: bool) {
+        if self.follower != follower {
+            self.follower = follower;
+            self.incarnation.increment();
+            self.needs_write = Some(true);
+        }
+    }
+
+    /// Set our application initialization status to true.
+    pub fn initialized(&mut self) {
+        self.initialized = true;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set our status on having initialzied data.
+    pub fn data_init(&mut self, data_init: bool) {
+        if self.data_init != data_init {
+            self.data_init = data_init;
+            self.incarnation.increment();
+            self.needs_write = Some(true);
+        }
+    }
+
+    /// Set our vote.
+    pub fn vote(&mut self, vote: Option<String>) {
+        self.vote = vote;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Are we in an election?
+    pub fn election(&mut self, election: Option<bool>) {
+        self.election = election;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set us to alive.
+    pub fn set_alive(&mut self) {
+        self.alive = true;
+        self.suspect = false;
+        self.confirmed = false;
+        self.detached = false;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set our suspectness.
+    pub fn set_suspect(&mut self) {
+        self.alive = false;
+        self.suspect = true;
+        self.confirmed = false;
+        self.detached = false;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set our confirmedness.
+    pub fn set_confirmed(&mut self) {
+        self.alive = false;
+        self.suspect = false;
+        self.confirmed = true;
+        self.detached = false;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Set our detachedness.
+    pub fn set_detached(&mut self) {
+        self.alive = false;
+        self.suspect = false;
+        self.confirmed = false;
+        self.detached = true;
+        self.incarnation.increment();
+        self.needs_write = Some(true);
+    }
+
+    /// Return the string we use for this CensusEntry when it is a candidate in an election.
+    pub fn candidate_string(&self) -> String {
+        format!("{}", self.id)
+    }
+
+    /// Return the service.group string
+    pub fn service_group(&self) -> String {
+        format!("{}.{}", self.service, self.group)
+    }
+
+    /// Update this entry from another entry. If the other side has a higher incarnation, take it
+    /// as the new you.
+    pub fn update_via(&mut self, other_ce: CensusEntry) -> bool {
+        if other_ce.incarnation > self.incarnation {
+            *self = other_ce;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn written(&mut self) {
+        self.needs_write = None;
+    }
+}
+
+impl PartialEq for CensusEntry {
+    // We are equal, but we don't care about some fields.
+    fn eq(&self, other: &CensusEntry) -> bool {
+        if self.id != other.id {
+            false
+        } else if self.incarnation != other.incarnation {
+            false
+        } else if self.hostname != other.hostname {
+            false
+        } else if self.ip != other.ip {
+            false
+        } else if self.suitability != other.suitability {
+            false
+        } else if self.port != other.port {
+            false
+        } else if self.exposes != other.exposes {
+            false
+        } else if self.exports != other.exports {
+            false
+        } else if self.cfg != other.cfg {
+            false
+        } else if self.zone != other.zone {
+            false
+        } else if self.leader != other.leader {
+            false
+        } else if self.follower != other.follower {
+            false
+        } else if self.data_init != other.data_init {
+            false
+        } else if self.vote != other.vote {
+            false
+        } else if self.election != other.election {
+            false
+        } else if self.service != other.service {
+            false
+        } else if self.group != other.group {
+            false
+        } else {
+            true
+        }
+    }
+}
+
+impl fmt::Display for CensusEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.service_group(), self.id)
+    }
+}
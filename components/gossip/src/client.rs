@@ -15,19 +15,27 @@
 //! The Gossip Client.
 //!
 //! This module takes a `UtpSocket`, and lets you send and receive messages with it. Messages are
-//! encoded with json.
+//! encoded with json, using the schema negotiated with the remote (see the `schema` module).
+//! Until a schema is set, messages are sent with the legacy schema every member understands.
+//!
+//! It also has `query`, which reads back a member's view of the ring without joining it.
 //!
 
-use std::net::ToSocketAddrs;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::str;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use common::wire_message::WireMessage;
 use hcore::crypto::SymKey;
 use rustc_serialize::json;
-use utp::UtpSocket;
+use utp::{UtpListener, UtpSocket};
 
-use error::Result;
-use gossip::rumor::{Protocol, Peer, RumorList};
+use error::{Error, Result};
+use member::MemberId;
+use rumor::{Protocol, Peer, RumorList};
+use schema::{self, LEGACY_SCHEMA};
 
 pub const BUFFER_SIZE: usize = 10000;
 
@@ -35,6 +43,7 @@ pub const BUFFER_SIZE: usize = 10000;
 pub struct Client<'a> {
     pub socket: UtpSocket,
     ring_key: Option<&'a SymKey>,
+    schema: u32,
 }
 
 impl<'a> Client<'a> {
@@ -48,6 +57,7 @@ impl<'a> Client<'a> {
         Ok(Client {
             socket: socket,
             ring_key: ring_key,
+            schema: LEGACY_SCHEMA,
         })
     }
 
@@ -56,9 +66,16 @@ impl<'a> Client<'a> {
         Client {
             socket: socket,
             ring_key: ring_key,
+            schema: LEGACY_SCHEMA,
         }
     }
 
+    /// Send messages with the newest schema both we and the remote speak, given the schema the
+    /// remote advertised.
+    pub fn set_schema(&mut self, theirs: Option<u32>) {
+        self.schema = schema::negotiate(theirs);
+    }
+
     /// Send a ping.
     ///
     /// # Errors
@@ -99,7 +116,7 @@ impl<'a> Client<'a> {
     /// # Errors
     ///
     /// * We cannot receive the data from the socket
-    /// * We cannot decode the data into a `gossip::message::Protocol`
+    /// * We cannot decode the data into a `rumor::Protocol`
     pub fn recv_message(&mut self) -> Result<Protocol> {
         let mut buf = [0u8; BUFFER_SIZE];
         let mut json_str = String::new();
@@ -121,7 +138,7 @@ impl<'a> Client<'a> {
                json_str);
 
         let wire_msg: WireMessage = try!(json::decode(&json_str));
-        Ok(try!(wire_msg.msg(self.ring_key)))
+        schema::from_wire(&wire_msg, self.ring_key)
     }
 
     /// Send a message.
@@ -132,10 +149,7 @@ impl<'a> Client<'a> {
     /// * We fail to send the encoded buffer to the remote
     pub fn send_message(&mut self, msg: Protocol) -> Result<()> {
         let encoded = {
-            let wire_msg = match self.ring_key.as_ref() {
-                Some(key) => try!(WireMessage::encrypted(&msg, &key)),
-                None => try!(WireMessage::plain(&msg)),
-            };
+            let wire_msg = try!(schema::to_wire(&msg, self.schema, self.ring_key));
             try!(json::encode(&wire_msg))
         };
         debug!("Encoded message {:#?}", encoded);
@@ -144,3 +158,58 @@ impl<'a> Client<'a> {
         Ok(())
     }
 }
+
+/// Asks the member listening at `dst` for everything it knows about the ring, waiting at most
+/// `timeout` for an answer.
+///
+/// We ping it as a member it has never seen before. Every rumor it has is hot for a new member,
+/// so its Ack carries its whole view of the ring: members, census entries, elections and files.
+/// We send no rumors of our own, so we never become part of the ring. The Ack arrives on a new
+/// connection, so the member must be able to reach us on the address we connected from.
+///
+/// # Errors
+///
+/// * If we cannot connect to the member, or listen for its Ack
+/// * If the member doesn't answer within the timeout
+/// * If the member answers with something other than an Ack
+pub fn query(dst: &str, ring_key: Option<&SymKey>, timeout: Duration) -> Result<RumorList> {
+    let mut client = try!(Client::new(dst, ring_key));
+    let local_ip = try!(client.socket.local_addr()).ip();
+    let listener = try!(UtpListener::bind(SocketAddr::new(local_ip, 0)));
+    let listening_on = try!(listener.local_addr());
+
+    let (tx, rx) = mpsc::channel();
+    try!(thread::Builder::new()
+        .name("query".to_string())
+        .spawn(move || {
+            let _ = tx.send(listener.accept());
+        }));
+
+    let me = Peer::new(MemberId::new_v4(), listening_on.to_string());
+    try!(client.ping(me, RumorList::new()));
+    // The member reads our ping until we hang up
+    drop(client);
+
+    let accepted = try!(wait_for(&rx, timeout).ok_or(Error::NoResponse(dst.to_string())));
+    let (socket, _src) = try!(accepted);
+    match try!(Client::from_socket(socket, ring_key).recv_message()) {
+        Protocol::Ack(_, rumors) => Ok(rumors),
+        _ => Err(Error::UnexpectedResponse(dst.to_string())),
+    }
+}
+
+fn wait_for<T>(rx: &mpsc::Receiver<T>, timeout: Duration) -> Option<T> {
+    let tick = Duration::from_millis(100);
+    let mut waited = Duration::new(0, 0);
+    while waited < timeout {
+        match rx.try_recv() {
+            Ok(value) => return Some(value),
+            Err(mpsc::TryRecvError::Empty) => {
+                thread::sleep(tick);
+                waited = waited + tick;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => return None,
+        }
+    }
+    None
+}
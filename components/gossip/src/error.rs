@@ -0,0 +1,97 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+use std::str;
+
+use common;
+use rustc_serialize::json;
+
+#[derive(Debug)]
+pub enum Error {
+    HabitatCommon(common::Error),
+    IO(io::Error),
+    JsonDecode(json::DecoderError),
+    JsonEncode(json::EncoderError),
+    NoResponse(String),
+    StrFromUtf8(str::Utf8Error),
+    UnexpectedResponse(String),
+    UnknownProtocol(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            Error::HabitatCommon(ref e) => format!("{}", e),
+            Error::IO(ref e) => format!("{}", e),
+            Error::JsonDecode(ref e) => format!("JSON decoding error: {}", e),
+            Error::JsonEncode(ref e) => format!("JSON encoding error: {}", e),
+            Error::NoResponse(ref peer) => format!("No response from {}", peer),
+            Error::StrFromUtf8(ref e) => format!("{}", e),
+            Error::UnexpectedResponse(ref peer) => format!("Unexpected response from {}", peer),
+            Error::UnknownProtocol(ref k) => format!("Unknown gossip protocol message {}", k),
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::HabitatCommon(ref err) => err.description(),
+            Error::IO(ref err) => err.description(),
+            Error::JsonDecode(_) => "JSON decoding error",
+            Error::JsonEncode(_) => "JSON encoding error",
+            Error::NoResponse(_) => "No response from a gossip peer",
+            Error::StrFromUtf8(_) => "Failed to convert a string as UTF-8",
+            Error::UnexpectedResponse(_) => "Unexpected response from a gossip peer",
+            Error::UnknownProtocol(_) => "Unknown gossip protocol message",
+        }
+    }
+}
+
+impl From<common::Error> for Error {
+    fn from(err: common::Error) -> Error {
+        Error::HabitatCommon(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::IO(err)
+    }
+}
+
+impl From<json::DecoderError> for Error {
+    fn from(err: json::DecoderError) -> Error {
+        Error::JsonDecode(err)
+    }
+}
+
+impl From<json::EncoderError> for Error {
+    fn from(err: json::EncoderError) -> Error {
+        Error::JsonEncode(err)
+    }
+}
+
+impl From<str::Utf8Error> for Error {
+    fn from(err: str::Utf8Error) -> Error {
+        Error::StrFromUtf8(err)
+    }
+}
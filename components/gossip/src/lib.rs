@@ -0,0 +1,43 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The gossip wire protocol shared by the Supervisor and the `hab` CLI.
+//!
+//! This crate holds everything needed to talk to a gossip ring: the rumor types and their
+//! payloads, the versioned wire encoding, and the client. The Supervisor builds its SWIM server
+//! and failure detector on top of it; `hab` uses it to inject rumors into a ring, and to read
+//! back what a member knows about it.
+
+extern crate habitat_common as common;
+extern crate habitat_core as hcore;
+#[macro_use]
+extern crate log;
+extern crate rand;
+extern crate rustc_serialize;
+extern crate utp;
+extern crate uuid;
+
+pub mod census;
+pub mod client;
+pub mod election;
+pub mod error;
+pub mod lamport_clock;
+pub mod member;
+pub mod rumor;
+pub mod schema;
+
+pub use self::error::{Error, Result};
+
+/// The default port for the Gossip protocol
+pub static GOSSIP_DEFAULT_PORT: usize = 9634;
@@ -25,11 +25,15 @@ use std::ops::Deref;
 use rand::{thread_rng, Rng};
 use uuid::Uuid;
 
-use gossip::lamport_clock::LamportClock;
+use lamport_clock::LamportClock;
+use schema::SCHEMA_VERSION;
 
 // How many members do we send a PingReq to for a failed node?
 static PINGREQ_MEMBERS: usize = 5;
 
+// Members in other zones are only probed once every CROSS_ZONE_ROUNDS trips through the list.
+static CROSS_ZONE_ROUNDS: usize = 5;
+
 /// Every time we receive a Suspect or Confirmed message about our own entry in the MemberList, we
 /// update our Incarnation.
 pub type Incarnation = LamportClock;
@@ -47,6 +51,16 @@ pub type MemberId = Uuid;
 
 /// A member in the gossip ring. Members can be marked 'permanent', which means they will always be
 /// ping-ed, even during failure.
+///
+/// Members may also carry a zone (a datacenter, region, or availability zone). Members in the same
+/// zone are probed at the normal rate; members across zones are probed less often, and with
+/// longer timeouts.
+///
+/// Each member advertises the newest gossip schema it speaks, so we know how to encode the
+/// messages we send it.
+///
+/// A Suspect member also records which member first suspected it, so the members that relay the
+/// suspicion aren't mistaken for members that suspect it themselves.
 #[derive(Clone, Debug, PartialEq, Eq, RustcDecodable, RustcEncodable)]
 pub struct Member {
     pub id: MemberId,
@@ -56,6 +70,9 @@ pub struct Member {
     pub incarnation: Incarnation,
     pub health: Health,
     pub permanent: bool,
+    pub zone: Option<String>,
+    pub schema: Option<u32>,
+    pub suspected_by: Option<MemberId>,
 }
 
 impl Member {
@@ -69,6 +86,18 @@ impl Member {
             incarnation: Incarnation::new(),
             health: Health::Alive,
             permanent: permanent,
+            zone: None,
+            schema: Some(SCHEMA_VERSION),
+            suspected_by: None,
+        }
+    }
+
+    /// Returns true if this member and the other are in the same zone. Members without a zone
+    /// are considered to be in every zone.
+    pub fn same_zone(&self, other: &Member) -> bool {
+        match (&self.zone, &other.zone) {
+            (&Some(ref mine), &Some(ref theirs)) => mine == theirs,
+            _ => true,
         }
     }
 
@@ -96,6 +125,7 @@ impl Member {
                 if self.id == *my_id {
                     self.incarnation.increment();
                     self.health = Health::Alive;
+                    self.suspected_by = None;
                     return true;
                 } else {
                     mem::replace(self, rhs);
@@ -134,6 +164,7 @@ impl fmt::Display for Member {
 pub struct MemberList {
     members: HashMap<MemberId, Member>,
     position: usize,
+    round: usize,
     order: Vec<Uuid>,
     pub my_id: MemberId,
 }
@@ -144,6 +175,7 @@ impl MemberList {
         let mut ml = MemberList {
             members: HashMap::new(),
             position: 0,
+            round: 0,
             order: Vec::new(),
             my_id: my_member.id.clone(),
         };
@@ -163,20 +195,33 @@ impl MemberList {
     /// Otherwise, we return `Some<&Member>`. When we have reached the end of the list, we
     /// randomize the order, and re-set our position to the top of the list.
     ///
-    /// This ensures that every member gets touched in each gossip round.
+    /// This ensures that every member in our zone gets touched in each gossip round. Members in
+    /// other zones are skipped, except for every `CROSS_ZONE_ROUNDS` round, so we don't probe
+    /// across the WAN at LAN rates.
     pub fn next(&mut self) -> Option<&Member> {
         if self.order.len() == 0 {
             return None;
         };
-        if self.position < self.order.len() {
-            let ref member = self.members.get(&self.order[self.position]).unwrap();
+        loop {
+            if self.position >= self.order.len() {
+                self.position = 0;
+                self.round = self.round.wrapping_add(1);
+                let mut rng = thread_rng();
+                rng.shuffle(&mut self.order);
+            }
+            let id = self.order[self.position].clone();
             self.position = self.position + 1;
-            Some(member)
-        } else {
-            self.position = 0;
-            let mut rng = thread_rng();
-            rng.shuffle(&mut self.order);
-            self.next()
+            if self.round % CROSS_ZONE_ROUNDS == 0 || !self.is_cross_zone(&id) {
+                return self.members.get(&id);
+            }
+        }
+    }
+
+    /// Return true if the given member is in a different zone than we are.
+    pub fn is_cross_zone(&self, member_id: &MemberId) -> bool {
+        match (self.members.get(&self.my_id), self.members.get(member_id)) {
+            (Some(me), Some(member)) => !me.same_zone(member),
+            _ => false,
         }
     }
 
@@ -214,15 +259,17 @@ impl MemberList {
     pub fn alive(&mut self, member_id: &MemberId) {
         if let Some(mut member) = self.members.get_mut(member_id) {
             member.health = Health::Alive;
+            member.suspected_by = None;
         }
     }
 
-    /// Set a members health to Suspect.
+    /// Set a members health to Suspect, recording that we are the ones who suspect it.
     pub fn suspect(&mut self, member_id: &MemberId) {
         if let Some(mut member) = self.members.get_mut(member_id) {
             if member.health != Health::Confirmed {
                 warn!("Member {} is suspect", member_id);
                 member.health = Health::Suspect;
+                member.suspected_by = Some(self.my_id.clone());
             }
         }
     }
@@ -238,7 +285,7 @@ impl MemberList {
     }
 
     /// Selects PINGREQ_MEMBERS number of members to use as targets for a PingReq. The members are
-    /// chosen completely randomly.
+    /// chosen randomly, preferring members in our own zone.
     pub fn pingreq_targets(&self, myself: &MemberId, target: &MemberId) -> Vec<Member> {
         let mut rng = thread_rng();
         let mut values: Vec<&Member> = self.members
@@ -246,6 +293,10 @@ impl MemberList {
             .filter(|m| &m.id != myself && &m.id != target)
             .collect();
         rng.shuffle(&mut values);
+        // The sort is stable, so members stay shuffled within each zone.
+        if let Some(me) = self.members.get(myself) {
+            values.sort_by_key(|m| !me.same_zone(m));
+        }
         let mut results: Vec<Member> = Vec::new();
         for member in values.into_iter().take(PINGREQ_MEMBERS) {
             results.push(member.clone());
@@ -294,7 +345,7 @@ impl Deref for MemberList {
 #[cfg(test)]
 mod test {
     mod member {
-        use gossip::member::{Member, Health, MemberId};
+        use member::{Member, Health, MemberId};
 
         fn bobo() -> Member {
             Member::new(String::from("bobo"),
@@ -303,6 +354,20 @@ mod test {
                         false)
         }
 
+        #[test]
+        fn same_zone() {
+            let mut bobo = bobo();
+            let mut other_bobo = bobo();
+            // No zones means everyone is together
+            assert!(bobo.same_zone(&other_bobo));
+            bobo.zone = Some(String::from("us-west-2a"));
+            assert!(bobo.same_zone(&other_bobo));
+            other_bobo.zone = Some(String::from("us-west-2a"));
+            assert!(bobo.same_zone(&other_bobo));
+            other_bobo.zone = Some(String::from("eu-central-1a"));
+            assert!(!bobo.same_zone(&other_bobo));
+        }
+
         #[test]
         fn update_via_rhs_higher_incarnation() {
             let my_id = MemberId::new_v4();
@@ -409,7 +474,7 @@ mod test {
 
     mod member_list {
         use uuid::Uuid;
-        use gossip::member::{Member, MemberList, Health};
+        use member::{Member, MemberList, Health, CROSS_ZONE_ROUNDS};
 
         fn new_member_list() -> MemberList {
             let james = Member::new(String::from("james"),
@@ -516,6 +581,98 @@ mod test {
             assert_eq!(ml.pingreq_targets(&my_id, &dead_id).len(), 5);
         }
 
+        #[test]
+        fn suspect_records_who_suspects() {
+            let mut ml = new_member_list();
+            let member = Member::new(String::from("a.foo.com"),
+                                     String::from("192.168.1.1"),
+                                     String::from("192.168.1.1:4312"),
+                                     false);
+            let id = member.id.clone();
+            ml.insert(member);
+
+            ml.suspect(&id);
+            assert_eq!(ml.get(&id).unwrap().suspected_by, Some(ml.my_id.clone()));
+            ml.alive(&id);
+            assert_eq!(ml.get(&id).unwrap().suspected_by, None);
+        }
+
+        #[test]
+        fn next_skips_cross_zone_members() {
+            let mut me = Member::new(String::from("james"),
+                                     String::from("192.168.1.1"),
+                                     String::from("192.168.1.1:4312"),
+                                     false);
+            me.zone = Some(String::from("east"));
+            let mut ml = MemberList::new(me);
+            let mut local = Member::new(String::from("local.foo.com"),
+                                        String::from("192.168.1.2"),
+                                        String::from("192.168.1.2:4312"),
+                                        false);
+            local.zone = Some(String::from("east"));
+            ml.insert(local);
+            let mut remote = Member::new(String::from("remote.foo.com"),
+                                         String::from("10.0.0.1"),
+                                         String::from("10.0.0.1:4312"),
+                                         false);
+            remote.zone = Some(String::from("west"));
+            let remote_id = remote.id.clone();
+            ml.insert(remote);
+            assert!(ml.is_cross_zone(&remote_id));
+
+            // The first round includes everyone
+            let mut hits = vec![];
+            for _ in 0..3 {
+                hits.push(ml.next().unwrap().hostname.clone());
+            }
+            assert!(hits.iter().any(|x| x == "remote.foo.com"));
+
+            // The following rounds only include our own zone
+            for _ in 0..((CROSS_ZONE_ROUNDS - 1) * 2) {
+                assert!(ml.next().unwrap().hostname != "remote.foo.com");
+            }
+
+            // Until we come back around to a cross-zone round
+            let mut hits = vec![];
+            for _ in 0..3 {
+                hits.push(ml.next().unwrap().hostname.clone());
+            }
+            assert!(hits.iter().any(|x| x == "remote.foo.com"));
+        }
+
+        #[test]
+        fn pingreq_targets_prefer_same_zone() {
+            let mut me = Member::new(String::from("james"),
+                                     String::from("192.168.1.1"),
+                                     String::from("192.168.1.1:4312"),
+                                     false);
+            me.zone = Some(String::from("east"));
+            let my_id = me.id.clone();
+            let mut ml = MemberList::new(me);
+            for x in 0..5 {
+                let mut remote = Member::new(format!("remote{}.foo.com", x),
+                                             String::from("10.0.0.1"),
+                                             String::from("10.0.0.1:4312"),
+                                             false);
+                remote.zone = Some(String::from("west"));
+                ml.insert(remote);
+            }
+            for x in 0..3 {
+                let mut local = Member::new(format!("local{}.foo.com", x),
+                                            String::from("192.168.1.2"),
+                                            String::from("192.168.1.2:4312"),
+                                            false);
+                local.zone = Some(String::from("east"));
+                ml.insert(local);
+            }
+            let target_id = Uuid::new_v4();
+            let targets = ml.pingreq_targets(&my_id, &target_id);
+            assert_eq!(targets.len(), 5);
+            for target in targets.iter().take(3) {
+                assert_eq!(target.zone, Some(String::from("east")));
+            }
+        }
+
         #[test]
         fn isolated() {
             let mut ml = new_member_list();
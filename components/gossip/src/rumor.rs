@@ -30,16 +30,19 @@ use election::Election;
 /// How many times does a rumor get shared with a member before we stop sharing it?
 pub const COLD_AFTER: usize = 3;
 
-use gossip::member::{Member, MemberId};
+use member::{Member, MemberId};
+use schema::SCHEMA_VERSION;
 
 /// A Peer is a representation of a member; it tracks how to contact the member, and whether this
-/// request is actually being proxied during a PingReq operation.
+/// request is actually being proxied during a PingReq operation. It also advertises the newest
+/// gossip schema the member speaks; peers from before schemas were versioned leave it unset.
 #[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
 pub struct Peer {
     pub member_id: MemberId,
     pub listening_on: String,
     pub proxy_through: Option<String>,
     pub proxy_to: Option<String>,
+    pub schema: Option<u32>,
 }
 
 impl Peer {
@@ -50,6 +53,7 @@ impl Peer {
             listening_on: listening_on,
             proxy_through: None,
             proxy_to: None,
+            schema: Some(SCHEMA_VERSION),
         }
     }
 }
@@ -71,9 +75,19 @@ pub enum Message {
     CensusEntry(CensusEntry),
     Election(Election),
     GossipFile(GossipFile),
+    Unknown(UnknownMessage),
     Blank,
 }
 
+/// A message of a kind we don't understand, received from a member running a newer release. We
+/// can't process it, but we keep it around so it still gets gossiped to the members that can.
+#[derive(Debug, RustcDecodable, RustcEncodable, Clone, PartialEq, Eq)]
+pub struct UnknownMessage {
+    pub kind: String,
+    pub schema: u32,
+    pub body: String,
+}
+
 /// A UUID for Rumors. In practice, always matches the UUID of a message payload.
 pub type RumorId = Uuid;
 
@@ -315,8 +329,8 @@ impl DerefMut for RumorList {
 #[cfg(test)]
 mod test {
     mod rumor_list {
-        use gossip::rumor::{Rumor, RumorId, RumorList};
-        use gossip::member::MemberId;
+        use rumor::{Rumor, RumorId, RumorList};
+        use member::MemberId;
 
         #[test]
         fn add_rumor() {
@@ -0,0 +1,362 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned encoding of the gossip protocol.
+//!
+//! Schema 1 (`LEGACY_SCHEMA`) is the original encoding: the whole `Protocol` is serialized as a
+//! single value, so a receiver that doesn't know one of the rumor kinds in it can't decode any of
+//! it. Schema 2 wraps each rumor in an envelope carrying its kind, the schema of its body, and the
+//! encoded body itself. Rumors of a kind we don't know become `Message::Unknown`, and are still
+//! passed along to everyone else - this is what lets us roll a new release through a ring one
+//! member at a time.
+//!
+//! Every member advertises the newest schema it speaks in its `Peer` and `Member` records. We
+//! send each member the newest schema we both understand, and fall back to the legacy encoding
+//! for members that haven't told us.
+
+use std::cmp;
+
+use common::wire_message::WireMessage;
+pub use common::wire_message::LEGACY_SCHEMA;
+use hcore::crypto::SymKey;
+use rustc_serialize::{json, Encodable};
+
+use error::{Error, Result};
+use rumor::{Message, Peer, Protocol, Rumor, RumorId, RumorList, UnknownMessage};
+
+/// The newest schema we speak.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// A rumor, as encoded on the wire from schema 2 on.
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+struct EncodedRumor {
+    id: RumorId,
+    kind: String,
+    schema: u32,
+    body: String,
+}
+
+/// A protocol message, as encoded on the wire from schema 2 on.
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+struct EncodedProtocol {
+    kind: String,
+    peer: Option<Peer>,
+    rumors: Vec<EncodedRumor>,
+}
+
+/// Returns the schema to use when talking to a member that advertised `theirs`; the newest schema
+/// we both speak, or the legacy schema if they didn't advertise one.
+pub fn negotiate(theirs: Option<u32>) -> u32 {
+    match theirs {
+        Some(schema) => cmp::max(LEGACY_SCHEMA, cmp::min(schema, SCHEMA_VERSION)),
+        None => LEGACY_SCHEMA,
+    }
+}
+
+/// Wraps a protocol message in a `WireMessage`, encoded with the given schema.
+///
+/// # Errors
+///
+/// * If we cannot encode the message
+/// * If we cannot encrypt the message with the ring key
+pub fn to_wire(msg: &Protocol, schema: u32, ring_key: Option<&SymKey>) -> Result<WireMessage> {
+    if schema <= LEGACY_SCHEMA {
+        return wrap(&legacy_protocol(msg), ring_key);
+    }
+    let mut wire_msg = try!(wrap(&try!(encode_protocol(msg)), ring_key));
+    wire_msg.set_schema(schema);
+    Ok(wire_msg)
+}
+
+/// Unwraps a protocol message from a `WireMessage`, using the schema the sender encoded it with.
+///
+/// # Errors
+///
+/// * If we cannot decrypt the message with the ring key
+/// * If we cannot decode the message
+/// * If the message is not a protocol message we know
+pub fn from_wire(wire_msg: &WireMessage, ring_key: Option<&SymKey>) -> Result<Protocol> {
+    if wire_msg.schema() <= LEGACY_SCHEMA {
+        return Ok(try!(wire_msg.msg(ring_key)));
+    }
+    let encoded: EncodedProtocol = try!(wire_msg.msg(ring_key));
+    decode_protocol(encoded)
+}
+
+fn wrap<T: Encodable>(msg: &T, ring_key: Option<&SymKey>) -> Result<WireMessage> {
+    match ring_key {
+        Some(key) => Ok(try!(WireMessage::encrypted(msg, key))),
+        None => Ok(try!(WireMessage::plain(msg))),
+    }
+}
+
+/// The legacy encoding can't carry rumors we don't understand, so they are left out.
+fn legacy_protocol(msg: &Protocol) -> Protocol {
+    match *msg {
+        Protocol::Ping(ref peer, ref rumors) => Protocol::Ping(peer.clone(), known_rumors(rumors)),
+        Protocol::Ack(ref peer, ref rumors) => Protocol::Ack(peer.clone(), known_rumors(rumors)),
+        Protocol::PingReq(ref peer, ref rumors) => {
+            Protocol::PingReq(peer.clone(), known_rumors(rumors))
+        }
+        Protocol::Inject(ref rumors) => Protocol::Inject(known_rumors(rumors)),
+    }
+}
+
+fn known_rumors(rumors: &RumorList) -> RumorList {
+    let mut known = RumorList::new();
+    for (id, rumor) in rumors.rumors.iter() {
+        if let Message::Unknown(_) = rumor.payload {
+            continue;
+        }
+        known.rumors.insert(id.clone(), rumor.clone());
+    }
+    known.heat = rumors.heat.clone();
+    known
+}
+
+fn encode_protocol(msg: &Protocol) -> Result<EncodedProtocol> {
+    let (kind, peer, rumors) = match *msg {
+        Protocol::Ping(ref peer, ref rumors) => ("ping", Some(peer.clone()), rumors),
+        Protocol::Ack(ref peer, ref rumors) => ("ack", Some(peer.clone()), rumors),
+        Protocol::PingReq(ref peer, ref rumors) => ("pingreq", Some(peer.clone()), rumors),
+        Protocol::Inject(ref rumors) => ("inject", None, rumors),
+    };
+    let mut encoded_rumors = Vec::with_capacity(rumors.len());
+    for rumor in rumors.values() {
+        if let Some(encoded) = try!(encode_rumor(rumor)) {
+            encoded_rumors.push(encoded);
+        }
+    }
+    Ok(EncodedProtocol {
+        kind: kind.to_string(),
+        peer: peer,
+        rumors: encoded_rumors,
+    })
+}
+
+fn encode_rumor(rumor: &Rumor) -> Result<Option<EncodedRumor>> {
+    let (kind, body) = match rumor.payload {
+        Message::Member(ref m) => ("member", try!(json::encode(m))),
+        Message::CensusEntry(ref ce) => ("census_entry", try!(json::encode(ce))),
+        Message::Election(ref e) => ("election", try!(json::encode(e))),
+        Message::GossipFile(ref gf) => ("gossip_file", try!(json::encode(gf))),
+        Message::Unknown(ref u) => {
+            // Pass it along exactly as we got it
+            return Ok(Some(EncodedRumor {
+                id: rumor.id.clone(),
+                kind: u.kind.clone(),
+                schema: u.schema,
+                body: u.body.clone(),
+            }));
+        }
+        Message::Blank => return Ok(None),
+    };
+    Ok(Some(EncodedRumor {
+        id: rumor.id.clone(),
+        kind: kind.to_string(),
+        schema: SCHEMA_VERSION,
+        body: body,
+    }))
+}
+
+fn decode_protocol(encoded: EncodedProtocol) -> Result<Protocol> {
+    let EncodedProtocol { kind, peer, rumors: encoded_rumors } = encoded;
+    let mut rumors = RumorList::new();
+    for encoded_rumor in encoded_rumors.into_iter() {
+        let rumor = decode_rumor(encoded_rumor);
+        rumors.rumors.insert(rumor.id.clone(), rumor);
+    }
+    match (&kind[..], peer) {
+        ("ping", Some(peer)) => Ok(Protocol::Ping(peer, rumors)),
+        ("ack", Some(peer)) => Ok(Protocol::Ack(peer, rumors)),
+        ("pingreq", Some(peer)) => Ok(Protocol::PingReq(peer, rumors)),
+        ("inject", _) => Ok(Protocol::Inject(rumors)),
+        (kind, _) => Err(Error::UnknownProtocol(kind.to_string())),
+    }
+}
+
+/// Decodes a rumor. Anything we can't make sense of - a kind we don't know, or a body from a newer
+/// schema that no longer decodes - is kept as an unknown message rather than failing the whole
+/// protocol message.
+fn decode_rumor(encoded: EncodedRumor) -> Rumor {
+    let payload = match &encoded.kind[..] {
+        "member" => json::decode(&encoded.body).ok().map(Message::Member),
+        "census_entry" => json::decode(&encoded.body).ok().map(Message::CensusEntry),
+        "election" => json::decode(&encoded.body).ok().map(Message::Election),
+        "gossip_file" => json::decode(&encoded.body).ok().map(Message::GossipFile),
+        _ => None,
+    };
+    let payload = match payload {
+        Some(payload) => payload,
+        None => {
+            debug!("Keeping unknown rumor {} of kind {} (schema {})",
+                   encoded.id,
+                   encoded.kind,
+                   encoded.schema);
+            Message::Unknown(UnknownMessage {
+                kind: encoded.kind,
+                schema: encoded.schema,
+                body: encoded.body,
+            })
+        }
+    };
+    Rumor {
+        id: encoded.id,
+        payload: payload,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use common::wire_message::WireMessage;
+    use census::CensusEntry;
+    use election::Election;
+    use member::Member;
+    use rumor::{Message, Peer, Protocol, Rumor, RumorList, UnknownMessage};
+    use super::{from_wire, negotiate, to_wire, LEGACY_SCHEMA, SCHEMA_VERSION};
+
+    fn peer() -> Peer {
+        Peer::new(Uuid::new_v4(), String::from("127.0.0.1:9634"))
+    }
+
+    fn member_rumor() -> Rumor {
+        Rumor::member(Member::new(String::from("bobo"),
+                                  String::from("127.0.0.1"),
+                                  String::from("127.0.0.1:9634"),
+                                  false))
+    }
+
+    fn unknown_rumor() -> Rumor {
+        Rumor {
+            id: Uuid::new_v4(),
+            payload: Message::Unknown(UnknownMessage {
+                kind: String::from("from_the_future"),
+                schema: SCHEMA_VERSION + 1,
+                body: String::from("{\"answer\":42}"),
+            }),
+        }
+    }
+
+    fn rumors_of(msg: Protocol) -> RumorList {
+        match msg {
+            Protocol::Ping(_, rumors) => rumors,
+            _ => panic!("Expected a ping"),
+        }
+    }
+
+    #[test]
+    fn negotiate_picks_the_newest_shared_schema() {
+        assert_eq!(negotiate(None), LEGACY_SCHEMA);
+        assert_eq!(negotiate(Some(0)), LEGACY_SCHEMA);
+        assert_eq!(negotiate(Some(LEGACY_SCHEMA)), LEGACY_SCHEMA);
+        assert_eq!(negotiate(Some(SCHEMA_VERSION)), SCHEMA_VERSION);
+        assert_eq!(negotiate(Some(SCHEMA_VERSION + 1)), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn round_trip_current_schema() {
+        let rumor = member_rumor();
+        let mut rumors = RumorList::new();
+        rumors.add_rumor(rumor.clone());
+        let wire_msg = to_wire(&Protocol::Ping(peer(), rumors), SCHEMA_VERSION, None).unwrap();
+        assert_eq!(wire_msg.schema(), SCHEMA_VERSION);
+        let decoded = rumors_of(from_wire(&wire_msg, None).unwrap());
+        assert_eq!(decoded.get(&rumor.id), Some(&rumor));
+    }
+
+    #[test]
+    fn round_trip_query_answer() {
+        let member = member_rumor();
+        let census = Rumor::census_entry(CensusEntry::new("redis", "default", Uuid::new_v4()));
+        let election = Rumor::election(Election::new(String::from("redis"),
+                                                     String::from("default"),
+                                                     Uuid::new_v4(),
+                                                     1,
+                                                     1));
+        let mut rumors = RumorList::new();
+        rumors.add_rumor(member.clone());
+        rumors.add_rumor(census.clone());
+        rumors.add_rumor(election.clone());
+        let wire_msg = to_wire(&Protocol::Ack(peer(), rumors), SCHEMA_VERSION, None).unwrap();
+        let decoded = match from_wire(&wire_msg, None).unwrap() {
+            Protocol::Ack(_, rumors) => rumors,
+            _ => panic!("Expected an ack"),
+        };
+        assert_eq!(decoded.get(&member.id), Some(&member));
+        assert_eq!(decoded.get(&census.id), Some(&census));
+        assert_eq!(decoded.get(&election.id), Some(&election));
+    }
+
+    #[test]
+    fn round_trip_legacy_schema() {
+        let rumor = member_rumor();
+        let mut rumors = RumorList::new();
+        rumors.add_rumor(rumor.clone());
+        let wire_msg = to_wire(&Protocol::Ping(peer(), rumors), LEGACY_SCHEMA, None).unwrap();
+        assert_eq!(wire_msg.schema(), LEGACY_SCHEMA);
+        let legacy: Protocol = wire_msg.msg(None).unwrap();
+        assert_eq!(rumors_of(legacy).get(&rumor.id), Some(&rumor));
+    }
+
+    #[test]
+    fn unknown_rumors_are_kept_and_relayed() {
+        let known = member_rumor();
+        let unknown = unknown_rumor();
+        let mut rumors = RumorList::new();
+        rumors.add_rumor(known.clone());
+        rumors.add_rumor(unknown.clone());
+        let wire_msg = to_wire(&Protocol::Ping(peer(), rumors), SCHEMA_VERSION, None).unwrap();
+        let decoded = rumors_of(from_wire(&wire_msg, None).unwrap());
+        assert_eq!(decoded.get(&known.id), Some(&known));
+        assert_eq!(decoded.get(&unknown.id), Some(&unknown));
+    }
+
+    #[test]
+    fn undecodable_known_kind_is_kept_as_unknown() {
+        let id = Uuid::new_v4();
+        let json = format!("{{\"kind\":\"ping\",\"peer\":{{\"member_id\":\"{}\",\
+                            \"listening_on\":\"127.0.0.1:9634\",\"proxy_through\":null,\
+                            \"proxy_to\":null,\"schema\":3}},\"rumors\":[{{\"id\":\"{}\",\
+                            \"kind\":\"member\",\"schema\":3,\"body\":\"[1,2,3]\"}}]}}",
+                           Uuid::new_v4(),
+                           id);
+        let mut wire_msg = WireMessage::plain(&::rustc_serialize::json::Json::from_str(&json)
+                .unwrap())
+            .unwrap();
+        wire_msg.set_schema(3);
+        let decoded = rumors_of(from_wire(&wire_msg, None).unwrap());
+        match decoded.get(&id).unwrap().payload {
+            Message::Unknown(ref u) => {
+                assert_eq!(u.kind, "member");
+                assert_eq!(u.schema, 3);
+            }
+            ref other => panic!("Expected an unknown message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn legacy_schema_drops_unknown_rumors() {
+        let known = member_rumor();
+        let unknown = unknown_rumor();
+        let mut rumors = RumorList::new();
+        rumors.add_rumor(known.clone());
+        rumors.add_rumor(unknown.clone());
+        let wire_msg = to_wire(&Protocol::Ping(peer(), rumors), LEGACY_SCHEMA, None).unwrap();
+        let decoded = rumors_of(from_wire(&wire_msg, None).unwrap());
+        assert_eq!(decoded.get(&known.id), Some(&known));
+        assert!(decoded.get(&unknown.id).is_none());
+    }
+}
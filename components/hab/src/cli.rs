@@ -21,6 +21,8 @@ use hcore::crypto::keys::PairType;
 use regex::Regex;
 use url::Url;
 
+use command::pkg::sbom::SbomFormat;
+
 pub fn get() -> App<'static, 'static> {
     let alias_apply = sub_config_apply()
         .about("Alias for 'config apply'")
@@ -73,6 +75,8 @@ pub fn get() -> App<'static, 'static> {
                     (default: 127.0.0.1:9634)")
                 (@arg RING: -r --ring +takes_value
                     "Ring key name, which will encrypt communication messages")
+                (@arg SIGNER: -s --signer +takes_value
+                    "Name of the sig key used to sign the file, which Supervisors may require")
             )
         )
         (@subcommand origin =>
@@ -96,6 +100,9 @@ pub fn get() -> App<'static, 'static> {
                     (@arg ORIGIN: +required +takes_value)
                     (@arg PAIR_TYPE: -t --type +takes_value +required {valid_pair_type}
                     "Export either the `public' or `secret' key")
+                    (@arg ENCRYPT: --encrypt
+                    "Encrypt the secret key with a passphrase, read from HAB_KEY_PASSPHRASE or \
+                    asked for")
                 )
                 (@subcommand generate =>
                     (about: "Generates a Habitat origin key")
@@ -104,9 +111,21 @@ pub fn get() -> App<'static, 'static> {
                 )
                 (@subcommand import =>
                     (about: "Reads a stdin stream containing a public or secret origin key \
-                        contents and writes the key to disk")
+                        contents and writes the key to disk. Passphrase encrypted secret keys \
+                        stay encrypted, and are checked with the passphrase from \
+                        HAB_KEY_PASSPHRASE")
                     (aliases: &["i", "im", "imp", "impo", "impor"])
                 )
+                (@subcommand revoke =>
+                    (about: "Revokes origin key revisions with a revocation list signed by the \
+                        latest origin key, and uploads the list to the depot")
+                    (aliases: &["r", "re", "rev", "revo", "revok"])
+                    (@arg ORIGIN: +required "The origin name")
+                    (@arg REVISION: +required +multiple
+                        "One or more key revisions to revoke (ex: 20160405144945)")
+                    (@arg DEPOT_URL: -u --url +takes_value {valid_url} "Use a specific Depot URL")
+                    (@arg AUTH_TOKEN: -z --auth +takes_value "Authentication token for the Depot")
+                )
                 (@subcommand upload =>
                     (@group upload =>
                         (@attributes +required)
@@ -140,6 +159,15 @@ pub fn get() -> App<'static, 'static> {
                     "Sets the destination directory (default: /bin)")
             )
             (subcommand: sub_pkg_build())
+            (@subcommand dependents =>
+                (about: "Lists the installed packages which depend on a package")
+                (aliases: &["d", "de", "dep", "depe", "depen", "depend", "depende", "dependen",
+                    "dependent"])
+                (@arg TRANSITIVE: -t --transitive
+                    "Include packages which depend on it through other packages")
+                (@arg PKG_IDENT: +required +takes_value
+                    "A package identifier (ex: core/openssl, core/openssl/1.0.2h)")
+            )
             (@subcommand exec =>
                 (about: "Executes a command using the 'PATH' context of an installed package")
                 (aliases: &["exe"])
@@ -157,6 +185,13 @@ pub fn get() -> App<'static, 'static> {
                 (@arg PKG_IDENT: +required +takes_value
                     "A package identifier (ex: core/redis, core/busybox-static/1.42.2)")
             )
+            (@subcommand gc =>
+                (about: "Removes old releases of installed packages, keeping the newest of each \
+                    package and any release still needed by another package or a running service. \
+                    Releases of the Supervisor are never removed.")
+                (@arg KEEP: -k --keep +takes_value {valid_keep}
+                    "The number of releases of each package to keep [default: 2]")
+            )
             (@subcommand hash =>
                 (about: "Generates a blake2b hashsum from a target at any given filepath")
                 (aliases: &["ha", "has"])
@@ -170,16 +205,37 @@ pub fn get() -> App<'static, 'static> {
                 (@arg PKG_IDENT: +required +takes_value
                     "A package identifier (ex: core/redis, core/busybox-static/1.42.2)")
             )
+            (@subcommand sbom =>
+                (about: "Prints a software bill of materials for a package, listing it and its \
+                    transitive dependencies with their checksums and licenses")
+                (@arg FORMAT: -f --format +takes_value {valid_sbom_format}
+                    "The document format, spdx or cyclonedx [default: spdx]")
+                (@arg PKG_IDENT_OR_ARTIFACT: +required +takes_value
+                    "An installed package identifier (ex: core/redis, core/redis/3.0.7), or a \
+                    filepath to a Habitat Artifact \
+                    (ex: /home/core-redis-3.0.7-21120102031201-x86_64-linux.hart)")
+            )
             (@subcommand sign =>
                 (about: "Signs an archive with an origin key, generating a Habitat Artifact")
                 (aliases: &["s", "si", "sig"])
                 (@arg ORIGIN: --origin +takes_value "Origin key used to create signature")
+                (@arg APPEND: --append
+                    "Countersign a Habitat Artifact, adding a signature to those it carries")
                 (@arg SOURCE: +required {file_exists}
                     "A path to a source archive file \
-                    (ex: /home/acme-redis-3.0.7-21120102031201.tar.xz)")
-                (@arg DEST: +required
+                    (ex: /home/acme-redis-3.0.7-21120102031201.tar.xz), or a Habitat Artifact \
+                    with --append")
+                (@arg DEST:
                     "The destination path to the signed Habitat Artifact \
-                    (ex: /home/acme-redis-3.0.7-21120102031201-x86_64-linux.hart)")
+                    (ex: /home/acme-redis-3.0.7-21120102031201-x86_64-linux.hart). Required \
+                    unless --append is given, which defaults to SOURCE")
+            )
+            (@subcommand uninstall =>
+                (about: "Removes an installed package, unless another installed package or a \
+                    running service still needs it")
+                (aliases: &["un", "uni", "unin", "unins", "uninst", "uninsta", "uninstal"])
+                (@arg PKG_IDENT: +required +takes_value
+                    "A package identifier (ex: core/redis, core/busybox-static/1.42.2)")
             )
             (@subcommand upload =>
                 (about: "Uploads a local Habitat Artifact to a Depot")
@@ -193,6 +249,9 @@ pub fn get() -> App<'static, 'static> {
             (@subcommand verify =>
                 (about: "Verifies a Habitat Artifact with an origin key")
                 (aliases: &["v", "ve", "ver", "veri", "verif"])
+                (@arg POLICY: --policy +takes_value
+                    "Signatures the artifact must carry (ex: '@origin+release|qa' requires the \
+                    origin signature and one by the release or qa key) [default: @origin]")
                 (@arg SOURCE: +required {file_exists}
                     "A path to a Habitat Artifact \
                     (ex: /home/acme-redis-3.0.7-21120102031201-x86_64-linux.hart)")
@@ -222,6 +281,50 @@ pub fn get() -> App<'static, 'static> {
                     (@arg RING: +required +takes_value "Ring key name")
                 )
             )
+            (@subcommand members =>
+                (about: "Lists the members of a ring, as seen by one of its Supervisors")
+                (aliases: &["m", "me", "mem", "memb", "membe", "member"])
+                (@arg PEER: -p --peer +takes_value
+                    "A comma-delimited list of one or more Habitat Supervisor peers to query; \
+                    the first to answer is used (default: 127.0.0.1:9634)")
+                (@arg RING: -r --ring +takes_value
+                    "Ring key name, which will encrypt communication messages")
+            )
+            (@subcommand census =>
+                (about: "Lists the census of a ring, as seen by one of its Supervisors")
+                (aliases: &["c", "ce", "cen", "cens", "censu"])
+                (@arg SERVICE_GROUP: +takes_value {valid_service_group}
+                    "Only list entries for this service group (ex: redis.default)")
+                (@arg PEER: -p --peer +takes_value
+                    "A comma-delimited list of one or more Habitat Supervisor peers to query; \
+                    the first to answer is used (default: 127.0.0.1:9634)")
+                (@arg RING: -r --ring +takes_value
+                    "Ring key name, which will encrypt communication messages")
+            )
+        )
+        (@subcommand secret =>
+            (about: "Commands relating to Habitat service secrets")
+            (aliases: &["sec", "secr", "secre"])
+            (@setting ArgRequiredElseHelp)
+            (@subcommand apply =>
+                (about: "Encrypts secrets for a service group and applies them to its Supervisors")
+                (aliases: &["a", "ap", "app", "appl"])
+                (@arg SERVICE_GROUP: +required +takes_value {valid_service_group}
+                    "Target service group (ex: redis.default)")
+                (@arg VERSION_NUMBER: +required
+                    "A version number (positive integer) for these secrets (ex: 42)")
+                (@arg FILE: +required {file_exists}
+                    "Path to a toml file of secrets on disk (ex: /tmp/secrets.toml)")
+                (@arg ORG: --org +takes_value "Name of service organization")
+                (@arg USER: +takes_value "Name of the user key")
+                (@arg PEER: -p --peer +takes_value
+                    "A comma-delimited list of one or more Habitat Supervisor peers to infect \
+                    (default: 127.0.0.1:9634)")
+                (@arg RING: -r --ring +takes_value
+                    "Ring key name, which will encrypt communication messages")
+                (@arg SIGNER: -s --signer +takes_value
+                    "Name of the sig key used to sign the secrets, which Supervisors may require")
+            )
         )
         (@subcommand service =>
             (about: "Commands relating to Habitat services")
@@ -304,6 +407,8 @@ fn sub_config_apply() -> App<'static, 'static> {
         (@arg FILE: {file_exists_or_stdin}
             "Path to local file on disk (ex: /tmp/config.toml, default: <stdin>)")
         (@arg ORG: --org +takes_value "Name of service organization")
+        (@arg SIGNER: -s --signer +takes_value
+            "Name of the sig key used to sign the configuration, which Supervisors may require")
     )
 }
 
@@ -338,8 +443,9 @@ fn sub_pkg_install() -> App<'static, 'static> {
         (about: "Installs a Habitat package from a Depot or locally from a Habitat Artifact")
         (@arg DEPOT_URL: -u --url +takes_value {valid_url} "Use a specific Depot URL")
         (@arg PKG_IDENT_OR_ARTIFACT: +required +multiple
-            "One or more Habitat package identifiers (ex: acme/redis) and/or filepaths \
-            to a Habitat Artifact (ex: /home/acme-redis-3.0.7-21120102031201-x86_64-linux.hart)")
+            "One or more Habitat package identifiers (ex: acme/redis), package requirements \
+            (ex: 'acme/redis >= 3.0, < 3.2') and/or filepaths to a Habitat Artifact \
+            (ex: /home/acme-redis-3.0.7-21120102031201-x86_64-linux.hart)")
     )
 }
 
@@ -359,6 +465,13 @@ fn file_exists_or_stdin(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_keep(val: String) -> result::Result<(), String> {
+    match val.parse::<usize>() {
+        Ok(keep) if keep > 0 => Ok(()),
+        _ => Err(format!("KEEP: '{}' is not a number greater than 0", &val)),
+    }
+}
+
 fn valid_pair_type(val: String) -> result::Result<(), String> {
     match PairType::from_str(&val) {
         Ok(_) => Ok(()),
@@ -369,6 +482,13 @@ fn valid_pair_type(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_sbom_format(val: String) -> result::Result<(), String> {
+    match SbomFormat::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("{}", e)),
+    }
+}
+
 fn valid_service_group(val: String) -> result::Result<(), String> {
     let regex = Regex::new(r"([A-Za-z_0-9]+)\.([A-Za-z_0-9]+)").unwrap();
     if regex.is_match(&val) {
@@ -17,22 +17,24 @@ pub mod apply {
     use std::io::{self, Read};
 
     use ansi_term::Colour::{Blue, Green, Yellow};
-    use hcore::crypto::SymKey;
+    use hcore::crypto::{SigKeyPair, SymKey};
     use hcore::service::ServiceGroup;
     use common::gossip_file::GossipFile;
 
     use error::Result;
-    use gossip::{self, hab_gossip};
+    use gossip;
+    use habitat_gossip::rumor::{Rumor, RumorList};
 
     pub fn start(peers: &Vec<String>,
                  ring_key: Option<&SymKey>,
+                 signer: Option<&SigKeyPair>,
                  sg: &ServiceGroup,
                  number: u64,
                  file_path: Option<&Path>)
                  -> Result<()> {
         println!("{}",
                  Yellow.bold().paint(format!("» Applying configuration")));
-        let file = match file_path {
+        let mut file = match file_path {
             Some(p) => try!(GossipFile::from_file(sg.clone(), p, number)),
             None => {
                 let mut body = String::new();
@@ -40,9 +42,15 @@ pub mod apply {
                 try!(GossipFile::from_body(sg.clone(), body.into(), number))
             }
         };
-        let rumor = hab_gossip::Rumor::gossip_file(file);
+        if let Some(signer) = signer {
+            println!("{} configuration with {}",
+                     Green.bold().paint("☛ Signing"),
+                     &signer.name_with_rev());
+            try!(file.sign(signer));
+        }
+        let rumor = Rumor::gossip_file(file);
 
-        let mut list = hab_gossip::RumorList::new();
+        let mut list = RumorList::new();
         list.add_rumor(rumor);
 
         if let Some(ring_key) = ring_key {
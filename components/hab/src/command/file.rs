@@ -16,14 +16,16 @@ pub mod upload {
     use std::path::Path;
 
     use ansi_term::Colour::{Blue, Green, Yellow};
-    use hcore::crypto::{BoxKeyPair, SymKey};
+    use hcore::crypto::{BoxKeyPair, SigKeyPair, SymKey};
     use common::gossip_file::GossipFile;
 
     use error::Result;
-    use gossip::{self, hab_gossip};
+    use gossip;
+    use habitat_gossip::rumor::{Rumor, RumorList};
 
     pub fn start(peers: &Vec<String>,
                  ring_key: Option<&SymKey>,
+                 signer: Option<&SigKeyPair>,
                  user_pair: &BoxKeyPair,
                  service_pair: &BoxKeyPair,
                  number: u64,
@@ -31,11 +33,18 @@ pub mod upload {
                  -> Result<()> {
         println!("{}",
                  Yellow.bold().paint(format!("» Uploading file {}", &file_path.display())));
-        let file =
+        let mut file =
             try!(GossipFile::from_file_encrypt(&user_pair, &service_pair, file_path, number));
+        if let Some(signer) = signer {
+            println!("{} {} with {}",
+                     Green.bold().paint("☛ Signing"),
+                     &file_path.display(),
+                     &signer.name_with_rev());
+            try!(file.sign(signer));
+        }
 
-        let rumor = hab_gossip::Rumor::gossip_file(file);
-        let mut list = hab_gossip::RumorList::new();
+        let rumor = Rumor::gossip_file(file);
+        let mut list = RumorList::new();
         list.add_rumor(rumor);
         if let Some(ring_key) = ring_key {
             println!("{} communication to \"{}\" ring with {}",
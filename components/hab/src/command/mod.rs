@@ -18,6 +18,7 @@ pub mod file;
 pub mod pkg;
 pub mod origin;
 pub mod ring;
+pub mod secret;
 pub mod service;
 pub mod studio;
 pub mod sup;
@@ -118,22 +118,24 @@ pub mod key {
     }
 
     pub mod export {
-        use std::io;
+        use std::io::{self, Read};
         use std::fs::File;
         use std::path::Path;
 
         use hcore::crypto::SigKeyPair;
-        use hcore::crypto::keys::PairType;
+        use hcore::crypto::keys::{passphrase, PairType};
 
         use error::Result;
 
-        pub fn start(origin: &str, pair_type: PairType, cache: &Path) -> Result<()> {
-            let latest = try!(SigKeyPair::get_latest_pair_for(origin, cache));
+        pub fn start(origin: &str, pair_type: PairType, encrypt: bool, cache: &Path) -> Result<()> {
             let path = match pair_type {
                 PairType::Public => {
+                    // Only the public key is loaded, so an encrypted secret key isn't asked for
+                    let latest = try!(SigKeyPair::get_latest_public_pair_for(origin, cache));
                     try!(SigKeyPair::get_public_key_path(&latest.name_with_rev(), cache))
                 }
                 PairType::Secret => {
+                    let latest = try!(SigKeyPair::get_latest_pair_for(origin, cache));
                     try!(SigKeyPair::get_secret_key_path(&latest.name_with_rev(), cache))
                 }
             };
@@ -141,7 +143,14 @@ pub mod key {
             debug!("Streaming file contents of {} {} to standard out",
                    &pair_type,
                    &path.display());
-            try!(io::copy(&mut file, &mut io::stdout()));
+            if encrypt {
+                let mut content = String::new();
+                try!(file.read_to_string(&mut content));
+                let passphrase = try!(passphrase::get_new());
+                print!("{}", try!(passphrase::encrypt_key_str(&content, &passphrase)));
+            } else {
+                try!(io::copy(&mut file, &mut io::stdout()));
+            }
             Ok(())
         }
     }
@@ -185,6 +194,59 @@ pub mod key {
         }
     }
 
+    pub mod revoke {
+        use std::path::Path;
+
+        use ansi_term::Colour::{Blue, Green, Yellow};
+
+        use depot_client::Client;
+        use hcore::crypto::{RevocationList, SigKeyPair};
+
+        use error::Result;
+
+        pub fn start(depot: &str,
+                     token: &str,
+                     origin: &str,
+                     revisions: &[&str],
+                     cache: &Path)
+                     -> Result<()> {
+            println!("{}",
+                     Yellow.bold().paint(format!("» Revoking {} origin key revisions", origin)));
+            let latest = try!(SigKeyPair::get_latest_pair_for(origin, cache));
+            // The depot serves one list per origin, which our upload replaces, so start from
+            // what it has in case revocations were made elsewhere since we last fetched it
+            let depot_client = try!(Client::new(depot, None));
+            if let Some(list) = try!(depot_client.fetch_revocation_list(origin, cache)) {
+                println!("{} revocation list {}",
+                         Green.paint("↓ Downloaded"),
+                         list.revision);
+            }
+            // Each list carries every revocation we know of, so the depot only needs to serve
+            // the newest one
+            let mut revoked = try!(RevocationList::get_revoked_for(origin, cache));
+            for rev in revisions {
+                let name_with_rev = format!("{}-{}", origin, rev);
+                if !revoked.contains(&name_with_rev) {
+                    revoked.push(name_with_rev);
+                }
+            }
+            let content = try!(RevocationList::sign(&latest, &revoked));
+            let list = try!(RevocationList::write_file_from_str(&content, cache));
+            println!("{} {} with {}",
+                     Green.paint("☑ Cached revocation list"),
+                     list.revision,
+                     &latest.name_with_rev());
+
+            try!(depot_client.put_revocation_list(origin, &content, token));
+            println!("{} revocation list {}",
+                     Green.bold().paint("✓ Uploaded"),
+                     list.revision);
+            println!("{}",
+                     Blue.paint(format!("★ Revoked {}.", list.revoked.join(", "))));
+            Ok(())
+        }
+    }
+
     pub mod upload {
         use std::path::Path;
 
@@ -299,7 +361,12 @@ pub mod key {
                      cache: &Path)
                      -> Result<()> {
             let depot_client = try!(Client::new(depot, None));
-            let latest = try!(SigKeyPair::get_latest_pair_for(origin, cache));
+            // Only load the secret key, which may need its passphrase, when it's uploaded too
+            let latest = if with_secret {
+                try!(SigKeyPair::get_latest_pair_for(origin, cache))
+            } else {
+                try!(SigKeyPair::get_latest_public_pair_for(origin, cache))
+            };
             let public_keyfile = try!(SigKeyPair::get_public_key_path(&latest.name_with_rev(),
                                                                       cache));
 
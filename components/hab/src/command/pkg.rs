@@ -103,6 +103,27 @@ pub mod build {
     }
 }
 
+pub mod dependents {
+    use std::path::Path;
+
+    use hcore::package::{PackageIdent, PackageIndex};
+
+    use error::Result;
+
+    pub fn start(ident: &PackageIdent, transitive: bool, fs_root_path: &Path) -> Result<()> {
+        let index = try!(PackageIndex::load(Some(fs_root_path)));
+        let dependents = if transitive {
+            index.transitive_dependents(ident)
+        } else {
+            index.dependents(ident)
+        };
+        for dependent in dependents {
+            println!("{}", dependent);
+        }
+        Ok(())
+    }
+}
+
 pub mod exec {
     use std::env;
     use std::ffi::OsString;
@@ -254,6 +275,79 @@ pub mod export {
     }
 }
 
+pub mod gc {
+    use std::cmp::Ordering;
+    use std::collections::{HashMap, HashSet};
+    use std::path::Path;
+
+    use ansi_term::Colour::{Blue, Green, Yellow};
+    use hcore::package::{PackageIdent, PackageIndex, PackageInstall};
+    use hcore::package::install::InstallLock;
+
+    use command::pkg::uninstall::running_services;
+    use error::Result;
+
+    /// The Supervisor runs out of its own package, and nothing records which release is running,
+    /// so none of its releases are collected.
+    const SUP_PACKAGES: &'static [(&'static str, &'static str)] =
+        &[("core", "hab-sup"), ("core", "hab-sup-static")];
+
+    pub fn start(keep: usize, fs_root_path: &Path) -> Result<()> {
+        println!("{}",
+                 Yellow.bold().paint(format!("» Removing old package releases, keeping {} of \
+                                              each package",
+                                             keep)));
+        // Hold the lock until the last release is removed, so nothing installed meanwhile can
+        // come to depend on a release we've already decided isn't needed
+        let lock = try!(InstallLock::acquire(fs_root_path));
+        let index = try!(PackageIndex::load(Some(fs_root_path)));
+        let mut releases: HashMap<(String, String), Vec<PackageIdent>> = HashMap::new();
+        for ident in index.installed() {
+            releases.entry((ident.origin.clone(), ident.name.clone()))
+                .or_insert(vec![])
+                .push(ident.clone());
+        }
+        let mut remove = HashSet::new();
+        for ((origin, name), mut idents) in releases {
+            if SUP_PACKAGES.iter().any(|&(o, n)| o == origin && n == name) {
+                continue;
+            }
+            idents.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+            remove.extend(idents.into_iter().skip(keep));
+        }
+        let running: Vec<PackageIdent> =
+            try!(running_services(fs_root_path)).into_iter().map(|(_, ident)| ident).collect();
+        // Keep whatever the packages being kept still need, which in turn keeps what those need
+        loop {
+            let mut needed: HashSet<PackageIdent> = running.iter().cloned().collect();
+            for ident in index.installed() {
+                if !remove.contains(ident) {
+                    needed.extend(index.tdeps(ident).unwrap_or(&[]).iter().cloned());
+                }
+            }
+            let still_removed: HashSet<PackageIdent> =
+                remove.difference(&needed).cloned().collect();
+            if still_removed.len() == remove.len() {
+                break;
+            }
+            remove = still_removed;
+        }
+
+        let mut remove: Vec<PackageIdent> = remove.into_iter().collect();
+        remove.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+        for ident in remove.iter() {
+            let pkg_install = try!(PackageInstall::load(ident, Some(fs_root_path)));
+            try!(pkg_install.uninstall_locked(&lock));
+            println!("{} {}", Green.paint("✗ Removed"), ident);
+        }
+        println!("{}",
+                 Blue.paint(format!("★ Garbage collection complete with {} package releases \
+                                     removed.",
+                                    remove.len())));
+        Ok(())
+    }
+}
+
 pub mod hash {
     use hcore::crypto::hash;
 
@@ -280,6 +374,507 @@ pub mod path {
     }
 }
 
+pub mod sbom {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use hcore::fs::cache_artifact_path;
+    use hcore::package::{PackageArchive, PackageIdent, PackageInstall};
+    use rustc_serialize::json::{self, Json, ToJson};
+    use time;
+    use uuid::Uuid;
+
+    use error::{Error, Result};
+    use VERSION;
+
+    /// The document formats a software bill of materials can be written in.
+    pub enum SbomFormat {
+        CycloneDx,
+        Spdx,
+    }
+
+    impl FromStr for SbomFormat {
+        type Err = Error;
+
+        fn from_str(value: &str) -> Result<Self> {
+            match value {
+                "cyclonedx" => Ok(SbomFormat::CycloneDx),
+                "spdx" => Ok(SbomFormat::Spdx),
+                _ => Err(Error::UnsupportedSbomFormat(value.to_string())),
+            }
+        }
+    }
+
+    // What we know of one package in the bill of materials
+    struct Component {
+        ident: PackageIdent,
+        deps: Vec<PackageIdent>,
+        checksum: Option<String>,
+        licenses: Vec<String>,
+        source: Option<String>,
+        shasum: Option<String>,
+    }
+
+    /// Prints a bill of materials for an installed package, or a Habitat Artifact, listing the
+    /// package and each of its transitive dependencies.
+    ///
+    /// Dependencies are described from their installed package when there is one, or else from
+    /// their artifact in the artifact cache. A dependency which is neither installed nor cached is
+    /// still listed, with what its identifier tells us.
+    pub fn start(ident_or_archive: &str, format: &SbomFormat, fs_root_path: &Path) -> Result<()> {
+        let (root, tdeps) = if Path::new(ident_or_archive).is_file() {
+            let mut archive = PackageArchive::new(Path::new(ident_or_archive));
+            let tdeps = try!(archive.tdeps());
+            (try!(from_archive(&mut archive)), tdeps)
+        } else {
+            let ident = try!(PackageIdent::from_str(ident_or_archive));
+            let pkg_install = try!(PackageInstall::load(&ident, Some(fs_root_path)));
+            let tdeps = try!(pkg_install.tdeps());
+            (try!(from_install(&pkg_install, fs_root_path)), tdeps)
+        };
+        let mut deps = Vec::with_capacity(tdeps.len());
+        for ident in tdeps.iter() {
+            deps.push(try!(find(ident, fs_root_path)));
+        }
+        let doc = match *format {
+            SbomFormat::CycloneDx => cyclonedx(&root, &deps),
+            SbomFormat::Spdx => spdx(&root, &deps),
+        };
+        println!("{}", json::as_pretty_json(&doc));
+        Ok(())
+    }
+
+    fn find(ident: &PackageIdent, fs_root_path: &Path) -> Result<Component> {
+        if let Ok(pkg_install) = PackageInstall::load(ident, Some(fs_root_path)) {
+            return from_install(&pkg_install, fs_root_path);
+        }
+        if let Some(mut archive) = cached_archive(ident, fs_root_path) {
+            return from_archive(&mut archive);
+        }
+        Ok(Component {
+            ident: ident.clone(),
+            deps: vec![],
+            checksum: None,
+            licenses: vec![],
+            source: None,
+            shasum: None,
+        })
+    }
+
+    fn cached_archive(ident: &PackageIdent, fs_root_path: &Path) -> Option<PackageArchive> {
+        match ident.archive_name() {
+            Some(name) => {
+                let path = cache_artifact_path(Some(fs_root_path)).join(name);
+                if path.is_file() {
+                    Some(PackageArchive::new(path))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        }
+    }
+
+    fn from_archive(archive: &mut PackageArchive) -> Result<Component> {
+        let mut component = Component {
+            ident: try!(archive.ident()),
+            deps: try!(archive.deps()),
+            checksum: Some(try!(archive.checksum())),
+            licenses: try!(archive.licenses()),
+            source: try!(archive.source()),
+            shasum: try!(archive.shasum()),
+        };
+        if let Ok(manifest) = archive.manifest() {
+            fill_from_manifest(&mut component, &manifest);
+        }
+        Ok(component)
+    }
+
+    // An installed package's checksum is that of the artifact it was installed from, if the
+    // artifact is still in the cache
+    fn from_install(pkg_install: &PackageInstall, fs_root_path: &Path) -> Result<Component> {
+        let checksum = match cached_archive(pkg_install.ident(), fs_root_path) {
+            Some(archive) => Some(try!(archive.checksum())),
+            None => None,
+        };
+        let mut component = Component {
+            ident: pkg_install.ident().clone(),
+            deps: try!(pkg_install.deps()),
+            checksum: checksum,
+            licenses: try!(pkg_install.licenses()),
+            source: try!(pkg_install.source()),
+            shasum: try!(pkg_install.shasum()),
+        };
+        if let Ok(manifest) = pkg_install.manifest() {
+            fill_from_manifest(&mut component, &manifest);
+        }
+        Ok(component)
+    }
+
+    // Packages built before their license and source were written to metafiles still have them
+    // in their manifest, on lines like "* __License__: Apache-2.0"
+    fn fill_from_manifest(component: &mut Component, manifest: &str) {
+        if component.licenses.is_empty() {
+            if let Some(licenses) = manifest_value(manifest, "License") {
+                component.licenses = licenses.split_whitespace().map(|l| l.to_string()).collect();
+            }
+        }
+        if component.source.is_none() {
+            // "[http://example.com/s.tgz](http://example.com/s.tgz)"
+            component.source = manifest_value(manifest, "Source")
+                .and_then(|source| enclosed(source, '[', ']'));
+        }
+        if component.shasum.is_none() {
+            // "`0a1b2c...`", or a sentence when the plan has no shasum
+            component.shasum = manifest_value(manifest, "SHA")
+                .and_then(|shasum| enclosed(shasum, '`', '`'));
+        }
+    }
+
+    fn manifest_value<'a>(manifest: &'a str, key: &str) -> Option<&'a str> {
+        let prefix = format!("* __{}__:", key);
+        match manifest.lines().find(|line| line.starts_with(&prefix)) {
+            Some(line) => Some(line[prefix.len()..].trim()),
+            None => None,
+        }
+    }
+
+    fn enclosed(value: &str, open: char, close: char) -> Option<String> {
+        if !value.starts_with(open) {
+            return None;
+        }
+        let rest = &value[open.len_utf8()..];
+        match rest.find(close) {
+            Some(end) if end > 0 => Some(rest[..end].to_string()),
+            _ => None,
+        }
+    }
+
+    fn spdx(root: &Component, deps: &[Component]) -> Json {
+        let created = timestamp();
+        let packages = Some(root)
+            .into_iter()
+            .chain(deps.iter())
+            .map(|component| spdx_package(component, &created))
+            .collect();
+        // Plan licenses aren't checked against the SPDX license list, so each is declared as a
+        // license of our own, named as the plan gave it
+        let mut licenses = BTreeMap::new();
+        for component in Some(root).into_iter().chain(deps.iter()) {
+            for license in component.licenses.iter() {
+                licenses.insert(spdx_license_ref(license), license.clone());
+            }
+        }
+        let extracted_licenses = licenses.into_iter()
+            .map(|(id, name)| {
+                object(vec![("licenseId", id.to_json()),
+                            ("name", name.to_json()),
+                            ("extractedText",
+                             format!("Declared by the plan as: {}", name).to_json())])
+            })
+            .collect();
+        let mut relationships =
+            vec![spdx_relationship("SPDXRef-DOCUMENT", "DESCRIBES", &spdx_id(&root.ident))];
+        for component in Some(root).into_iter().chain(deps.iter()) {
+            for dep in component.deps.iter() {
+                relationships.push(spdx_relationship(&spdx_id(&component.ident),
+                                                     "DEPENDS_ON",
+                                                     &spdx_id(dep)));
+            }
+        }
+        let creation_info =
+            object(vec![("created", created.to_json()),
+                        ("creators", vec![format!("Tool: hab-{}", VERSION)].to_json())]);
+        let namespace = format!("http://spdx.org/spdxdocs/{}-{}",
+                                root.ident.to_string().replace("/", "-"),
+                                Uuid::new_v4().to_hyphenated_string());
+        object(vec![("spdxVersion", "SPDX-2.3".to_json()),
+                    ("dataLicense", "CC0-1.0".to_json()),
+                    ("SPDXID", "SPDXRef-DOCUMENT".to_json()),
+                    ("name", root.ident.to_string().to_json()),
+                    ("documentNamespace", namespace.to_json()),
+                    ("creationInfo", creation_info),
+                    ("documentDescribes", vec![spdx_id(&root.ident)].to_json()),
+                    ("packages", Json::Array(packages)),
+                    ("relationships", Json::Array(relationships)),
+                    ("hasExtractedLicensingInfos", Json::Array(extracted_licenses))])
+    }
+
+    fn spdx_package(component: &Component, created: &str) -> Json {
+        let ident = &component.ident;
+        let license = spdx_license_expression(&component.licenses);
+        let mut pairs = vec![("SPDXID", spdx_id(ident).to_json()),
+                             ("name", ident.name.to_json()),
+                             ("supplier", format!("Organization: {}", ident.origin).to_json()),
+                             ("versionInfo", version(ident).to_json()),
+                             ("downloadLocation",
+                              component.source
+                                 .clone()
+                                 .unwrap_or("NOASSERTION".to_string())
+                                 .to_json()),
+                             ("filesAnalyzed", false.to_json()),
+                             ("licenseConcluded", "NOASSERTION".to_json()),
+                             ("licenseDeclared",
+                              license.clone().unwrap_or("NOASSERTION".to_string()).to_json()),
+                             ("copyrightText", "NOASSERTION".to_json()),
+                             ("comment", format!("Habitat package {}", ident).to_json())];
+        if let Some(ref release) = ident.release {
+            pairs.push(("annotations",
+                        Json::Array(vec![object(vec![("annotationDate", created.to_json()),
+                                                     ("annotationType", "OTHER".to_json()),
+                                                     ("annotator",
+                                                      format!("Tool: hab-{}", VERSION).to_json()),
+                                                     ("comment",
+                                                      format!("hab:release={}", release)
+                                                          .to_json())])])));
+        }
+        if let Some(name) = ident.archive_name() {
+            pairs.push(("packageFileName", name.to_json()));
+        }
+        if let Some(ref checksum) = component.checksum {
+            pairs.push(("checksums",
+                        Json::Array(vec![object(vec![("algorithm", "BLAKE2b-256".to_json()),
+                                                     ("checksumValue", checksum.to_json())])])));
+        }
+        if let Some(ref shasum) = component.shasum {
+            pairs.push(("sourceInfo",
+                        format!("Built from source with SHA-256 checksum {}", shasum).to_json()));
+        }
+        object(pairs)
+    }
+
+    fn spdx_license_expression(licenses: &[String]) -> Option<String> {
+        if licenses.is_empty() {
+            None
+        } else {
+            let refs: Vec<String> = licenses.iter().map(|l| spdx_license_ref(l)).collect();
+            Some(refs.join(" AND "))
+        }
+    }
+
+    fn spdx_license_ref(license: &str) -> String {
+        format!("LicenseRef-{}", spdx_idstring(license))
+    }
+
+    fn spdx_relationship(element: &str, kind: &str, related: &str) -> Json {
+        object(vec![("spdxElementId", element.to_json()),
+                    ("relationshipType", kind.to_json()),
+                    ("relatedSpdxElement", related.to_json())])
+    }
+
+    fn spdx_id(ident: &PackageIdent) -> String {
+        format!("SPDXRef-Package-{}", spdx_idstring(&ident.to_string()))
+    }
+
+    // SPDX identifiers may only hold letters, numbers, `.` and `-`
+    fn spdx_idstring(value: &str) -> String {
+        value.chars()
+            .map(|c| match c {
+                'a'...'z' | 'A'...'Z' | '0'...'9' | '.' | '-' => c,
+                _ => '-',
+            })
+            .collect()
+    }
+
+    fn cyclonedx(root: &Component, deps: &[Component]) -> Json {
+        let dependencies = Some(root)
+            .into_iter()
+            .chain(deps.iter())
+            .map(|component| {
+                let depends_on: Vec<String> = component.deps
+                    .iter()
+                    .map(|dep| dep.to_string())
+                    .collect();
+                object(vec![("ref", component.ident.to_string().to_json()),
+                            ("dependsOn", depends_on.to_json())])
+            })
+            .collect();
+        let tool = object(vec![("vendor", "Habitat".to_json()),
+                               ("name", "hab".to_json()),
+                               ("version", VERSION.to_json())]);
+        let metadata = object(vec![("timestamp", timestamp().to_json()),
+                                   ("tools", Json::Array(vec![tool])),
+                                   ("component", cyclonedx_component(root, "application"))]);
+        let components = deps.iter().map(|dep| cyclonedx_component(dep, "library")).collect();
+        object(vec![("bomFormat", "CycloneDX".to_json()),
+                    ("specVersion", "1.4".to_json()),
+                    ("serialNumber",
+                     format!("urn:uuid:{}", Uuid::new_v4().to_hyphenated_string()).to_json()),
+                    ("version", Json::U64(1)),
+                    ("metadata", metadata),
+                    ("components", Json::Array(components)),
+                    ("dependencies", Json::Array(dependencies))])
+    }
+
+    fn cyclonedx_component(component: &Component, kind: &str) -> Json {
+        let ident = &component.ident;
+        let mut pairs = vec![("type", kind.to_json()),
+                             ("bom-ref", ident.to_string().to_json()),
+                             ("group", ident.origin.to_json()),
+                             ("name", ident.name.to_json()),
+                             ("version", version(ident).to_json())];
+        if let Some(ref checksum) = component.checksum {
+            pairs.push(("hashes",
+                        Json::Array(vec![object(vec![("alg", "BLAKE2b-256".to_json()),
+                                                     ("content", checksum.to_json())])])));
+        }
+        if !component.licenses.is_empty() {
+            let licenses = component.licenses
+                .iter()
+                .map(|license| object(vec![("license", object(vec![("name", license.to_json())]))]))
+                .collect();
+            pairs.push(("licenses", Json::Array(licenses)));
+        }
+        if let Some(ref source) = component.source {
+            pairs.push(("externalReferences",
+                        Json::Array(vec![object(vec![("type", "distribution".to_json()),
+                                                     ("url", source.to_json())])])));
+        }
+        let mut properties = vec![];
+        if let Some(ref release) = ident.release {
+            properties.push(object(vec![("name", "hab:release".to_json()),
+                                        ("value", release.to_json())]));
+        }
+        if let Some(ref shasum) = component.shasum {
+            properties.push(object(vec![("name", "hab:source_sha256".to_json()),
+                                        ("value", shasum.to_json())]));
+        }
+        if !properties.is_empty() {
+            pairs.push(("properties", Json::Array(properties)));
+        }
+        object(pairs)
+    }
+
+    fn object(pairs: Vec<(&str, Json)>) -> Json {
+        Json::Object(pairs.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    fn version(ident: &PackageIdent) -> String {
+        ident.version.clone().unwrap_or(String::new())
+    }
+
+    fn timestamp() -> String {
+        time::now_utc().rfc3339().to_string()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::path::PathBuf;
+        use std::str::FromStr;
+
+        use hcore::package::{PackageArchive, PackageIdent};
+
+        use super::*;
+
+        fn fixture(name: &str) -> PathBuf {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../core/tests/fixtures").join(name)
+        }
+
+        fn component(licenses: &[&str]) -> Component {
+            Component {
+                ident: PackageIdent::from_str("core/redis/3.0.7/20160614130303").unwrap(),
+                deps: vec![],
+                checksum: None,
+                licenses: licenses.iter().map(|l| l.to_string()).collect(),
+                source: None,
+                shasum: None,
+            }
+        }
+
+        #[test]
+        fn sbom_format_from_str() {
+            assert!(match SbomFormat::from_str("cyclonedx").unwrap() {
+                SbomFormat::CycloneDx => true,
+                _ => false,
+            });
+            assert!(match SbomFormat::from_str("spdx").unwrap() {
+                SbomFormat::Spdx => true,
+                _ => false,
+            });
+            assert!(SbomFormat::from_str("swid").is_err());
+        }
+
+        #[test]
+        fn component_from_archive() {
+            let mut archive = PackageArchive::new(fixture("happyhumans-possums-8.1.4-\
+                                                           20160427165340-x86_64-linux.hart"));
+            let component = from_archive(&mut archive).unwrap();
+            assert_eq!(component.ident.to_string(),
+                       "happyhumans/possums/8.1.4/20160427165340");
+            assert!(component.deps.is_empty());
+            assert!(component.checksum.is_some());
+            assert!(component.licenses.is_empty());
+        }
+
+        #[test]
+        fn manifest_values() {
+            let manifest = "* __License__: Apache-2.0 MIT\n\
+                            * __Source__: [http://example.com/s.tgz](http://example.com/s.tgz)\n\
+                            * __SHA__: `0a1b2c`\n";
+            assert_eq!(manifest_value(manifest, "License"), Some("Apache-2.0 MIT"));
+            assert_eq!(manifest_value(manifest, "Maintainer"), None);
+            assert_eq!(enclosed("`0a1b2c`", '`', '`'), Some("0a1b2c".to_string()));
+            assert_eq!(enclosed("No shasum given", '`', '`'), None);
+            assert_eq!(enclosed("``", '`', '`'), None);
+
+            let mut component = component(&[]);
+            fill_from_manifest(&mut component, manifest);
+            assert_eq!(component.licenses, vec!["Apache-2.0", "MIT"]);
+            assert_eq!(component.source, Some("http://example.com/s.tgz".to_string()));
+            assert_eq!(component.shasum, Some("0a1b2c".to_string()));
+        }
+
+        #[test]
+        fn license_expressions() {
+            assert_eq!(spdx_license_expression(&["Apache-2.0".to_string(),
+                                                 "GPL-2.0+".to_string()]),
+                       Some("LicenseRef-Apache-2.0 AND LicenseRef-GPL-2.0-".to_string()));
+            assert_eq!(spdx_license_expression(&["Boost Software License".to_string()]),
+                       Some("LicenseRef-Boost-Software-License".to_string()));
+            assert_eq!(spdx_license_expression(&[]), None);
+        }
+
+        #[test]
+        fn package_spdx_id() {
+            let ident = PackageIdent::from_str("core/redis/3.0.7/20160614130303").unwrap();
+            assert_eq!(spdx_id(&ident),
+                       "SPDXRef-Package-core-redis-3.0.7-20160614130303");
+        }
+
+        #[test]
+        fn spdx_licenses_and_release() {
+            let doc = spdx(&component(&["BSD"]), &[]);
+            let package = &doc.find("packages").unwrap().as_array().unwrap()[0];
+            assert_eq!(package.find("licenseDeclared").and_then(|l| l.as_string()),
+                       Some("LicenseRef-BSD"));
+            assert_eq!(package.find("versionInfo").and_then(|v| v.as_string()),
+                       Some("3.0.7"));
+            let annotation = &package.find("annotations").unwrap().as_array().unwrap()[0];
+            assert_eq!(annotation.find("comment").and_then(|c| c.as_string()),
+                       Some("hab:release=20160614130303"));
+            let licenses = doc.find("hasExtractedLicensingInfos").unwrap().as_array().unwrap();
+            assert_eq!(licenses.len(), 1);
+            assert_eq!(licenses[0].find("licenseId").and_then(|l| l.as_string()),
+                       Some("LicenseRef-BSD"));
+            assert_eq!(licenses[0].find("name").and_then(|l| l.as_string()),
+                       Some("BSD"));
+        }
+
+        #[test]
+        fn cyclonedx_licenses() {
+            let json = cyclonedx_component(&component(&["Apache-2.0", "Boost Software License"]),
+                                           "library");
+            let licenses = json.find("licenses").unwrap().as_array().unwrap();
+            assert_eq!(licenses[0].find_path(&["license", "name"]).and_then(|l| l.as_string()),
+                       Some("Apache-2.0"));
+            assert_eq!(licenses[1].find_path(&["license", "name"]).and_then(|l| l.as_string()),
+                       Some("Boost Software License"));
+            assert!(licenses.iter().all(|l| l.find_path(&["license", "id"]).is_none()));
+        }
+    }
+}
+
 pub mod sign {
     use std::path::Path;
 
@@ -301,6 +896,113 @@ pub mod sign {
                  Blue.paint(format!("★ Signed artifact {}.", dst.display())));
         Ok(())
     }
+
+    pub fn append(key: &SigKeyPair, src: &Path, dst: &Path, cache: &Path) -> Result<()> {
+        println!("{}",
+                 Yellow.bold().paint(format!("» Countersigning {}", src.display())));
+        println!("{} {} with {} to create {}",
+                 Green.paint("☛ Countersigning"),
+                 src.display(),
+                 &key.name_with_rev(),
+                 dst.display());
+        try!(artifact::append_signature(src, dst, key, cache));
+        println!("{}",
+                 Blue.paint(format!("★ Countersigned artifact {}.", dst.display())));
+        Ok(())
+    }
+}
+
+pub mod uninstall {
+    use std::fs::{self, File};
+    use std::io::{self, Read};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use ansi_term::Colour::{Blue, Yellow};
+    use hcore::fs::SVC_PATH;
+    use hcore::package::{PackageIdent, PackageIndex, PackageInstall};
+    use hcore::package::install::InstallLock;
+    use libc;
+
+    use error::{Error, Result};
+
+    pub fn start(ident: &PackageIdent, fs_root_path: &Path) -> Result<()> {
+        // Hold the lock from the check until the package is gone, so nothing installed
+        // meanwhile can come to depend on it
+        let lock = try!(InstallLock::acquire(fs_root_path));
+        let pkg_install = try!(PackageInstall::load(ident, Some(fs_root_path)));
+        let ident = pkg_install.ident().clone();
+        println!("{}",
+                 Yellow.bold().paint(format!("» Uninstalling {}", &ident)));
+        let users = try!(users_of(&ident, fs_root_path));
+        if !users.is_empty() {
+            return Err(Error::PackageInUse(ident.to_string(), users));
+        }
+        try!(pkg_install.uninstall_locked(&lock));
+        println!("{}", Blue.paint(format!("★ Uninstalled {}", &ident)));
+        Ok(())
+    }
+
+    /// Returns the name and package release of every service the Supervisor is running, from
+    /// the files it keeps in each service's directory under `fs_root_path`.
+    pub fn running_services(fs_root_path: &Path) -> Result<Vec<(String, PackageIdent)>> {
+        let mut services = vec![];
+        let svc_root = fs_root_path.join(SVC_PATH);
+        if !svc_root.is_dir() {
+            return Ok(services);
+        }
+        for entry in try!(fs::read_dir(&svc_root)) {
+            let service = try!(entry).file_name().to_string_lossy().into_owned();
+            let pid = match read_file(&svc_root.join(&service).join("PID")) {
+                Some(pid) => {
+                    match pid.trim().parse::<libc::pid_t>() {
+                        Ok(pid) => pid,
+                        Err(_) => continue,
+                    }
+                }
+                None => continue,
+            };
+            if !is_running(pid) {
+                continue;
+            }
+            if let Some(ident) = read_file(&svc_root.join(&service).join("PKG_IDENT")) {
+                if let Ok(ident) = PackageIdent::from_str(ident.trim()) {
+                    services.push((service, ident));
+                }
+            }
+        }
+        Ok(services)
+    }
+
+    /// Returns what still needs an installed package: the installed packages which depend on
+    /// it, and the services running it.
+    fn users_of(ident: &PackageIdent, fs_root_path: &Path) -> Result<Vec<String>> {
+        let index = try!(PackageIndex::load(Some(fs_root_path)));
+        let mut users: Vec<String> =
+            index.transitive_dependents(ident).iter().map(|i| i.to_string()).collect();
+        for (service, running) in try!(running_services(fs_root_path)) {
+            if &running == ident {
+                users.push(format!("the running {} service", service));
+            }
+        }
+        Ok(users)
+    }
+
+    fn read_file(path: &Path) -> Option<String> {
+        let mut content = String::new();
+        match File::open(path).and_then(|mut f| f.read_to_string(&mut content)) {
+            Ok(_) => Some(content),
+            Err(_) => None,
+        }
+    }
+
+    fn is_running(pid: libc::pid_t) -> bool {
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        // The process exists, but belongs to someone we can't signal
+        io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
 }
 
 pub mod upload {
@@ -437,18 +1139,18 @@ pub mod verify {
     use std::path::Path;
 
     use ansi_term::Colour::{Blue, Green, Yellow};
-    use hcore::crypto::artifact;
+    use hcore::crypto::artifact::{self, SignaturePolicy};
 
     use error::Result;
 
-    pub fn start(src: &Path, cache: &Path) -> Result<()> {
+    pub fn start(src: &Path, cache: &Path, policy: &SignaturePolicy) -> Result<()> {
         println!("{}",
                  Yellow.bold().paint(format!("» Verifying artifact {}", &src.display())));
-        let (name_with_rev, hash) = try!(artifact::verify(src, cache));
+        let (names_with_rev, hash) = try!(artifact::verify_with_policy(src, cache, policy));
         println!("{} checksum {} signed with {}",
                  Green.bold().paint("✓ Verifed"),
                  &hash,
-                 &name_with_rev);
+                 names_with_rev.join(", "));
         println!("{}",
                  Blue.paint(format!("★ Verified artifact {}.", &src.display())));
         Ok(())
@@ -71,3 +71,94 @@ pub mod key {
         }
     }
 }
+
+pub mod members {
+    use ansi_term::Colour::{Blue, Yellow};
+    use habitat_gossip::rumor::Message;
+    use hcore::crypto::SymKey;
+
+    use error::Result;
+    use gossip;
+
+    pub fn start(peers: &Vec<String>, ring_key: Option<&SymKey>) -> Result<()> {
+        println!("{}",
+                 Yellow.bold().paint(format!("» Querying ring members via {:?}", &peers)));
+        let rumors = try!(gossip::query_peers(peers, ring_key));
+        let mut members: Vec<_> = rumors.values()
+            .filter_map(|rumor| match rumor.payload {
+                Message::Member(ref member) => Some(member),
+                _ => None,
+            })
+            .collect();
+        members.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+        for member in members.iter() {
+            println!("{}\t{}\t{}\t{:?}\t{}",
+                     member.id,
+                     member.hostname,
+                     member.gossip_listener,
+                     member.health,
+                     member.zone.as_ref().map_or("-", |z| &z[..]));
+        }
+        println!("{}", Blue.paint(format!("★ {} members.", members.len())));
+        Ok(())
+    }
+}
+
+pub mod census {
+    use ansi_term::Colour::{Blue, Yellow};
+    use habitat_gossip::rumor::Message;
+    use hcore::crypto::SymKey;
+    use hcore::service::ServiceGroup;
+
+    use error::Result;
+    use gossip;
+
+    pub fn start(peers: &Vec<String>,
+                 ring_key: Option<&SymKey>,
+                 sg: Option<&ServiceGroup>)
+                 -> Result<()> {
+        println!("{}",
+                 Yellow.bold().paint(format!("» Querying ring census via {:?}", &peers)));
+        let rumors = try!(gossip::query_peers(peers, ring_key));
+        let mut entries: Vec<_> = rumors.values()
+            .filter_map(|rumor| match rumor.payload {
+                Message::CensusEntry(ref ce) => Some(ce),
+                _ => None,
+            })
+            .filter(|ce| match sg {
+                Some(sg) => ce.service == sg.service && ce.group == sg.group,
+                None => true,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (a.service_group(), &a.hostname).cmp(&(b.service_group(), &b.hostname))
+        });
+        for ce in entries.iter() {
+            let role = if ce.leader {
+                "leader"
+            } else if ce.follower {
+                "follower"
+            } else {
+                "-"
+            };
+            let status = if ce.alive {
+                "alive"
+            } else if ce.suspect {
+                "suspect"
+            } else if ce.confirmed {
+                "confirmed"
+            } else {
+                "detached"
+            };
+            println!("{}\t{}\t{}\t{}\t{}\t{}",
+                     ce.service_group(),
+                     ce.hostname,
+                     ce.ip,
+                     ce.port.as_ref().map_or("-", |p| &p[..]),
+                     role,
+                     status);
+        }
+        println!("{}", Blue.paint(format!("★ {} census entries.", entries.len())));
+        Ok(())
+    }
+}
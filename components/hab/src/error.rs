@@ -21,6 +21,7 @@ use std::result;
 
 use depot_client;
 use common;
+use habitat_gossip;
 use hcore;
 
 pub type Result<T> = result::Result<T, Error>;
@@ -37,11 +38,14 @@ pub enum Error {
     FileNotFound(String),
     HabitatCommon(common::Error),
     HabitatCore(hcore::Error),
+    HabitatGossip(habitat_gossip::Error),
     IO(io::Error),
     PackageArchiveMalformed(String),
+    PackageInUse(String, Vec<String>),
     PathPrefixError(path::StripPrefixError),
     SubcommandNotSupported(String),
     UnsupportedExportFormat(String),
+    UnsupportedSbomFormat(String),
 }
 
 impl fmt::Display for Error {
@@ -62,16 +66,23 @@ impl fmt::Display for Error {
             Error::FileNotFound(ref e) => format!("File not found at: {}", e),
             Error::HabitatCommon(ref e) => format!("{}", e),
             Error::HabitatCore(ref e) => format!("{}", e),
+            Error::HabitatGossip(ref e) => format!("{}", e),
             Error::IO(ref err) => format!("{}", err),
             Error::PackageArchiveMalformed(ref e) => {
                 format!("Package archive was unreadable or contained unexpected contents: {:?}",
                         e)
             }
+            Error::PackageInUse(ref ident, ref users) => {
+                format!("{} is still needed by {}", ident, users.join(", "))
+            }
             Error::PathPrefixError(ref err) => format!("{}", err),
             Error::SubcommandNotSupported(ref e) => {
                 format!("Subcommand `{}' not supported on this operating system", e)
             }
             Error::UnsupportedExportFormat(ref e) => format!("Unsupported export format: {}", e),
+            Error::UnsupportedSbomFormat(ref e) => {
+                format!("Unsupported SBOM format: {}, must be one of (spdx, cyclonedx)", e)
+            }
         };
         write!(f, "{}", msg)
     }
@@ -91,13 +102,16 @@ impl error::Error for Error {
             Error::FileNotFound(_) => "File not found",
             Error::HabitatCommon(ref err) => err.description(),
             Error::HabitatCore(ref err) => err.description(),
+            Error::HabitatGossip(ref err) => err.description(),
             Error::IO(ref err) => err.description(),
             Error::PackageArchiveMalformed(_) => {
                 "Package archive was unreadable or had unexpected contents"
             }
+            Error::PackageInUse(_, _) => "Package is still needed by other packages or services",
             Error::PathPrefixError(ref err) => err.description(),
             Error::SubcommandNotSupported(_) => "Subcommand not supported on this operating system",
             Error::UnsupportedExportFormat(_) => "Unsupported export format",
+            Error::UnsupportedSbomFormat(_) => "Unsupported SBOM format",
         }
     }
 }
@@ -126,6 +140,12 @@ impl From<hcore::Error> for Error {
     }
 }
 
+impl From<habitat_gossip::Error> for Error {
+    fn from(err: habitat_gossip::Error) -> Error {
+        Error::HabitatGossip(err)
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Error {
         Error::IO(err)
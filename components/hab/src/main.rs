@@ -15,6 +15,7 @@
 extern crate habitat_core as hcore;
 extern crate habitat_common as common;
 extern crate habitat_depot_client as depot_client;
+extern crate habitat_gossip;
 extern crate habitat_http_client as http_client;
 
 extern crate ansi_term;
@@ -22,15 +23,15 @@ extern crate ansi_term;
 extern crate clap;
 extern crate env_logger;
 extern crate hyper;
+extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate pbr;
 extern crate regex;
 extern crate rustc_serialize;
+extern crate time;
 extern crate toml;
 extern crate url;
-// Temporary depdency for gossip/rumor injection code duplication.
-extern crate utp;
 extern crate uuid;
 
 mod analytics;
@@ -51,16 +52,17 @@ use std::thread;
 use ansi_term::Colour::Red;
 use clap::ArgMatches;
 
+use command::pkg::sbom::SbomFormat;
 use error::{Error, Result};
 use hcore::env as henv;
 use hcore::crypto::{init, default_cache_key_path, BoxKeyPair, SigKeyPair, SymKey};
+use hcore::crypto::artifact::SignaturePolicy;
 use hcore::crypto::keys::PairType;
 use hcore::fs::{cache_artifact_path, cache_analytics_path, FS_ROOT_PATH};
 use hcore::service::ServiceGroup;
 use hcore::package::PackageIdent;
 use hcore::url::{DEFAULT_DEPOT_URL, DEPOT_URL_ENVVAR};
-
-use gossip::hab_gossip;
+use habitat_gossip::GOSSIP_DEFAULT_PORT;
 
 const VERSION: &'static str = include_str!(concat!(env!("OUT_DIR"), "/VERSION"));
 
@@ -73,6 +75,8 @@ const HABITAT_ORG_ENVVAR: &'static str = "HAB_ORG";
 /// Makes the --user CLI param optional when this env var is set
 const HABITAT_USER_ENVVAR: &'static str = "HAB_USER";
 
+const HABITAT_SIGNER_ENVVAR: &'static str = "HAB_SIGNER";
+
 const FS_ROOT_ENVVAR: &'static str = "FS_ROOT";
 
 const DEFAULT_BINLINK_DIR: &'static str = "/bin";
@@ -129,6 +133,7 @@ fn start() -> Result<()> {
                         ("export", Some(sc)) => try!(sub_origin_key_export(sc)),
                         ("generate", Some(sc)) => try!(sub_origin_key_generate(sc)),
                         ("import", Some(_)) => try!(sub_origin_key_import()),
+                        ("revoke", Some(sc)) => try!(sub_origin_key_revoke(sc)),
                         ("upload", Some(sc)) => try!(sub_origin_key_upload(sc)),
                         _ => unreachable!(),
                     }
@@ -140,12 +145,16 @@ fn start() -> Result<()> {
             match matches.subcommand() {
                 ("binlink", Some(m)) => try!(sub_pkg_binlink(m)),
                 ("build", Some(m)) => try!(sub_pkg_build(m)),
+                ("dependents", Some(m)) => try!(sub_pkg_dependents(m)),
                 ("exec", Some(m)) => try!(sub_pkg_exec(m, remaining_args)),
                 ("export", Some(m)) => try!(sub_pkg_export(m)),
+                ("gc", Some(m)) => try!(sub_pkg_gc(m)),
                 ("hash", Some(m)) => try!(sub_pkg_hash(m)),
                 ("install", Some(m)) => try!(sub_pkg_install(m)),
                 ("path", Some(m)) => try!(sub_pkg_path(m)),
+                ("sbom", Some(m)) => try!(sub_pkg_sbom(m)),
                 ("sign", Some(m)) => try!(sub_pkg_sign(m)),
+                ("uninstall", Some(m)) => try!(sub_pkg_uninstall(m)),
                 ("upload", Some(m)) => try!(sub_pkg_upload(m)),
                 ("verify", Some(m)) => try!(sub_pkg_verify(m)),
                 _ => unreachable!(),
@@ -161,6 +170,14 @@ fn start() -> Result<()> {
                         _ => unreachable!(),
                     }
                 }
+                ("members", Some(m)) => try!(sub_ring_members(m)),
+                ("census", Some(m)) => try!(sub_ring_census(m)),
+                _ => unreachable!(),
+            }
+        }
+        ("secret", Some(matches)) => {
+            match matches.subcommand() {
+                ("apply", Some(m)) => try!(sub_secret_apply(m)),
                 _ => unreachable!(),
             }
         }
@@ -204,14 +221,7 @@ fn sub_cli_setup() -> Result<()> {
 fn sub_config_apply(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
-    let peers_str = m.value_of("PEER").unwrap_or("127.0.0.1");
-    let mut peers: Vec<String> = peers_str.split(",").map(|p| p.into()).collect();
-    for p in peers.iter_mut() {
-        if p.find(':').is_none() {
-            p.push(':');
-            p.push_str(&hab_gossip::GOSSIP_DEFAULT_PORT.to_string());
-        }
-    }
+    let peers = peers_from(&m);
     let number = value_t!(m, "VERSION_NUMBER", u64).unwrap_or_else(|e| e.exit());
     let file_path = match m.value_of("FILE") {
         Some("-") | None => None,
@@ -234,20 +244,23 @@ fn sub_config_apply(m: &ArgMatches) -> Result<()> {
     };
     sg.organization = org;
 
-    command::config::apply::start(&peers, ring_key.as_ref(), &sg, number, file_path)
+    let signer = match signer_param_or_env(&m) {
+        Some(name) => Some(try!(SigKeyPair::get_latest_pair_for(&name, &cache))),
+        None => None,
+    };
+
+    command::config::apply::start(&peers,
+                                  ring_key.as_ref(),
+                                  signer.as_ref(),
+                                  &sg,
+                                  number,
+                                  file_path)
 }
 
 fn sub_file_upload(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
-    let peers_str = m.value_of("PEER").unwrap_or("127.0.0.1");
-    let mut peers: Vec<String> = peers_str.split(",").map(|p| p.into()).collect();
-    for p in peers.iter_mut() {
-        if p.find(':').is_none() {
-            p.push(':');
-            p.push_str(&hab_gossip::GOSSIP_DEFAULT_PORT.to_string());
-        }
-    }
+    let peers = peers_from(&m);
     let number = value_t!(m, "VERSION_NUMBER", u64).unwrap_or_else(|e| e.exit());
     let file_path = Path::new(m.value_of("FILE").unwrap());
     match file_path.metadata() {
@@ -280,14 +293,57 @@ fn sub_file_upload(m: &ArgMatches) -> Result<()> {
     let user = try!(user_param_or_env(&m));
     let user_pair = try!(BoxKeyPair::get_latest_pair_for(&user, &cache));
 
+    let signer = match signer_param_or_env(&m) {
+        Some(name) => Some(try!(SigKeyPair::get_latest_pair_for(&name, &cache))),
+        None => None,
+    };
+
     command::file::upload::start(&peers,
                                  ring_key.as_ref(),
+                                 signer.as_ref(),
                                  &user_pair,
                                  &service_pair,
                                  number,
                                  file_path)
 }
 
+fn sub_secret_apply(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Some(Path::new(&fs_root));
+    let peers = peers_from(&m);
+    let number = value_t!(m, "VERSION_NUMBER", u64).unwrap_or_else(|e| e.exit());
+    let file_path = Path::new(m.value_of("FILE").unwrap());
+
+    init();
+    let cache = default_cache_key_path(fs_root_path);
+    let ring_key = match m.value_of("RING") {
+        Some(name) => Some(try!(SymKey::get_latest_pair_for(&name, &cache))),
+        None => None,
+    };
+
+    let mut sg = try!(ServiceGroup::from_str(m.value_of("SERVICE_GROUP").unwrap()));
+    // secrets are encrypted for the service group's key, which is named for its organization
+    let org = try!(org_param_or_env(&m));
+    sg.organization = Some(org.to_string());
+    let service_pair = try!(BoxKeyPair::get_latest_pair_for(&sg.to_string(), &cache));
+
+    let user = try!(user_param_or_env(&m));
+    let user_pair = try!(BoxKeyPair::get_latest_pair_for(&user, &cache));
+
+    let signer = match signer_param_or_env(&m) {
+        Some(name) => Some(try!(SigKeyPair::get_latest_pair_for(&name, &cache))),
+        None => None,
+    };
+
+    command::secret::apply::start(&peers,
+                                  ring_key.as_ref(),
+                                  signer.as_ref(),
+                                  &user_pair,
+                                  &service_pair,
+                                  number,
+                                  file_path)
+}
+
 fn sub_origin_key_download(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
@@ -307,9 +363,16 @@ fn sub_origin_key_export(m: &ArgMatches) -> Result<()> {
     let fs_root_path = Some(Path::new(&fs_root));
     let origin = m.value_of("ORIGIN").unwrap();
     let pair_type = try!(PairType::from_str(m.value_of("PAIR_TYPE").unwrap()));
+    let encrypt = m.is_present("ENCRYPT");
+    if encrypt && pair_type != PairType::Secret {
+        return Err(Error::ArgumentError("Only secret keys can be exported with --encrypt"));
+    }
     init();
 
-    command::origin::key::export::start(origin, pair_type, &default_cache_key_path(fs_root_path))
+    command::origin::key::export::start(origin,
+                                        pair_type,
+                                        encrypt,
+                                        &default_cache_key_path(fs_root_path))
 }
 
 fn sub_origin_key_generate(m: &ArgMatches) -> Result<()> {
@@ -331,6 +394,23 @@ fn sub_origin_key_import() -> Result<()> {
     command::origin::key::import::start(&content, &default_cache_key_path(fs_root_path))
 }
 
+fn sub_origin_key_revoke(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Some(Path::new(&fs_root));
+    let env_or_default = henv::var(DEPOT_URL_ENVVAR).unwrap_or(DEFAULT_DEPOT_URL.to_string());
+    let url = m.value_of("DEPOT_URL").unwrap_or(&env_or_default);
+    let token = try!(auth_token_param_or_env(&m));
+    let origin = m.value_of("ORIGIN").unwrap();
+    let revisions: Vec<&str> = m.values_of("REVISION").unwrap().collect();
+    init();
+
+    command::origin::key::revoke::start(url,
+                                        &token,
+                                        origin,
+                                        &revisions,
+                                        &default_cache_key_path(fs_root_path))
+}
+
 fn sub_origin_key_upload(m: &ArgMatches) -> Result<()> {
 
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
@@ -397,6 +477,14 @@ fn sub_pkg_build(m: &ArgMatches) -> Result<()> {
     command::pkg::build::start(plan_context, root, src, keys, reuse)
 }
 
+fn sub_pkg_dependents(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Path::new(&fs_root);
+    let ident = try!(PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap()));
+
+    command::pkg::dependents::start(&ident, m.is_present("TRANSITIVE"), &fs_root_path)
+}
+
 fn sub_pkg_exec(m: &ArgMatches, cmd_args: Vec<OsString>) -> Result<()> {
     let ident = try!(PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap()));
     let cmd = m.value_of("CMD").unwrap();
@@ -436,6 +524,14 @@ fn sub_pkg_install(m: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+fn sub_pkg_gc(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Path::new(&fs_root);
+    let keep = m.value_of("KEEP").unwrap_or("2").parse::<usize>().unwrap();
+
+    command::pkg::gc::start(keep, &fs_root_path)
+}
+
 fn sub_pkg_path(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Path::new(&fs_root);
@@ -444,16 +540,43 @@ fn sub_pkg_path(m: &ArgMatches) -> Result<()> {
     command::pkg::path::start(&ident, &fs_root_path)
 }
 
+fn sub_pkg_sbom(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Path::new(&fs_root);
+    let ident_or_artifact = m.value_of("PKG_IDENT_OR_ARTIFACT").unwrap();
+    let format = try!(SbomFormat::from_str(m.value_of("FORMAT").unwrap_or("spdx")));
+
+    command::pkg::sbom::start(ident_or_artifact, &format, &fs_root_path)
+}
+
 fn sub_pkg_sign(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
     let src = Path::new(m.value_of("SOURCE").unwrap());
-    let dst = Path::new(m.value_of("DEST").unwrap());
+    let append = m.is_present("APPEND");
+    // Countersigning rewrites the artifact in place unless told otherwise
+    let dst = match m.value_of("DEST") {
+        Some(dst) => Path::new(dst),
+        None if append => src,
+        None => return Err(Error::ArgumentError("DEST is required unless --append is given")),
+    };
     init();
-    let pair = try!(SigKeyPair::get_latest_pair_for(&try!(origin_param_or_env(&m)),
-                                                    &default_cache_key_path(fs_root_path)));
+    let cache = default_cache_key_path(fs_root_path);
+    let pair = try!(SigKeyPair::get_latest_pair_for(&try!(origin_param_or_env(&m)), &cache));
 
-    command::pkg::sign::start(&pair, &src, &dst)
+    if append {
+        command::pkg::sign::append(&pair, &src, &dst, &cache)
+    } else {
+        command::pkg::sign::start(&pair, &src, &dst)
+    }
+}
+
+fn sub_pkg_uninstall(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Path::new(&fs_root);
+    let ident = try!(PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap()));
+
+    command::pkg::uninstall::start(&ident, &fs_root_path)
 }
 
 fn sub_pkg_upload(m: &ArgMatches) -> Result<()> {
@@ -471,9 +594,13 @@ fn sub_pkg_verify(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
     let src = Path::new(m.value_of("SOURCE").unwrap());
+    let policy = match m.value_of("POLICY") {
+        Some(policy) => try!(SignaturePolicy::from_str(policy)),
+        None => SignaturePolicy::default(),
+    };
     init();
 
-    command::pkg::verify::start(&src, &default_cache_key_path(fs_root_path))
+    command::pkg::verify::start(&src, &default_cache_key_path(fs_root_path), &policy)
 }
 
 fn sub_ring_key_export(m: &ArgMatches) -> Result<()> {
@@ -504,6 +631,40 @@ fn sub_ring_key_import() -> Result<()> {
     command::ring::key::import::start(&content, &default_cache_key_path(fs_root_path))
 }
 
+fn sub_ring_members(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Some(Path::new(&fs_root));
+    let peers = peers_from(&m);
+    init();
+    let ring_key = match m.value_of("RING") {
+        Some(name) => {
+            Some(try!(SymKey::get_latest_pair_for(&name, &default_cache_key_path(fs_root_path))))
+        }
+        None => None,
+    };
+
+    command::ring::members::start(&peers, ring_key.as_ref())
+}
+
+fn sub_ring_census(m: &ArgMatches) -> Result<()> {
+    let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
+    let fs_root_path = Some(Path::new(&fs_root));
+    let peers = peers_from(&m);
+    let sg = match m.value_of("SERVICE_GROUP") {
+        Some(sg) => Some(try!(ServiceGroup::from_str(sg))),
+        None => None,
+    };
+    init();
+    let ring_key = match m.value_of("RING") {
+        Some(name) => {
+            Some(try!(SymKey::get_latest_pair_for(&name, &default_cache_key_path(fs_root_path))))
+        }
+        None => None,
+    };
+
+    command::ring::census::start(&peers, ring_key.as_ref(), sg.as_ref())
+}
+
 fn sub_service_key_generate(m: &ArgMatches) -> Result<()> {
     let fs_root = henv::var(FS_ROOT_ENVVAR).unwrap_or(FS_ROOT_PATH.to_string());
     let fs_root_path = Some(Path::new(&fs_root));
@@ -612,6 +773,30 @@ fn org_param_or_env(m: &ArgMatches) -> Result<String> {
     }
 }
 
+/// Returns the PEER param as a list of peers, adding the default gossip port to any peer without
+/// one.
+fn peers_from(m: &ArgMatches) -> Vec<String> {
+    let peers_str = m.value_of("PEER").unwrap_or("127.0.0.1");
+    let mut peers: Vec<String> = peers_str.split(",").map(|p| p.into()).collect();
+    for p in peers.iter_mut() {
+        if p.find(':').is_none() {
+            p.push(':');
+            p.push_str(&GOSSIP_DEFAULT_PORT.to_string());
+        }
+    }
+    peers
+}
+
+/// Check to see if the user has passed in a SIGNER param.
+/// If not, check the HAB_SIGNER env var. If that's
+/// empty too, then nothing is signed.
+fn signer_param_or_env(m: &ArgMatches) -> Option<String> {
+    match m.value_of("SIGNER") {
+        Some(s) => Some(s.to_string()),
+        None => henv::var(HABITAT_SIGNER_ENVVAR).ok(),
+    }
+}
+
 /// Check to see if the user has passed in a USER param.
 /// If not, check the HAB_USER env var. If that's
 /// empty too, then return an error.
@@ -169,6 +169,43 @@
 # pkg_expose=(80 443)
 # ```
 #
+# ### pkg_config_actions
+# An array of `file=action` pairs declaring what the supervisor does when a
+# rendered config file changes: `restart` the service, run the `reconfigure`
+# hook only, or `none`. Files not listed restart the service, except that a
+# gossiped config change runs the `reconfigure` hook instead, if there is one.
+# ```
+# pkg_config_actions=(haproxy.conf=reconfigure errors.http=none)
+# ```
+#
+# ### pkg_config_perms
+# An array of `file=mode[:owner[:group]]` pairs giving the mode, and optionally
+# the owner, of rendered config files. Files not listed keep the supervisor's
+# default mode and owner.
+# ```
+# pkg_config_perms=(secrets.conf=0640:hab:hab)
+# ```
+#
+# ### pkg_binds
+# An associative array of the services this package must be bound to, each
+# with a space separated list of the values the bound service has to export.
+# ```
+# pkg_binds=([database]="port host")
+# ```
+#
+# ### pkg_binds_optional
+# Like `pkg_binds`, but the package runs without these binds.
+# ```
+# pkg_binds_optional=([cache]="port")
+# ```
+#
+# ### pkg_exports
+# An associative array of the values this package exports to services bound to
+# it, each with the config key it is read from.
+# ```
+# pkg_exports=([port]=port [host]=network.host)
+# ```
+#
 # ### pkg_origin
 # A string to use for the origin. The origin is used to denote a particular upstream of a
 # package; when we resolve dependencies, we consider a version of a package to be equal
@@ -348,6 +385,16 @@ pkg_include_dirs=()
 pkg_svc_run=''
 # An array of ports to expose.
 pkg_expose=()
+# An array of `file=action` pairs for rendered config file changes
+pkg_config_actions=()
+# An array of `file=mode[:owner[:group]]` pairs for rendered config files
+pkg_config_perms=()
+# The services this package must be bound to, and the values each must export
+declare -A pkg_binds
+# The services this package can be bound to, and the values each must export
+declare -A pkg_binds_optional
+# The values this package exports to services bound to it, and their config keys
+declare -A pkg_exports
 # The user to run the service as
 pkg_svc_user=hab
 # The group to run the service as
@@ -1753,14 +1800,22 @@ do_default_install() {
 
 # **Internal** Write out the package data to files:
 #
+# * `$pkg_prefix/BUILDER_VERSION` - The version of this program which built the package
 # * `$pkg_prefix/BUILD_DEPS` - Any dependencies we need build the package
+# * `$pkg_prefix/BUILD_HOST` - The name of the host which built the package
 # * `$pkg_prefix/CFLAGS` - Any CFLAGS for things that link against us
+# * `$pkg_prefix/CONFIG_ACTIONS` - What to do when each rendered config file changes
 # * `$pkg_prefix/DEPS` - Any dependencies we need to use the package at runtime
 # * `$pkg_prefix/EXPOSES` - Any ports we expose
 # * `$pkg_prefix/FILES` - blake2b checksums of all files in the package
 # * `$pkg_prefix/LDFLAGS` - Any LDFLAGS for things that link against us
 # * `$pkg_prefix/LD_RUN_PATH` - The LD_RUN_PATH for things that link against us
+# * `$pkg_prefix/LICENSES` - The license identifiers of the software, one per line
 # * `$pkg_prefix/PATH` - Any PATH entries for things that link against us
+# * `$pkg_prefix/PLAN_COMMIT` - The git commit of the Plan, if it is in a git
+#   repository
+# * `$pkg_prefix/SHASUM` - The sha256 sum of the downloaded `$pkg_source`
+# * `$pkg_prefix/SOURCE` - The URL the software was downloaded from
 _build_metadata() {
   build_line "Building package metadata"
   local ld_run_path_part=""
@@ -1820,6 +1875,26 @@ _build_metadata() {
     echo $port_part > $pkg_prefix/EXPOSES
   fi
 
+  if [[ ${#pkg_config_actions[@]} -gt 0 ]]; then
+    printf "%s\n" "${pkg_config_actions[@]}" > $pkg_prefix/CONFIG_ACTIONS
+  fi
+
+  if [[ ${#pkg_config_perms[@]} -gt 0 ]]; then
+    printf "%s\n" "${pkg_config_perms[@]}" > $pkg_prefix/CONFIG_PERMS
+  fi
+
+  local bind
+  for bind in "${!pkg_binds[@]}"; do
+    echo "$bind=${pkg_binds[$bind]}" >> $pkg_prefix/BINDS
+  done
+  for bind in "${!pkg_binds_optional[@]}"; do
+    echo "$bind=${pkg_binds_optional[$bind]}" >> $pkg_prefix/BINDS_OPTIONAL
+  done
+  local exported
+  for exported in "${!pkg_exports[@]}"; do
+    echo "$exported=${pkg_exports[$exported]}" >> $pkg_prefix/EXPORTS
+  done
+
   if [[ ${#pkg_interpreters[@]} -gt 0 ]]; then
     local interpreters="$(printf "${pkg_prefix}/%s\n" ${pkg_interpreters[@]})"
     printf "%s\n" ${pkg_interpreters[@]} \
@@ -1849,6 +1924,23 @@ _build_metadata() {
     echo "$deps" > $pkg_prefix/TDEPS
   fi
 
+  # This is `LICENSES`, not `LICENSE`, so it doesn't clobber a `LICENSE` file the software
+  # installs into `$pkg_prefix` itself.
+  if [[ ${#pkg_license[@]} -gt 0 ]]; then
+    printf "%s\n" "${pkg_license[@]}" > $pkg_prefix/LICENSES
+  fi
+  if [[ -n "${pkg_source:-}" ]]; then
+    echo "$pkg_source" > $pkg_prefix/SOURCE
+  fi
+  if [[ -n "${pkg_shasum:-}" ]]; then
+    echo "$pkg_shasum" > $pkg_prefix/SHASUM
+  fi
+  uname -n > $pkg_prefix/BUILD_HOST
+  echo "$HAB_PLAN_BUILD" > $pkg_prefix/BUILDER_VERSION
+  if exists git && git -C "$PLAN_CONTEXT" rev-parse HEAD > /dev/null 2>&1; then
+    git -C "$PLAN_CONTEXT" rev-parse HEAD > $pkg_prefix/PLAN_COMMIT
+  fi
+
   echo "$pkg_target" > $pkg_prefix/TARGET
   echo "${pkg_origin}/${pkg_name}/${pkg_version}/${pkg_release}" \
     >> $pkg_prefix/IDENT
@@ -1868,7 +1960,7 @@ _build_metadata() {
 }
 
 # Copy the `./config` directory, relative to the Plan, to `$pkg_prefix/config`.
-# Do the same with `default.toml`. Delegates most of the implementation to the
+# Do the same with `default.toml` and `schema.toml`. Delegates most of the implementation to the
 # `do_default_build_config()` function.
 do_build_config() {
   do_default_build_config
@@ -1889,6 +1981,9 @@ do_default_build_config() {
   if [[ -f $PLAN_CONTEXT/default.toml ]]; then
     cp $PLAN_CONTEXT/default.toml $pkg_prefix
   fi
+  if [[ -f $PLAN_CONTEXT/schema.toml ]]; then
+    cp $PLAN_CONTEXT/schema.toml $pkg_prefix
+  fi
   return 0
 }
 
@@ -16,7 +16,8 @@
 //! in our group, and handles reading, writing, and serializing it with the discovery backend
 //! (etcd.) It has 4 main components:
 //!
-//! * CensusEntry: a given supervisors entry in the census.
+//! * CensusEntry: a given supervisors entry in the census (defined in `habitat_gossip`, as it is
+//!   shared as a rumor).
 //! * CensusEntryActor: a GenServer responsible for serializing our Census Entry to the backend
 //! * Census: The complete list of all supervisors, plus functions for analyzing the data, and
 //!   updating the census.
@@ -31,7 +32,6 @@
 //! every supervisors CensusEntry to elect a new leader in a reasonable amount of time.
 
 use std::collections::{HashMap, BTreeMap};
-use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, RwLock};
 use std::thread;
@@ -40,257 +40,14 @@ use std::time::Duration;
 use toml;
 use uuid::Uuid;
 
-use gossip::member::{MemberId, MemberList, Health};
-use gossip::lamport_clock::LamportClock;
+pub use habitat_gossip::census::{CensusEntry, CensusEntryId};
+
+use gossip::member::{MemberList, Health};
 use error::{Error, Result};
-use util;
 
 static LOGKEY: &'static str = "CN";
 pub static MIN_QUORUM: usize = 3;
 
-pub type CensusEntryId = Uuid;
-
-/// A CensusEntry. Manages all the data about a given member of the census.
-#[derive(Debug, Clone, RustcDecodable, RustcEncodable, Eq)]
-pub struct CensusEntry {
-    pub id: CensusEntryId,
-    pub member_id: MemberId,
-    pub hostname: String,
-    pub ip: String,
-    suitability: u64,
-    pub port: Option<String>,
-    pub exposes: Option<Vec<String>>,
-    pub leader: bool,
-    pub follower: bool,
-    pub data_init: bool,
-    pub vote: Option<String>,
-    pub election: Option<bool>,
-    pub needs_write: Option<bool>,
-    pub initialized: bool,
-    keep_me: bool,
-    pub service: String,
-    pub group: String,
-    pub alive: bool,
-    pub suspect: bool,
-    pub confirmed: bool,
-    pub detached: bool,
-    pub incarnation: LamportClock,
-}
-
-impl CensusEntry {
-    /// Create a new CensusEntry for this supervisor.
-    pub fn new<S>(service: S, group: S, member_id: MemberId) -> CensusEntry
-        where S: Into<String>
-    {
-        CensusEntry {
-            id: Uuid::new_v4(),
-            member_id: member_id,
-            hostname: util::sys::hostname(None).unwrap_or(String::from("unknown")),
-            ip: util::sys::ip(None).unwrap_or(String::from("127.0.0.1")),
-            suitability: 0,
-            port: None,
-            exposes: None,
-            leader: false,
-            follower: false,
-            data_init: false,
-            vote: None,
-            election: None,
-            needs_write: None,
-            initialized: false,
-            keep_me: true,
-            alive: true,
-            suspect: false,
-            confirmed: false,
-            detached: false,
-            service: service.into(),
-            group: group.into(),
-            incarnation: LamportClock::new(),
-        }
-    }
-
-    pub fn needs_write(&self) -> bool {
-        self.needs_write.is_some()
-    }
-
-    /// Set our suitability number. This is an arbitrary determination of our 'suitability' to a
-    /// task; most likely, being the leader in an election.
-    pub fn suitability(&mut self, suitability: u64) {
-        self.suitability = suitability;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set a port number; often used as the default for watches
-    pub fn port(&mut self, port: Option<String>) {
-        self.port = port;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set an array of port numbers we expose.
-    pub fn exposes(&mut self, exposes: Option<Vec<String>>) {
-        self.exposes = exposes;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set our status at the leader.
-    pub fn leader(&mut self, leader: bool) {
-        if self.leader != leader {
-            self.leader = leader;
-            self.incarnation.increment();
-            self.needs_write = Some(true);
-        }
-    }
-
-    /// Set our status as a follower.
-    pub fn follower(&mut self, follower: bool) {
-        if self.follower != follower {
-            self.follower = follower;
-            self.incarnation.increment();
-            self.needs_write = Some(true);
-        }
-    }
-
-    /// Set our application initialization status to true.
-    pub fn initialized(&mut self) {
-        self.initialized = true;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set our status on having initialzied data.
-    pub fn data_init(&mut self, data_init: bool) {
-        if self.data_init != data_init {
-            self.data_init = data_init;
-            self.incarnation.increment();
-            self.needs_write = Some(true);
-        }
-    }
-
-    /// Set our vote.
-    pub fn vote(&mut self, vote: Option<String>) {
-        self.vote = vote;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Are we in an election?
-    pub fn election(&mut self, election: Option<bool>) {
-        self.election = election;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set us to alive.
-    pub fn set_alive(&mut self) {
-        self.alive = true;
-        self.suspect = false;
-        self.confirmed = false;
-        self.detached = false;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set our suspectness.
-    pub fn set_suspect(&mut self) {
-        self.alive = false;
-        self.suspect = true;
-        self.confirmed = false;
-        self.detached = false;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set our confirmedness.
-    pub fn set_confirmed(&mut self) {
-        self.alive = false;
-        self.suspect = false;
-        self.confirmed = true;
-        self.detached = false;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Set our detachedness.
-    pub fn set_detached(&mut self) {
-        self.alive = false;
-        self.suspect = false;
-        self.confirmed = false;
-        self.detached = true;
-        self.incarnation.increment();
-        self.needs_write = Some(true);
-    }
-
-    /// Return the string we use for this CensusEntry when it is a candidate in an election.
-    pub fn candidate_string(&self) -> String {
-        format!("{}", self.id)
-    }
-
-    /// Return the service.group string
-    pub fn service_group(&self) -> String {
-        format!("{}.{}", self.service, self.group)
-    }
-
-    /// Update this entry from another entry. If the other side has a higher incarnation, take it
-    /// as the new you.
-    pub fn update_via(&mut self, other_ce: CensusEntry) -> bool {
-        if other_ce.incarnation > self.incarnation {
-            *self = other_ce;
-            true
-        } else {
-            false
-        }
-    }
-
-    pub fn written(&mut self) {
-        self.needs_write = None;
-    }
-}
-
-impl PartialEq for CensusEntry {
-    // We are equal, but we don't care about some fields.
-    fn eq(&self, other: &CensusEntry) -> bool {
-        if self.id != other.id {
-            false
-        } else if self.incarnation != other.incarnation {
-            false
-        } else if self.hostname != other.hostname {
-            false
-        } else if self.ip != other.ip {
-            false
-        } else if self.suitability != other.suitability {
-            false
-        } else if self.port != other.port {
-            false
-        } else if self.exposes != other.exposes {
-            false
-        } else if self.leader != other.leader {
-            false
-        } else if self.follower != other.follower {
-            false
-        } else if self.data_init != other.data_init {
-            false
-        } else if self.vote != other.vote {
-            false
-        } else if self.election != other.election {
-            false
-        } else if self.service != other.service {
-            false
-        } else if self.group != other.group {
-            false
-        } else {
-            true
-        }
-    }
-}
-
-impl fmt::Display for CensusEntry {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}: {}", self.service_group(), self.id)
-    }
-}
-
 /// A simple map of Census Entries; used for decoding toml data
 #[derive(Debug, RustcEncodable, RustcDecodable)]
 pub struct CensusMap {
@@ -626,6 +383,27 @@ impl Census {
         self.population.get(&Uuid::parse_str(&my_vote).unwrap())
     }
 
+    /// Every member of the census, ordered by ip, port, hostname and finally id, so the order
+    /// doesn't change from one render to the next.
+    pub fn members_ordered(&self) -> Vec<&CensusEntry> {
+        let mut members: Vec<&CensusEntry> = self.population.values().collect();
+        members.sort_by(|a, b| {
+            (&a.ip, &a.port, &a.hostname, a.id.simple().to_string())
+                .cmp(&(&b.ip, &b.port, &b.hostname, b.id.simple().to_string()))
+        });
+        members
+    }
+
+    /// The alive members of the census, in the same order as `members_ordered`.
+    pub fn alive_members(&self) -> Vec<&CensusEntry> {
+        self.members_ordered().into_iter().filter(|ce| ce.alive).collect()
+    }
+
+    /// The alive followers of the census, in the same order as `members_ordered`.
+    pub fn followers(&self) -> Vec<&CensusEntry> {
+        self.alive_members().into_iter().filter(|ce| ce.follower).collect()
+    }
+
     pub fn total_population(&self) -> usize {
         self.population.len()
     }
@@ -893,6 +671,34 @@ mod test {
             assert_eq!(census.minimum_quorum(), true);
         }
 
+        #[test]
+        fn members_ordered() {
+            let mut census = generate_census();
+            add_entries(&mut census, 5);
+            for (n, (_id, ce)) in census.population.iter_mut().enumerate() {
+                ce.ip = format!("10.0.0.{}", 5 - n);
+            }
+            let ips: Vec<String> =
+                census.members_ordered().iter().map(|ce| ce.ip.clone()).collect();
+            assert_eq!(ips,
+                       vec!["10.0.0.0", "10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4",
+                            "10.0.0.5"]);
+        }
+
+        #[test]
+        fn alive_members_and_followers() {
+            let mut census = generate_census();
+            add_entries(&mut census, 5);
+            confirm_entries(&mut census, 2);
+            assert_eq!(census.alive_members().len(), 4);
+            assert!(census.followers().is_empty());
+            for (_id, ce) in census.population.iter_mut() {
+                ce.follower(true);
+            }
+            assert_eq!(census.followers().len(), 4);
+            assert!(census.followers().iter().all(|ce| ce.alive));
+        }
+
         #[test]
         fn has_leader() {
             let mut census = generate_census();
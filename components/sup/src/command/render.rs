@@ -0,0 +1,354 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Renders a package's configuration files and hooks without starting it. Actually the `render`
+//! command.
+//!
+//! # Examples
+//!
+//! ```bash
+//! $ hab-sup render core/redis --user-toml ./user.toml --census ./census.toml
+//! ```
+//!
+//! Will print every rendered configuration file and hook. With `--diff`, each is instead compared
+//! to the file the running service currently has.
+//!
+//! The census file describes the other members of the ring, one `[[member]]` table each:
+//!
+//! ```toml
+//! [[member]]
+//! service = "redis"
+//! group = "default"
+//! ip = "10.0.0.2"
+//! port = "6379"
+//! leader = true
+//!
+//! [member.cfg]
+//! port = "6379"
+//! ```
+//!
+//! `group` defaults to `default`, `alive` to true, and `leader` and `follower` to false.
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use toml;
+
+use census::{Census, CensusEntry, CensusList};
+use config::Config;
+use error::{Error, Result};
+use gossip::member::MemberId;
+use package::Package;
+use service_config::ServiceConfig;
+
+static LOGKEY: &'static str = "CR";
+
+/// Render the configuration files and hooks for a package, printing them or diffing them against
+/// the service's current files.
+///
+/// # Failures
+///
+/// * If the package cannot be found
+/// * If the census file, user or gossip config cannot be found, read or parsed
+/// * If the gossip config doesn't match the package's config schema
+/// * If a configuration file or hook fails to render
+pub fn package(config: &Config) -> Result<()> {
+    let package = try!(Package::load(config.package(), None));
+    let census_list = try!(census_list(config, &package));
+    let service_config =
+        try!(ServiceConfig::from_files(config,
+                                       &package,
+                                       &census_list,
+                                       config.bind(),
+                                       config.user_toml().as_ref().map(|p| Path::new(p)),
+                                       config.gossip_toml().as_ref().map(|p| Path::new(p))));
+
+    let pi = &package.pkg_install;
+    let mut rendered = Vec::new();
+    for (name, contents) in try!(service_config.render(&package)) {
+        rendered.push((pi.svc_config_path().join(&name), contents));
+    }
+    let hooks = package.hooks();
+    for hook in vec![hooks.init_hook,
+                     hooks.file_updated_hook,
+                     hooks.reconfigure_hook,
+                     hooks.health_check_hook,
+                     hooks.run_hook]
+        .into_iter()
+        .filter_map(|h| h) {
+        let contents = try!(hook.render(&service_config));
+        rendered.push((hook.path, contents));
+    }
+
+    for (path, contents) in rendered {
+        if config.diff() {
+            print_diff(&path, &contents);
+        } else {
+            println!("==> {}", path.display());
+            println!("{}", contents);
+        }
+    }
+    Ok(())
+}
+
+/// Build the census the service would see; the local census holds only this member, plus
+/// whatever the census file describes.
+fn census_list(config: &Config, package: &Package) -> Result<CensusList> {
+    let me = CensusEntry::new(package.name.clone(),
+                              config.group().to_string(),
+                              MemberId::new_v4());
+    let mut census_list = CensusList::new(Census::new(me));
+    if let Some(ref path) = *config.census_file() {
+        for ce in try!(census_entries(Path::new(path))) {
+            census_list.insert_entry(ce);
+        }
+    }
+    Ok(census_list)
+}
+
+fn census_entries(path: &Path) -> Result<Vec<CensusEntry>> {
+    let mut file = try!(File::open(path));
+    let mut contents = String::new();
+    try!(file.read_to_string(&mut contents));
+    let mut toml_parser = toml::Parser::new(&contents);
+    let table = try!(toml_parser.parse()
+        .ok_or(sup_error!(Error::TomlParser(toml_parser.errors))));
+    let members = match table.get("member") {
+        Some(&toml::Value::Array(ref members)) => members.clone(),
+        Some(_) => return Err(invalid_census(path, "member must be an array of tables")),
+        None => Vec::new(),
+    };
+    let mut entries = Vec::new();
+    for member in members.iter() {
+        let member = match *member {
+            toml::Value::Table(ref member) => member,
+            _ => return Err(invalid_census(path, "member must be an array of tables")),
+        };
+        let service = match member.get("service").and_then(|s| s.as_str()) {
+            Some(service) => service.to_string(),
+            None => return Err(invalid_census(path, "every member needs a service")),
+        };
+        let group = member.get("group").and_then(|g| g.as_str()).unwrap_or("default").to_string();
+        let mut ce = CensusEntry::new(service, group, MemberId::new_v4());
+        if let Some(hostname) = member.get("hostname").and_then(|h| h.as_str()) {
+            ce.hostname = hostname.to_string();
+        }
+        if let Some(ip) = member.get("ip").and_then(|i| i.as_str()) {
+            ce.ip = ip.to_string();
+        }
+        if let Some(zone) = member.get("zone").and_then(|z| z.as_str()) {
+            ce.zone = Some(zone.to_string());
+        }
+        if let Some(port) = member.get("port") {
+            let port = match *port {
+                toml::Value::String(ref p) => p.clone(),
+                toml::Value::Integer(p) => p.to_string(),
+                _ => return Err(invalid_census(path, "port must be a string or integer")),
+            };
+            ce.port(Some(port));
+        }
+        if let Some(exposes) = member.get("exposes").and_then(|e| e.as_slice()) {
+            let exposes = exposes.iter().filter_map(|e| e.as_str()).map(|e| e.to_string());
+            ce.exposes(Some(exposes.collect()));
+        }
+        if let Some(exports) = member.get("exports").and_then(|e| e.as_slice()) {
+            let exports = exports.iter().filter_map(|e| e.as_str()).map(|e| e.to_string());
+            ce.exports(Some(exports.collect()));
+        }
+        if let Some(cfg) = member.get("cfg") {
+            let cfg = match *cfg {
+                toml::Value::Table(ref cfg) => cfg,
+                _ => return Err(invalid_census(path, "cfg must be a table")),
+            };
+            let cfg = cfg.iter()
+                .map(|(k, v)| {
+                    let v = match *v {
+                        toml::Value::String(ref v) => v.clone(),
+                        ref v => v.to_string(),
+                    };
+                    (k.clone(), v)
+                })
+                .collect();
+            ce.cfg(Some(cfg));
+        }
+        ce.leader(member.get("leader").and_then(|l| l.as_bool()).unwrap_or(false));
+        ce.follower(member.get("follower").and_then(|f| f.as_bool()).unwrap_or(false));
+        ce.alive = member.get("alive").and_then(|a| a.as_bool()).unwrap_or(true);
+        entries.push(ce);
+    }
+    Ok(entries)
+}
+
+fn invalid_census(path: &Path, reason: &str) -> ::error::SupError {
+    sup_error!(Error::InvalidCensusFile(format!("{}: {}", path.display(), reason)))
+}
+
+/// Print a line diff between the file on disk, if any, and what we rendered.
+fn print_diff(path: &Path, rendered: &str) {
+    let mut current = String::new();
+    if let Ok(mut file) = File::open(path) {
+        if let Err(e) = file.read_to_string(&mut current) {
+            outputln!("Failed to read {}: {}", path.display(), e);
+        }
+    }
+    if current == rendered {
+        println!("==> {} is unchanged", path.display());
+        return;
+    }
+    println!("--- {}", path.display());
+    println!("+++ {} (rendered)", path.display());
+    for line in diff_lines(&current, rendered) {
+        println!("{}", line);
+    }
+}
+
+/// A line-by-line diff of two texts from their longest common subsequence. Every line is
+/// prefixed with ` ` if unchanged, `-` if only in `old`, and `+` if only in `new`.
+fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                ::std::cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(format!(" {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| format!("-{}", l)));
+    lines.extend(new[j..].iter().map(|l| format!("+{}", l)));
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::prelude::*;
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+
+    use error::{Error, SupError};
+    use super::{census_entries, diff_lines};
+
+    fn census_file(dir: &TempDir, contents: &str) -> PathBuf {
+        let path = dir.path().join("census.toml");
+        let mut file = File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn census_file_members() {
+        let dir = TempDir::new("census").unwrap();
+        let path = census_file(&dir,
+                               r#"
+[[member]]
+service = "redis"
+ip = "10.0.0.2"
+port = 6379
+exports = ["port"]
+leader = true
+
+[member.cfg]
+port = 6379
+name = "one"
+
+[[member]]
+service = "redis"
+group = "staging"
+hostname = "two"
+port = "6380"
+alive = false
+"#);
+        let entries = census_entries(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].service, "redis");
+        assert_eq!(entries[0].group, "default");
+        assert_eq!(entries[0].ip, "10.0.0.2");
+        assert_eq!(entries[0].port, Some("6379".to_string()));
+        assert_eq!(entries[0].exports, Some(vec!["port".to_string()]));
+        assert!(entries[0].leader);
+        assert!(!entries[0].follower);
+        assert!(entries[0].alive);
+        let cfg = entries[0].cfg.as_ref().unwrap();
+        assert_eq!(cfg.get("port"), Some(&"6379".to_string()));
+        assert_eq!(cfg.get("name"), Some(&"one".to_string()));
+        assert_eq!(entries[1].group, "staging");
+        assert_eq!(entries[1].hostname, "two");
+        assert_eq!(entries[1].port, Some("6380".to_string()));
+        assert!(!entries[1].leader);
+        assert!(!entries[1].alive);
+    }
+
+    #[test]
+    fn census_file_without_members() {
+        let dir = TempDir::new("census").unwrap();
+        let path = census_file(&dir, "");
+        assert!(census_entries(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn census_file_member_needs_service() {
+        let dir = TempDir::new("census").unwrap();
+        let path = census_file(&dir, "[[member]]\nip = \"10.0.0.2\"\n");
+        match census_entries(&path) {
+            Err(SupError { err: Error::InvalidCensusFile(_), .. }) => (),
+            Err(e) => panic!("Unexpected error {}", e),
+            Ok(_) => panic!("A member without a service should not parse"),
+        }
+    }
+
+    #[test]
+    fn census_file_bad_port() {
+        let dir = TempDir::new("census").unwrap();
+        let path = census_file(&dir, "[[member]]\nservice = \"redis\"\nport = true\n");
+        assert!(census_entries(&path).is_err());
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        assert_eq!(diff_lines("a\nb\n", "a\nb\n"), vec![" a", " b"]);
+    }
+
+    #[test]
+    fn diff_changes() {
+        assert_eq!(diff_lines("a\nb\nc\n", "a\nx\nc\nd\n"),
+                   vec![" a", "-b", "+x", " c", "+d"]);
+    }
+
+    #[test]
+    fn diff_from_nothing() {
+        assert_eq!(diff_lines("", "a\n"), vec!["+a"]);
+    }
+}
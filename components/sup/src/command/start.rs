@@ -64,6 +64,7 @@ use common::command::ProgressBar;
 use common::command::package::install;
 use depot_client::Client;
 use hcore::crypto::default_cache_key_path;
+use hcore::crypto::artifact::SignaturePolicy;
 use hcore::fs::{cache_artifact_path, FS_ROOT_PATH};
 use hcore::package::PackageIdent;
 
@@ -105,11 +106,12 @@ pub fn package(config: &Config) -> Result<()> {
                         if &latest_ident > package.ident() {
                             outputln!("Downloading latest version from remote: {}", latest_ident);
                             let mut progress = ProgressBar::default();
-                            let archive = try!(depot_client.fetch_package(latest_ident,
+                            let mut archive = try!(depot_client.fetch_package(latest_ident,
                                                &cache_artifact_path(None),
                                                Some(&mut progress)));
-                            try!(archive.verify(&default_cache_key_path(None)));
-                            try!(archive.unpack(None));
+                            try!(archive.verify_and_unpack(None,
+                                                           &default_cache_key_path(None),
+                                                           &SignaturePolicy::Origin));
                         } else {
                             outputln!("Already running latest.");
                         };
@@ -25,6 +25,7 @@ use std::str::FromStr;
 use hcore::package::PackageIdent;
 
 use error::{Error, SupError};
+use gossip::detector::DetectorConfig;
 use gossip::server::GOSSIP_DEFAULT_PORT;
 use topology::Topology;
 
@@ -34,6 +35,7 @@ static LOGKEY: &'static str = "CFG";
 /// An enum with the various CLI commands. Used to keep track of what command was called.
 pub enum Command {
     Config,
+    Render,
     Start,
     ShellBash,
     ShellSh,
@@ -65,6 +67,7 @@ impl FromStr for Command {
     fn from_str(s: &str) -> Result<Command, SupError> {
         match s {
             "config" => Ok(Command::Config),
+            "render" => Ok(Command::Render),
             "bash" => Ok(Command::ShellBash),
             "sh" => Ok(Command::ShellSh),
             "start" => Ok(Command::Start),
@@ -104,12 +107,20 @@ pub struct Config {
     outfile: Option<String>,
     gossip_peer: Vec<String>,
     gossip_permanent: bool,
+    gossip_detector: DetectorConfig,
     update_strategy: UpdateStrategy,
     service_group: String,
     file_path: String,
     version_number: u64,
     organization: Option<String>,
     ring: Option<String>,
+    zone: Option<String>,
+    config_signers: Vec<String>,
+    user_toml: Option<String>,
+    gossip_toml: Option<String>,
+    census_file: Option<String>,
+    diff: bool,
+    config_dir: Option<String>,
 }
 
 impl Config {
@@ -326,6 +337,35 @@ impl Config {
         self
     }
 
+    /// Return the failure detector timeouts
+    pub fn gossip_detector(&self) -> &DetectorConfig {
+        &self.gossip_detector
+    }
+
+    /// Set how often we probe a member, in milliseconds
+    pub fn set_gossip_probe_interval(&mut self, ms: u64) -> &mut Config {
+        self.gossip_detector.probe_interval = ms as i64;
+        self
+    }
+
+    /// Set how long we wait for a probe to be acknowledged, in milliseconds
+    pub fn set_gossip_probe_timeout(&mut self, ms: u64) -> &mut Config {
+        self.gossip_detector.probe_timeout = ms as i64;
+        self
+    }
+
+    /// Set the shortest time a member can be suspect before being confirmed, in milliseconds
+    pub fn set_gossip_suspicion_timeout(&mut self, ms: u64) -> &mut Config {
+        self.gossip_detector.suspicion_min = ms as i64;
+        self
+    }
+
+    /// Set the longest time a member can be suspect before being confirmed, in milliseconds
+    pub fn set_gossip_suspicion_max_timeout(&mut self, ms: u64) -> &mut Config {
+        self.gossip_detector.suspicion_max = ms as i64;
+        self
+    }
+
     pub fn gossip_peer(&self) -> &[String] {
         &self.gossip_peer
     }
@@ -401,6 +441,83 @@ impl Config {
     pub fn ring(&self) -> &Option<String> {
         &self.ring
     }
+
+    /// Set the zone (datacenter, region, etc.) this supervisor runs in
+    pub fn set_zone(&mut self, zone: String) -> &mut Config {
+        self.zone = Some(zone);
+        self
+    }
+
+    /// Return the zone
+    pub fn zone(&self) -> &Option<String> {
+        &self.zone
+    }
+
+    /// Set the sig keys allowed to sign gossiped configuration and files
+    pub fn set_config_signers(&mut self, signers: Vec<String>) -> &mut Config {
+        self.config_signers = signers;
+        self
+    }
+
+    /// Return the sig keys allowed to sign gossiped configuration and files
+    pub fn config_signers(&self) -> &[String] {
+        &self.config_signers
+    }
+
+    /// Set the user.toml to render with, in place of the service's own
+    pub fn set_user_toml(&mut self, user_toml: String) -> &mut Config {
+        self.user_toml = Some(user_toml);
+        self
+    }
+
+    /// Return the user.toml to render with
+    pub fn user_toml(&self) -> &Option<String> {
+        &self.user_toml
+    }
+
+    /// Set the gossiped config to render with, in place of the service's own
+    pub fn set_gossip_toml(&mut self, gossip_toml: String) -> &mut Config {
+        self.gossip_toml = Some(gossip_toml);
+        self
+    }
+
+    /// Return the gossiped config to render with
+    pub fn gossip_toml(&self) -> &Option<String> {
+        &self.gossip_toml
+    }
+
+    /// Set the file describing the census to render with
+    pub fn set_census_file(&mut self, census_file: String) -> &mut Config {
+        self.census_file = Some(census_file);
+        self
+    }
+
+    /// Return the file describing the census to render with
+    pub fn census_file(&self) -> &Option<String> {
+        &self.census_file
+    }
+
+    /// Set whether to diff rendered files against the ones on disk, rather than print them
+    pub fn set_diff(&mut self, diff: bool) -> &mut Config {
+        self.diff = diff;
+        self
+    }
+
+    /// Return whether to diff rendered files against the ones on disk
+    pub fn diff(&self) -> bool {
+        self.diff
+    }
+
+    /// Set the directory of files, one config key each, that override the service's config
+    pub fn set_config_dir(&mut self, config_dir: String) -> &mut Config {
+        self.config_dir = Some(config_dir);
+        self
+    }
+
+    /// Return the directory of config override files
+    pub fn config_dir(&self) -> &Option<String> {
+        &self.config_dir
+    }
 }
 
 #[cfg(test)]
@@ -442,6 +559,19 @@ mod tests {
         assert_eq!(c.url().as_ref().unwrap(), "http://foolio.com");
     }
 
+    #[test]
+    fn gossip_detector() {
+        let mut c = Config::new();
+        c.set_gossip_probe_interval(1000);
+        c.set_gossip_probe_timeout(2000);
+        c.set_gossip_suspicion_timeout(3000);
+        c.set_gossip_suspicion_max_timeout(4000);
+        assert_eq!(c.gossip_detector().probe_interval, 1000);
+        assert_eq!(c.gossip_detector().probe_timeout, 2000);
+        assert_eq!(c.gossip_detector().suspicion_min, 3000);
+        assert_eq!(c.gossip_detector().suspicion_max, 4000);
+    }
+
     #[test]
     fn topology() {
         let mut c = Config::new();
@@ -0,0 +1,426 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schemas for service configuration.
+//!
+//! A package may ship a `schema.toml` next to its `default.toml`, describing the shape of its
+//! `cfg` tree. The merged configuration (`default.toml`, `user.toml`, gossiped configuration and
+//! the environment) is validated against it before it is rendered, so a typo in an applied
+//! configuration is rejected instead of breaking the service.
+//!
+//! A schema describes a table:
+//!
+//! ```toml
+//! # Reject keys the schema doesn't describe
+//! strict = true
+//!
+//! [keys.port]
+//! type = "integer"
+//! required = true
+//! min = 1
+//! max = 65535
+//!
+//! [keys.log_level]
+//! type = "string"
+//! enum = ["debug", "info", "warn", "error"]
+//!
+//! [keys.tls]
+//! type = "table"
+//! [keys.tls.keys.enabled]
+//! type = "boolean"
+//! ```
+//!
+//! Types are `string`, `integer`, `float`, `boolean`, `datetime`, `array` and `table`; a key
+//! without a type may hold anything. Tables describe their keys with `keys`, and arrays their
+//! elements with `items`. `min` and `max` bound numbers.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use toml;
+
+use error::{Error, Result};
+
+static LOGKEY: &'static str = "CS";
+
+/// The name of the schema file in a package.
+pub const SCHEMA_FILE: &'static str = "schema.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Datetime,
+    Array,
+    Table,
+}
+
+impl ValueType {
+    fn from_str(value_type: &str) -> Option<ValueType> {
+        match value_type {
+            "string" => Some(ValueType::String),
+            "integer" => Some(ValueType::Integer),
+            "float" => Some(ValueType::Float),
+            "boolean" => Some(ValueType::Boolean),
+            "datetime" => Some(ValueType::Datetime),
+            "array" => Some(ValueType::Array),
+            "table" => Some(ValueType::Table),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match *self {
+            ValueType::String => "string",
+            ValueType::Integer => "integer",
+            ValueType::Float => "float",
+            ValueType::Boolean => "boolean",
+            ValueType::Datetime => "datetime",
+            ValueType::Array => "array",
+            ValueType::Table => "table",
+        }
+    }
+
+    /// Returns true if the value is of this type. Integers are acceptable floats.
+    fn matches(&self, value: &toml::Value) -> bool {
+        match (*self, value) {
+            (ValueType::String, &toml::Value::String(_)) => true,
+            (ValueType::Integer, &toml::Value::Integer(_)) => true,
+            (ValueType::Float, &toml::Value::Float(_)) => true,
+            (ValueType::Float, &toml::Value::Integer(_)) => true,
+            (ValueType::Boolean, &toml::Value::Boolean(_)) => true,
+            (ValueType::Datetime, &toml::Value::Datetime(_)) => true,
+            (ValueType::Array, &toml::Value::Array(_)) => true,
+            (ValueType::Table, &toml::Value::Table(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The rules for a single value.
+#[derive(Debug)]
+struct Spec {
+    value_type: Option<ValueType>,
+    required: bool,
+    one_of: Option<Vec<toml::Value>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    items: Option<Box<Spec>>,
+    keys: BTreeMap<String, Spec>,
+    strict: bool,
+}
+
+impl Spec {
+    fn parse(path: &str, table: &toml::Table) -> Result<Spec> {
+        let value_type = match table.get("type") {
+            Some(&toml::Value::String(ref t)) => {
+                match ValueType::from_str(t) {
+                    Some(value_type) => Some(value_type),
+                    None => return Err(invalid_schema(path, &format!("unknown type {}", t))),
+                }
+            }
+            Some(_) => return Err(invalid_schema(path, "type must be a string")),
+            None => None,
+        };
+        let one_of = match table.get("enum") {
+            Some(&toml::Value::Array(ref values)) => Some(values.clone()),
+            Some(_) => return Err(invalid_schema(path, "enum must be an array")),
+            None => None,
+        };
+        let items = match table.get("items") {
+            Some(&toml::Value::Table(ref items)) => {
+                Some(Box::new(try!(Spec::parse(&format!("{}[]", path), items))))
+            }
+            Some(_) => return Err(invalid_schema(path, "items must be a table")),
+            None => None,
+        };
+        let mut keys = BTreeMap::new();
+        match table.get("keys") {
+            Some(&toml::Value::Table(ref specs)) => {
+                for (key, spec) in specs.iter() {
+                    let key_path = format!("{}.{}", path, key);
+                    match *spec {
+                        toml::Value::Table(ref spec) => {
+                            keys.insert(key.clone(), try!(Spec::parse(&key_path, spec)));
+                        }
+                        _ => return Err(invalid_schema(&key_path, "must be a table")),
+                    }
+                }
+            }
+            Some(_) => return Err(invalid_schema(path, "keys must be a table")),
+            None => {}
+        }
+        Ok(Spec {
+            value_type: value_type,
+            required: try!(flag(path, table, "required")),
+            one_of: one_of,
+            min: try!(bound(path, table, "min")),
+            max: try!(bound(path, table, "max")),
+            items: items,
+            keys: keys,
+            strict: try!(flag(path, table, "strict")),
+        })
+    }
+
+    fn check(&self, path: &str, value: &toml::Value, errors: &mut Vec<String>) {
+        if let Some(value_type) = self.value_type {
+            if !value_type.matches(value) {
+                errors.push(format!("{}: expected {}, found {}",
+                                    path,
+                                    value_type.as_str(),
+                                    value.type_str()));
+                return;
+            }
+        }
+        if let Some(ref one_of) = self.one_of {
+            if !one_of.contains(value) {
+                let allowed: Vec<String> = one_of.iter().map(|v| v.to_string()).collect();
+                errors.push(format!("{}: {} is not one of {}", path, value, allowed.join(", ")));
+            }
+        }
+        let number = match *value {
+            toml::Value::Integer(i) => Some(i as f64),
+            toml::Value::Float(f) => Some(f),
+            _ => None,
+        };
+        if let Some(number) = number {
+            if let Some(min) = self.min {
+                if number < min {
+                    errors.push(format!("{}: {} is less than the minimum of {}", path, value, min));
+                }
+            }
+            if let Some(max) = self.max {
+                if number > max {
+                    errors.push(format!("{}: {} is more than the maximum of {}", path, value, max));
+                }
+            }
+        }
+        match *value {
+            toml::Value::Array(ref values) => {
+                if let Some(ref items) = self.items {
+                    for (i, item) in values.iter().enumerate() {
+                        items.check(&format!("{}[{}]", path, i), item, errors);
+                    }
+                }
+            }
+            toml::Value::Table(ref table) => {
+                for (key, spec) in self.keys.iter() {
+                    let key_path = format!("{}.{}", path, key);
+                    match table.get(key) {
+                        Some(value) => spec.check(&key_path, value, errors),
+                        None if spec.required => errors.push(format!("{}: is required", key_path)),
+                        None => {}
+                    }
+                }
+                if self.strict {
+                    for key in table.keys() {
+                        if !self.keys.contains_key(key) {
+                            errors.push(format!("{}.{}: is not a known key", path, key));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A package's configuration schema.
+#[derive(Debug)]
+pub struct ConfigSchema {
+    root: Spec,
+}
+
+impl ConfigSchema {
+    /// Loads the schema shipped in a package, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// * If the schema cannot be read
+    /// * If the schema is not valid
+    pub fn load(pkg_path: &Path) -> Result<Option<ConfigSchema>> {
+        let mut file = match File::open(pkg_path.join(SCHEMA_FILE)) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                debug!("No {} in {}", SCHEMA_FILE, pkg_path.display());
+                return Ok(None);
+            }
+            Err(e) => return Err(sup_error!(Error::Io(e))),
+        };
+        let mut schema = String::new();
+        try!(file.read_to_string(&mut schema));
+        Ok(Some(try!(ConfigSchema::from_str(&schema))))
+    }
+
+    /// Parses a schema.
+    ///
+    /// # Errors
+    ///
+    /// * If the schema is not valid toml
+    /// * If the schema does not describe a table
+    pub fn from_str(schema: &str) -> Result<ConfigSchema> {
+        let mut toml_parser = toml::Parser::new(schema);
+        let table = try!(toml_parser.parse()
+            .ok_or(sup_error!(Error::TomlParser(toml_parser.errors))));
+        let mut root = try!(Spec::parse("cfg", &table));
+        match root.value_type {
+            None | Some(ValueType::Table) => root.value_type = Some(ValueType::Table),
+            Some(_) => return Err(invalid_schema("cfg", "the top level must be a table")),
+        }
+        Ok(ConfigSchema { root: root })
+    }
+
+    /// Validates a `cfg` tree against the schema.
+    ///
+    /// # Errors
+    ///
+    /// * If the tree does not conform to the schema; the error lists every problem found
+    pub fn validate(&self, cfg: &toml::Value) -> Result<()> {
+        let mut errors = Vec::new();
+        self.root.check("cfg", cfg, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(sup_error!(Error::InvalidConfig(errors)))
+        }
+    }
+}
+
+fn invalid_schema(path: &str, reason: &str) -> ::error::SupError {
+    sup_error!(Error::InvalidConfigSchema(format!("{}: {}", path, reason)))
+}
+
+fn flag(path: &str, table: &toml::Table, name: &str) -> Result<bool> {
+    match table.get(name) {
+        Some(&toml::Value::Boolean(b)) => Ok(b),
+        Some(_) => Err(invalid_schema(path, &format!("{} must be a boolean", name))),
+        None => Ok(false),
+    }
+}
+
+fn bound(path: &str, table: &toml::Table, name: &str) -> Result<Option<f64>> {
+    match table.get(name) {
+        Some(&toml::Value::Integer(i)) => Ok(Some(i as f64)),
+        Some(&toml::Value::Float(f)) => Ok(Some(f)),
+        Some(_) => Err(invalid_schema(path, &format!("{} must be a number", name))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use toml;
+
+    use error::{Error, SupError};
+    use super::ConfigSchema;
+
+    const SCHEMA: &'static str = r#"
+strict = true
+
+[keys.port]
+type = "integer"
+required = true
+min = 1
+max = 65535
+
+[keys.log_level]
+type = "string"
+enum = ["debug", "info"]
+
+[keys.hosts]
+type = "array"
+items = { type = "string" }
+
+[keys.tls]
+type = "table"
+[keys.tls.keys.enabled]
+type = "boolean"
+"#;
+
+    fn cfg(toml: &str) -> toml::Value {
+        toml::Value::Table(toml::Parser::new(toml).parse().unwrap())
+    }
+
+    fn errors_for(toml: &str) -> Vec<String> {
+        let schema = ConfigSchema::from_str(SCHEMA).unwrap();
+        match schema.validate(&cfg(toml)) {
+            Ok(()) => Vec::new(),
+            Err(SupError { err: Error::InvalidConfig(errors), .. }) => errors,
+            Err(e) => panic!("Unexpected error {}", e),
+        }
+    }
+
+    #[test]
+    fn valid_config() {
+        let errors = errors_for(r#"
+port = 80
+log_level = "info"
+hosts = ["a", "b"]
+[tls]
+enabled = true
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn wrong_type() {
+        assert_eq!(errors_for("port = \"80\""),
+                   vec!["cfg.port: expected integer, found string"]);
+    }
+
+    #[test]
+    fn missing_required_key() {
+        assert_eq!(errors_for("log_level = \"info\""),
+                   vec!["cfg.port: is required"]);
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(errors_for("port = 0"),
+                   vec!["cfg.port: 0 is less than the minimum of 1"]);
+    }
+
+    #[test]
+    fn not_in_enum() {
+        assert_eq!(errors_for("port = 80\nlog_level = \"loud\""),
+                   vec!["cfg.log_level: \"loud\" is not one of \"debug\", \"info\""]);
+    }
+
+    #[test]
+    fn nested_values() {
+        assert_eq!(errors_for("port = 80\nhosts = [\"a\", 1]\n[tls]\nenabled = \"yes\""),
+                   vec!["cfg.hosts[1]: expected string, found integer",
+                        "cfg.tls.enabled: expected boolean, found string"]);
+    }
+
+    #[test]
+    fn strict_rejects_unknown_keys() {
+        assert_eq!(errors_for("port = 80\nprot = 81"),
+                   vec!["cfg.prot: is not a known key"]);
+    }
+
+    #[test]
+    fn bad_schema() {
+        match ConfigSchema::from_str("[keys.port]\ntype = \"number\"") {
+            Err(SupError { err: Error::InvalidConfigSchema(reason), .. }) => {
+                assert_eq!(reason, "cfg.port: unknown type number")
+            }
+            other => panic!("Expected an invalid schema, got {:?}", other),
+        }
+    }
+}
@@ -56,6 +56,7 @@ use uuid;
 use wonder::actor;
 
 use common;
+use habitat_gossip;
 use depot_client;
 use hcore::{self, package};
 use output::StructuredOutput;
@@ -98,6 +99,7 @@ impl SupError {
 #[derive(Debug)]
 pub enum Error {
     ActorError(actor::ActorError),
+    BindContract(String),
     CommandNotImplemented,
     DbInvalidPath,
     DepotClient(depot_client::Error),
@@ -105,6 +107,7 @@ pub enum Error {
     FileNotFound(String),
     HabitatCommon(common::Error),
     HabitatCore(hcore::Error),
+    HabitatGossip(habitat_gossip::Error),
     HandlebarsTemplateFileError(handlebars::TemplateFileError),
     HandlebarsRenderError(handlebars::RenderError),
     HealthCheck(String),
@@ -113,6 +116,9 @@ pub enum Error {
     /// TODO: once discovery/etcd.rs is purged, this error can be removed
     HyperError(hyper::error::Error),
     InvalidBinding(String),
+    InvalidCensusFile(String),
+    InvalidConfig(Vec<String>),
+    InvalidConfigSchema(String),
     InvalidKeyParameter(String),
     InvalidPidFile,
     InvalidServiceGroupString(String),
@@ -122,6 +128,7 @@ pub enum Error {
     JsonEncode(json::EncoderError),
     KeyNotFound(String),
     MetaFileIO(io::Error),
+    MissingBinds(Vec<String>),
     NetParseError(net::AddrParseError),
     NoRunFile,
     NulError(ffi::NulError),
@@ -135,6 +142,7 @@ pub enum Error {
     TomlEncode(toml::Error),
     TomlParser(Vec<toml::ParserError>),
     TryRecvError(mpsc::TryRecvError),
+    UnknownBind(String),
     UnknownTopology(String),
     UnpackFailed,
     UuidParseError(uuid::ParseError),
@@ -149,11 +157,13 @@ impl fmt::Display for SupError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let content = match self.err {
             Error::ActorError(ref err) => format!("Actor returned error: {:?}", err),
+            Error::BindContract(ref e) => format!("Bound service does not meet contract: {}", e),
             Error::ExecCommandNotFound(ref c) => {
                 format!("`{}' was not found on the filesystem or in PATH", c)
             }
             Error::HabitatCommon(ref err) => format!("{}", err),
             Error::HabitatCore(ref err) => format!("{}", err),
+            Error::HabitatGossip(ref err) => format!("{}", err),
             Error::HandlebarsTemplateFileError(ref err) => format!("{:?}", err),
             Error::HandlebarsRenderError(ref err) => format!("{}", err),
             Error::CommandNotImplemented => format!("Command is not yet implemented!"),
@@ -169,6 +179,11 @@ impl fmt::Display for SupError {
             Error::InvalidBinding(ref binding) => {
                 format!("Invalid binding - must be ':' delimited: {}", binding)
             }
+            Error::InvalidCensusFile(ref e) => format!("Invalid census file: {}", e),
+            Error::InvalidConfig(ref errors) => {
+                format!("Invalid configuration: {}", errors.join("; "))
+            }
+            Error::InvalidConfigSchema(ref e) => format!("Invalid configuration schema: {}", e),
             Error::InvalidKeyParameter(ref e) => {
                 format!("Invalid parameter for key generation: {:?}", e)
             }
@@ -182,6 +197,10 @@ impl fmt::Display for SupError {
             Error::JsonEncode(ref e) => format!("JSON encoding error: {}", e),
             Error::KeyNotFound(ref e) => format!("Key not found in key cache: {}", e),
             Error::MetaFileIO(ref e) => format!("IO error while accessing MetaFile: {:?}", e),
+            Error::MissingBinds(ref b) => {
+                format!("Missing required binds: {}; specify them with --bind",
+                        b.join(", "))
+            }
             Error::NetParseError(ref e) => format!("Can't parse ip:port: {}", e),
             Error::NoRunFile => {
                 format!("No run file is present for this package; specify a run hook or \
@@ -217,6 +236,7 @@ impl fmt::Display for SupError {
                 format!("Failed to parse toml:\n{}", toml_parser_string(errs))
             }
             Error::TryRecvError(ref err) => format!("{}", err),
+            Error::UnknownBind(ref b) => format!("The package does not declare a bind named {}", b),
             Error::UnknownTopology(ref t) => format!("Unknown topology {}!", t),
             Error::UnpackFailed => format!("Failed to unpack a package"),
             Error::UuidParseError(ref e) => format!("Uuid Parse Error: {:?}", e),
@@ -238,11 +258,13 @@ impl error::Error for SupError {
     fn description(&self) -> &str {
         match self.err {
             Error::ActorError(_) => "A running actor responded with an error",
+            Error::BindContract(_) => "A bound service does not export the values needed",
             Error::ExecCommandNotFound(_) => "Exec command was not found on filesystem or in PATH",
             Error::HandlebarsRenderError(ref err) => err.description(),
             Error::HandlebarsTemplateFileError(ref err) => err.description(),
             Error::HabitatCommon(ref err) => err.description(),
             Error::HabitatCore(ref err) => err.description(),
+            Error::HabitatGossip(ref err) => err.description(),
             Error::CommandNotImplemented => "Command is not yet implemented!",
             Error::DbInvalidPath => "A bad filepath was provided for an internal datastore",
             Error::DepotClient(ref err) => err.description(),
@@ -252,6 +274,9 @@ impl error::Error for SupError {
             Error::HTTP(_) => "Received an HTTP error",
             Error::HyperError(ref err) => err.description(),
             Error::InvalidBinding(_) => "Invalid binding parameter",
+            Error::InvalidCensusFile(_) => "Census file is not valid",
+            Error::InvalidConfig(_) => "Configuration does not match the package's schema",
+            Error::InvalidConfigSchema(_) => "Invalid configuration schema",
             Error::InvalidKeyParameter(_) => "Key parameter error",
             Error::InvalidPidFile => "Invalid child process PID file",
             Error::InvalidServiceGroupString(_) => {
@@ -263,6 +288,7 @@ impl error::Error for SupError {
             Error::JsonEncode(_) => "JSON encoding error",
             Error::KeyNotFound(_) => "Key not found in key cache",
             Error::MetaFileIO(_) => "MetaFile could not be read or written to",
+            Error::MissingBinds(_) => "Required binds were not specified",
             Error::NetParseError(_) => "Can't parse IP:port",
             Error::NoRunFile => {
                 "No run file is present for this package; specify a run hook or $pkg_svc_run \
@@ -283,6 +309,7 @@ impl error::Error for SupError {
             Error::TomlEncode(_) => "Failed to encode toml!",
             Error::TomlParser(_) => "Failed to parse toml!",
             Error::TryRecvError(_) => "A channel failed to recieve a response",
+            Error::UnknownBind(_) => "The package does not declare a bind with that name",
             Error::UnknownTopology(_) => "Unknown topology",
             Error::UnpackFailed => "Failed to unpack a package",
             Error::UuidParseError(_) => "Uuid Parse Error",
@@ -323,6 +350,12 @@ impl From<handlebars::TemplateFileError> for SupError {
     }
 }
 
+impl From<habitat_gossip::Error> for SupError {
+    fn from(err: habitat_gossip::Error) -> SupError {
+        sup_error!(Error::HabitatGossip(err))
+    }
+}
+
 impl From<hcore::Error> for SupError {
     fn from(err: hcore::Error) -> SupError {
         sup_error!(Error::HabitatCore(err))
@@ -16,8 +16,17 @@
 //!
 //! This module tracks the status of all our outbound connections, and takes care of marking them
 //! as suspect or confirmed.
+//!
+//! The timeouts used are modeled on the Lifeguard extensions to SWIM:
+//!
+//! * Local Health Awareness: we keep a score of how healthy *we* appear to be. Missed probes and
+//! having to refute suspicion about ourselves raise the score; successful probes lower it. Probe
+//! intervals and ack timeouts are scaled by the score, so a supervisor that is itself lagging
+//! slows down rather than accusing its peers.
+//! * Dynamic Suspicion: a suspect member starts with a long suspicion timeout, which shrinks
+//! towards the minimum as independent members confirm the suspicion via gossip.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use rustc_serialize::{Encodable, Encoder};
 use time::{SteadyTime, Duration};
@@ -27,9 +36,57 @@ use gossip::member::MemberId;
 /// Failure time in milliseconds
 pub static REQUEST_FAILURE_TIME: i64 = 5000;
 
-/// How long before we declare this member all the way gone
+/// How long before we declare this member all the way gone, once enough members agree
 pub static REQUEST_CONFIRM_TIME: i64 = 10000;
 
+/// How often we send an outbound probe, in milliseconds
+pub static PROBE_INTERVAL: i64 = 200;
+
+/// How long we wait to confirm a suspect when nobody else agrees with us, in milliseconds
+pub static SUSPICION_MAX_TIME: i64 = 60000;
+
+/// How many independent confirmations shrink the suspicion timeout all the way to the minimum
+pub static SUSPICION_CONFIRMATIONS: usize = 3;
+
+/// The highest our local health score can climb
+pub static MAX_LOCAL_HEALTH: u32 = 8;
+
+/// How much longer we wait on probes to members in another zone
+pub static CROSS_ZONE_FACTOR: i64 = 3;
+
+/// The timeouts used by the failure detector.
+#[derive(Clone, Debug, PartialEq, Eq, RustcEncodable)]
+pub struct DetectorConfig {
+    /// How often we send an outbound probe, in milliseconds
+    pub probe_interval: i64,
+    /// How long we wait for an Ack (or a PingReq Ack) before moving on, in milliseconds
+    pub probe_timeout: i64,
+    /// The shortest a suspicion can last, in milliseconds
+    pub suspicion_min: i64,
+    /// The longest a suspicion can last, in milliseconds
+    pub suspicion_max: i64,
+    /// How many confirmations bring the suspicion timeout down to `suspicion_min`
+    pub suspicion_confirmations: usize,
+    /// The maximum local health score
+    pub max_local_health: u32,
+    /// The multiplier applied to probe timeouts for members in another zone
+    pub cross_zone_factor: i64,
+}
+
+impl Default for DetectorConfig {
+    fn default() -> DetectorConfig {
+        DetectorConfig {
+            probe_interval: PROBE_INTERVAL,
+            probe_timeout: REQUEST_FAILURE_TIME,
+            suspicion_min: REQUEST_CONFIRM_TIME,
+            suspicion_max: SUSPICION_MAX_TIME,
+            suspicion_confirmations: SUSPICION_CONFIRMATIONS,
+            max_local_health: MAX_LOCAL_HEALTH,
+            cross_zone_factor: CROSS_ZONE_FACTOR,
+        }
+    }
+}
+
 /// The status of a request
 #[derive(Debug, RustcEncodable, RustcDecodable, PartialEq, Eq)]
 pub enum Status {
@@ -44,14 +101,29 @@ pub enum Status {
 pub struct RequestState {
     pub status: Status,
     pub timeout: SteadyTime,
+    /// Whether this request crosses zones, and gets the longer timeouts
+    pub cross_zone: bool,
+    /// When we first suspected this member; only meaningful while Failed
+    pub suspected_at: SteadyTime,
+    /// The members who have independently told us this member is suspect
+    pub confirmations: HashSet<MemberId>,
 }
 
 impl RequestState {
     /// Create a new RequestState. Starts out in 'Running'.
     pub fn new() -> RequestState {
+        RequestState::with_timeout(REQUEST_FAILURE_TIME)
+    }
+
+    /// Create a new RequestState which times out after `timeout` milliseconds.
+    pub fn with_timeout(timeout: i64) -> RequestState {
+        let now = SteadyTime::now();
         RequestState {
             status: Status::Running,
-            timeout: SteadyTime::now() + Duration::milliseconds(REQUEST_FAILURE_TIME),
+            timeout: now + Duration::milliseconds(timeout),
+            cross_zone: false,
+            suspected_at: now,
+            confirmations: HashSet::new(),
         }
     }
 }
@@ -59,9 +131,11 @@ impl RequestState {
 // We have to hand-write the serialization due to our use of SteadyTime.
 impl Encodable for RequestState {
     fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
-        try!(s.emit_struct("RequestState", 2, |s| {
+        try!(s.emit_struct("RequestState", 4, |s| {
             try!(s.emit_struct_field("status", 0, |s| self.status.encode(s)));
             try!(s.emit_struct_field("timeout", 1, |s| (format!("{}", self.timeout)).encode(s)));
+            try!(s.emit_struct_field("cross_zone", 2, |s| self.cross_zone.encode(s)));
+            try!(s.emit_struct_field("confirmations", 3, |s| self.confirmations.encode(s)));
             Ok(())
         }));
         Ok(())
@@ -72,12 +146,24 @@ impl Encodable for RequestState {
 #[derive(Debug, RustcEncodable)]
 pub struct Detector {
     pub open_requests: HashMap<MemberId, RequestState>,
+    pub config: DetectorConfig,
+    /// Our Local Health Multiplier; 0 is perfectly healthy.
+    pub local_health: u32,
 }
 
 impl Detector {
-    /// Create a new failure detector.
+    /// Create a new failure detector with the default timeouts.
     pub fn new() -> Detector {
-        Detector { open_requests: HashMap::new() }
+        Detector::with_config(DetectorConfig::default())
+    }
+
+    /// Create a new failure detector with the given timeouts.
+    pub fn with_config(config: DetectorConfig) -> Detector {
+        Detector {
+            open_requests: HashMap::new(),
+            config: config,
+            local_health: 0,
+        }
     }
 
     /// Returns true if a member has an open request
@@ -85,48 +171,152 @@ impl Detector {
         self.open_requests.contains_key(member_id)
     }
 
-    /// Start a new request
-    pub fn start(&mut self, member_id: MemberId) {
-        self.open_requests.insert(member_id, RequestState::new());
+    /// The interval between outbound probes, scaled by our local health.
+    pub fn probe_interval(&self) -> i64 {
+        self.config.probe_interval * (self.local_health as i64 + 1)
+    }
+
+    /// The time we wait for an ack, scaled by our local health.
+    pub fn probe_timeout(&self) -> i64 {
+        self.config.probe_timeout * (self.local_health as i64 + 1)
+    }
+
+    /// The time we wait for an ack from a member in another zone.
+    pub fn cross_zone_probe_timeout(&self) -> i64 {
+        self.probe_timeout() * self.config.cross_zone_factor
+    }
+
+    fn timeout_for(&self, cross_zone: bool) -> i64 {
+        if cross_zone {
+            self.cross_zone_probe_timeout()
+        } else {
+            self.probe_timeout()
+        }
+    }
+
+    /// The suspicion timeout for a member, given how many independent confirmations we have.
+    ///
+    /// Starts at `suspicion_max`, and falls logarithmically to `suspicion_min` as confirmations
+    /// arrive.
+    pub fn suspicion_timeout(&self, confirmations: usize) -> i64 {
+        let min = self.config.suspicion_min;
+        let max = if self.config.suspicion_max > min {
+            self.config.suspicion_max
+        } else {
+            min
+        };
+        if self.config.suspicion_confirmations == 0 {
+            return min;
+        }
+        let frac = ((confirmations as f64) + 1.0).ln() /
+                   ((self.config.suspicion_confirmations as f64) + 1.0).ln();
+        let timeout = max - ((max - min) as f64 * frac).floor() as i64;
+        if timeout < min { min } else { timeout }
+    }
+
+    /// Raise our local health score; we missed a probe, or had to refute suspicion of ourselves.
+    pub fn degrade_local_health(&mut self) {
+        if self.local_health < self.config.max_local_health {
+            self.local_health = self.local_health + 1;
+            debug!("Local health degraded to {}", self.local_health);
+        }
+    }
+
+    /// Lower our local health score; we completed a probe successfully.
+    pub fn improve_local_health(&mut self) {
+        if self.local_health > 0 {
+            self.local_health = self.local_health - 1;
+            debug!("Local health improved to {}", self.local_health);
+        }
+    }
+
+    /// Start a new request. Requests to members in another zone get longer timeouts.
+    pub fn start(&mut self, member_id: MemberId, cross_zone: bool) {
+        let mut rs = RequestState::with_timeout(self.timeout_for(cross_zone));
+        rs.cross_zone = cross_zone;
+        self.open_requests.insert(member_id, rs);
     }
 
     /// Set a requests state to PingReq
     pub fn pingreq(&mut self, member_id: &MemberId) {
+        let (probe_timeout, cross_zone_timeout) = (self.probe_timeout(),
+                                                   self.cross_zone_probe_timeout());
         if let Some(rs) = self.open_requests.get_mut(member_id) {
+            let timeout = if rs.cross_zone { cross_zone_timeout } else { probe_timeout };
             rs.status = Status::PingReq;
-            rs.timeout = SteadyTime::now() + Duration::milliseconds(REQUEST_FAILURE_TIME);
+            rs.timeout = SteadyTime::now() + Duration::milliseconds(timeout);
         }
     }
 
     /// Sets a requests state to AwaitingAck
     pub fn awaiting_ack(&mut self, member_id: &MemberId) {
+        let (probe_timeout, cross_zone_timeout) = (self.probe_timeout(),
+                                                   self.cross_zone_probe_timeout());
         if let Some(rs) = self.open_requests.get_mut(member_id) {
+            let timeout = if rs.cross_zone { cross_zone_timeout } else { probe_timeout };
             rs.status = Status::AwaitingAck;
-            rs.timeout = SteadyTime::now() + Duration::milliseconds(REQUEST_FAILURE_TIME);
+            rs.timeout = SteadyTime::now() + Duration::milliseconds(timeout);
         }
     }
 
-    /// Sets a requests state to Failed
+    /// Sets a requests state to Failed, starting the suspicion timeout.
     pub fn failed(&mut self, member_id: &MemberId) {
+        let timeout = self.suspicion_timeout(0);
         if let Some(rs) = self.open_requests.get_mut(member_id) {
+            let now = SteadyTime::now();
             rs.status = Status::Failed;
-            rs.timeout = SteadyTime::now() + Duration::milliseconds(REQUEST_CONFIRM_TIME);
+            rs.suspected_at = now;
+            rs.confirmations.clear();
+            rs.timeout = now + Duration::milliseconds(timeout);
         }
     }
 
+    /// Records that `from` independently believes `member_id` is suspect. If this is a new
+    /// confirmation, the suspicion timeout for the member shrinks accordingly.
+    ///
+    /// Returns true if the confirmation was new.
+    pub fn confirm_suspicion(&mut self, member_id: &MemberId, from: &MemberId) -> bool {
+        if member_id == from {
+            return false;
+        }
+        let confirmations = match self.open_requests.get_mut(member_id) {
+            Some(rs) => {
+                if rs.status != Status::Failed || !rs.confirmations.insert(from.clone()) {
+                    return false;
+                }
+                rs.confirmations.len()
+            }
+            None => return false,
+        };
+        let timeout = self.suspicion_timeout(confirmations);
+        if let Some(rs) = self.open_requests.get_mut(member_id) {
+            rs.timeout = rs.suspected_at + Duration::milliseconds(timeout);
+        }
+        true
+    }
+
     /// On success, we remove the request from the detector
     pub fn success(&mut self, member_id: &MemberId) {
-        self.open_requests.remove(member_id);
+        if let Some(rs) = self.open_requests.remove(member_id) {
+            if rs.status != Status::Failed {
+                self.improve_local_health();
+            }
+        }
     }
 
-    /// Checks the timeout of connections. Returns a tuple of (suspect, confirmed) members.
+    /// Checks the timeout of connections. Returns a tuple of (pingreq, suspect, confirmed)
+    /// members.
+    ///
+    /// Every probe that runs out of time on its PingReq counts against our local health, as we
+    /// may well be the member that is struggling.
     pub fn expire(&mut self) -> (Vec<MemberId>, Vec<MemberId>, Vec<MemberId>) {
         debug!("Detector State: {:#?}", self);
         let mut pingreq_members = Vec::new();
         let mut failed_members = Vec::new();
         let mut confirmed_members = Vec::new();
+        let now = SteadyTime::now();
         for (member_id, request_state) in self.open_requests.iter() {
-            if SteadyTime::now() > request_state.timeout {
+            if now > request_state.timeout {
                 match request_state.status {
                     Status::AwaitingAck => pingreq_members.push(member_id.clone()),
                     Status::PingReq => failed_members.push(member_id.clone()),
@@ -136,6 +326,7 @@ impl Detector {
             }
         }
         for member_id in failed_members.iter() {
+            self.degrade_local_health();
             self.failed(member_id);
         }
         for member_id in confirmed_members.iter() {
@@ -161,13 +352,13 @@ mod test {
 
     mod detector {
         use gossip::member::MemberId;
-        use gossip::detector::{Detector, Status};
+        use gossip::detector::{Detector, DetectorConfig, Status};
 
         #[test]
         fn start() {
             let mut d = Detector::new();
             let id = MemberId::new_v4();
-            d.start(id);
+            d.start(id, false);
             assert!(d.open_requests.contains_key(&id));
         }
 
@@ -175,10 +366,94 @@ mod test {
         fn pingreq() {
             let mut d = Detector::new();
             let id = MemberId::new_v4();
-            d.start(id);
+            d.start(id, false);
             d.pingreq(&id);
             assert!(d.open_requests.contains_key(&id));
             assert_eq!(d.open_requests.get(&id).unwrap().status, Status::PingReq);
         }
+
+        #[test]
+        fn local_health_scales_timeouts() {
+            let mut d = Detector::with_config(DetectorConfig::default());
+            assert_eq!(d.probe_interval(), d.config.probe_interval);
+            assert_eq!(d.probe_timeout(), d.config.probe_timeout);
+            d.degrade_local_health();
+            d.degrade_local_health();
+            assert_eq!(d.probe_interval(), d.config.probe_interval * 3);
+            assert_eq!(d.probe_timeout(), d.config.probe_timeout * 3);
+            d.improve_local_health();
+            assert_eq!(d.probe_timeout(), d.config.probe_timeout * 2);
+        }
+
+        #[test]
+        fn local_health_is_bounded() {
+            let mut d = Detector::new();
+            d.improve_local_health();
+            assert_eq!(d.local_health, 0);
+            for _ in 0..(d.config.max_local_health + 5) {
+                d.degrade_local_health();
+            }
+            assert_eq!(d.local_health, d.config.max_local_health);
+        }
+
+        #[test]
+        fn success_improves_local_health() {
+            let mut d = Detector::new();
+            let id = MemberId::new_v4();
+            d.degrade_local_health();
+            d.start(id, false);
+            d.success(&id);
+            assert_eq!(d.local_health, 0);
+            assert!(!d.exists(&id));
+        }
+
+        #[test]
+        fn suspicion_timeout_shrinks_with_confirmations() {
+            let d = Detector::new();
+            assert_eq!(d.suspicion_timeout(0), d.config.suspicion_max);
+            assert!(d.suspicion_timeout(1) < d.suspicion_timeout(0));
+            assert!(d.suspicion_timeout(2) < d.suspicion_timeout(1));
+            assert_eq!(d.suspicion_timeout(d.config.suspicion_confirmations),
+                       d.config.suspicion_min);
+            assert_eq!(d.suspicion_timeout(d.config.suspicion_confirmations + 10),
+                       d.config.suspicion_min);
+        }
+
+        #[test]
+        fn cross_zone_requests_wait_longer() {
+            let mut d = Detector::new();
+            let local = MemberId::new_v4();
+            let remote = MemberId::new_v4();
+            d.start(local, false);
+            d.start(remote, true);
+            assert!(d.open_requests.get(&remote).unwrap().cross_zone);
+            assert!(d.open_requests.get(&remote).unwrap().timeout >
+                    d.open_requests.get(&local).unwrap().timeout);
+            assert_eq!(d.cross_zone_probe_timeout(),
+                       d.probe_timeout() * d.config.cross_zone_factor);
+        }
+
+        #[test]
+        fn confirm_suspicion() {
+            let mut d = Detector::new();
+            let id = MemberId::new_v4();
+            let confirmer = MemberId::new_v4();
+
+            // Confirmations only count once a member has failed
+            d.start(id, false);
+            assert_eq!(d.confirm_suspicion(&id, &confirmer), false);
+
+            d.failed(&id);
+            let before = d.open_requests.get(&id).unwrap().timeout;
+            assert_eq!(d.confirm_suspicion(&id, &confirmer), true);
+            let after = d.open_requests.get(&id).unwrap().timeout;
+            assert!(after < before);
+
+            // The same member confirming twice does not count twice
+            assert_eq!(d.confirm_suspicion(&id, &confirmer), false);
+            // A member can't confirm its own suspicion
+            assert_eq!(d.confirm_suspicion(&id, &id), false);
+            assert_eq!(d.open_requests.get(&id).unwrap().confirmations.len(), 1);
+        }
     }
 }
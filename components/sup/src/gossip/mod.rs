@@ -14,11 +14,11 @@
 
 //! The gossip infrastructure.
 //!
-//! Start with the server module, then read the rumor, detector, and member modules.
+//! Start with the server module, then read the rumor, detector, and member modules. The rumor
+//! types, wire encoding and client are shared with the `hab` CLI, and live in `habitat_gossip`;
+//! they are re-exported here.
+
+pub use habitat_gossip::{client, lamport_clock, member, rumor, schema};
 
-pub mod rumor;
-pub mod lamport_clock;
-pub mod member;
 pub mod server;
-pub mod client;
 pub mod detector;
@@ -35,23 +35,20 @@ use hcore::service::ServiceGroup;
 use utp::{UtpListener, UtpSocket};
 
 use gossip::client::Client;
-use gossip::member::{Member, MemberList, Health};
+use gossip::member::{Member, MemberId, MemberList, Health};
 use gossip::rumor::{Peer, Protocol, Rumor, RumorList, Message};
-use gossip::detector::Detector;
+use gossip::detector::{Detector, DetectorConfig};
 use election::ElectionList;
 use census::{Census, CensusEntry, CensusList};
 use error::Result;
 use util;
 
 static LOGKEY: &'static str = "GS";
-/// How often do we send an outbound request, in milliseconds
-static OUTBOUND_INTERVAL: u64 = 200;
 /// How many outbound threads do we allow?
 static OUTBOUND_MAX_THREADS: usize = 5;
 /// How many inbound threads do we allow?
 static INBOUND_MAX_THREADS: usize = 5;
-/// The default port for the Gossip protocol
-pub static GOSSIP_DEFAULT_PORT: usize = 9634;
+pub use habitat_gossip::GOSSIP_DEFAULT_PORT;
 
 /// A gossip server
 pub struct Server {
@@ -86,7 +83,9 @@ impl Server {
                group: String,
                organization: Option<String>,
                exposes: Option<Vec<String>>,
-               port: Option<String>)
+               port: Option<String>,
+               zone: Option<String>,
+               detector_config: DetectorConfig)
                -> Server {
 
         let hostname = util::sys::hostname(None).unwrap_or(String::from("unknown"));
@@ -94,12 +93,14 @@ impl Server {
         let peer_listen = listen.clone();
         let peer_listen2 = peer_listen.clone();
 
-        let member = Member::new(hostname, listen_ip, peer_listen2, permanent);
+        let mut member = Member::new(hostname, listen_ip, peer_listen2, permanent);
+        member.zone = zone.clone();
 
         let service_group = format!("{}.{}", service, group);
         let mut ce = CensusEntry::new(service.clone(), group.clone(), member.id.clone());
         ce.exposes = exposes;
         ce.port = port;
+        ce.zone = zone;
         let my_id = member.id.clone();
         let leader_id = member.id.clone();
         outputln!("Supervisor {}", member);
@@ -118,7 +119,7 @@ impl Server {
             rumor_list: Arc::new(RwLock::new(RumorList::new())),
             census_list: Arc::new(RwLock::new(census_list)),
             peer: Peer::new(my_id, peer_listen),
-            detector: Arc::new(RwLock::new(Detector::new())),
+            detector: Arc::new(RwLock::new(Detector::with_config(detector_config))),
             election_list: Arc::new(RwLock::new(ElectionList::new(service_group, leader_id))),
             gossip_file_list:
                 Arc::new(RwLock::new(GossipFileList::new(ServiceGroup::new(service,
@@ -362,6 +363,10 @@ fn receive(socket: UtpSocket,
                     return;
                 }
             };
+            // A proxy may be older than the peer, so only speak the peer's schema directly to it
+            if from_peer.proxy_through.is_none() {
+                c.set_schema(from_peer.schema);
+            }
 
             // Generate our Ack rumors
             let ack_rumors = {
@@ -385,14 +390,21 @@ fn receive(socket: UtpSocket,
                 Err(e) => debug!("Failed to send Ack to {:?}: {:?}", from_peer, e),
             }
 
-            // Update the heat for the rumors we shared
-            {
+            // Update the heat for the rumors we shared. `hab ring` queries ping as a throwaway
+            // member that brings no rumors and never joins, so they get no heat entry to keep.
+            let is_query = remote_rumor_list.rumors.is_empty() &&
+                           member_list.read().unwrap().get(&from_peer.member_id).is_none();
+            if !is_query {
                 let mut rl = rumor_list.write().unwrap();
                 rl.update_heat_for(&from_peer.member_id, &ack_rumors);
             }
 
             // Update our rumors
             debug!("Updating rumors from {:#?}", from_peer);
+            observe_suspicions(&from_peer.member_id,
+                               &my_peer.member_id,
+                               &remote_rumor_list,
+                               &detector);
             process_rumors(remote_rumor_list,
                            rumor_list,
                            member_list,
@@ -427,6 +439,10 @@ fn receive(socket: UtpSocket,
                 }
                 // Update our rumors
                 debug!("Updating rumors via ack from {:#?} ", from_peer);
+                observe_suspicions(&from_peer.member_id,
+                                   &my_peer.member_id,
+                                   &remote_rumor_list,
+                                   &detector);
                 process_rumors(remote_rumor_list,
                                rumor_list,
                                member_list,
@@ -475,6 +491,38 @@ fn receive(socket: UtpSocket,
     }
 }
 
+/// Feeds the failure detector with what a peer is telling us about member health.
+///
+/// * A Suspect member rumor counts as a confirmation of our own suspicion from the member that
+/// suspected it, which shortens the suspicion timeout. The peer relaying the rumor only counts
+/// when it relays someone else's suspicion, so no one member can confirm its own suspicion to us
+/// over and over; the detector counts each suspecting member once.
+/// * A rumor that we ourselves are Suspect or Confirmed means we are going to have to refute
+/// it; that counts against our local health.
+fn observe_suspicions(from: &MemberId,
+                      my_id: &MemberId,
+                      remote_rumors: &RumorList,
+                      detector: &Arc<RwLock<Detector>>) {
+    let mut fd = detector.write().unwrap();
+    for remote_rumor in remote_rumors.rumors.values() {
+        if let Message::Member(ref m) = remote_rumor.payload {
+            if &m.id == my_id {
+                if m.health != Health::Alive {
+                    fd.degrade_local_health();
+                }
+            } else if m.health == Health::Suspect {
+                let suspecter = match m.suspected_by {
+                    Some(ref suspecter) if suspecter != from && suspecter != my_id => suspecter,
+                    _ => continue,
+                };
+                if fd.confirm_suspicion(&m.id, suspecter) {
+                    debug!("Suspicion of {} confirmed by {}, via {}", m.id, suspecter, from);
+                }
+            }
+        }
+    }
+}
+
 pub fn process_rumors(remote_rumors: RumorList,
                       rumor_list: Arc<RwLock<RumorList>>,
                       member_list: Arc<RwLock<MemberList>>,
@@ -549,14 +597,25 @@ pub fn process_rumors(remote_rumors: RumorList,
                     rl.add_rumor(Rumor::gossip_file(gossip_file));
                 }
             }
+            Message::Unknown(unknown) => {
+                // We can't process it, but members running a newer release can; keep gossiping it
+                let mut rl = rumor_list.write().unwrap();
+                if !rl.contains_key(&id) {
+                    debug!("Relaying unknown rumor {} of kind {}", id, unknown.kind);
+                    rl.add_rumor(Rumor {
+                        id: id,
+                        payload: Message::Unknown(unknown),
+                    });
+                }
+            }
             Message::Blank => {}
         }
     }
 }
 
 
-/// The outbound distributor. Every OUTBOUND_INTERVAL in milliseconds, it spawns a new connection
-/// to the next member.
+/// The outbound distributor. Every probe interval, it spawns a new connection to the next member.
+/// The interval grows as our local health degrades.
 ///
 /// Like inbound, it is backed by a thread pool - if we have more than OUTBOUND_MAX_THREADS running
 /// at once, we delay the next outbound message until a thread is free.
@@ -568,7 +627,10 @@ pub fn outbound(ring_key: Arc<Option<SymKey>>,
     let pool = ThreadPool::new(OUTBOUND_MAX_THREADS);
     loop {
         // Pretty chimpy, but will work for now
-        thread::sleep(Duration::from_millis(OUTBOUND_INTERVAL));
+        let interval = {
+            detector.read().unwrap().probe_interval()
+        };
+        thread::sleep(Duration::from_millis(interval as u64));
 
         if pool.active_count() == pool.max_count() {
             info!("{} of {} outbound threads full; delaying this round",
@@ -628,9 +690,12 @@ pub fn send_outbound(ring_key: Arc<Option<SymKey>>,
                      rumor_list: Arc<RwLock<RumorList>>,
                      member_list: Arc<RwLock<MemberList>>,
                      detector: Arc<RwLock<Detector>>) {
+    let cross_zone = {
+        member_list.read().unwrap().is_cross_zone(&member.id)
+    };
     {
         let mut d = detector.write().unwrap();
-        d.start(member.id.clone());
+        d.start(member.id.clone(), cross_zone);
     }
 
     let mut c = match Client::new(&member.gossip_listener[..], ring_key.deref().as_ref()) {
@@ -648,6 +713,7 @@ pub fn send_outbound(ring_key: Arc<Option<SymKey>>,
             return;
         }
     };
+    c.set_schema(member.schema);
 
     let ping_rumors = {
         let rl = rumor_list.read().unwrap();
@@ -717,6 +783,7 @@ pub fn send_pingreq(ring_key: Arc<Option<SymKey>>,
                 continue;
             }
         };
+        c.set_schema(pingreq_member.schema);
 
         let mut proxy_peer = my_peer.clone();
         proxy_peer.proxy_through = Some(pingreq_member.gossip_listener.clone());
@@ -789,8 +856,9 @@ pub fn failure_detector(ring_key: Arc<Option<SymKey>>,
                     fd.exists(member_id)
                 };
                 if !member_detected {
+                    let cross_zone = ml.is_cross_zone(member_id);
                     let mut fd = detector.write().unwrap();
-                    fd.start(member_id.clone());
+                    fd.start(member_id.clone(), cross_zone);
                     fd.failed(&member_id);
                 }
             }
@@ -42,6 +42,7 @@
 extern crate habitat_core as hcore;
 extern crate habitat_common as common;
 extern crate habitat_depot_client as depot_client;
+extern crate habitat_gossip;
 extern crate handlebars;
 #[macro_use]
 extern crate hyper;
@@ -61,7 +62,6 @@ extern crate time;
 extern crate wonder;
 extern crate uuid;
 extern crate utp;
-extern crate rand;
 extern crate threadpool;
 extern crate urlencoded;
 extern crate openssl;
@@ -273,7 +273,9 @@ pub mod state_machine;
 pub mod sidecar;
 pub mod health_check;
 pub mod config;
+pub mod config_schema;
 pub mod service_config;
+pub mod templating;
 pub mod census;
 pub mod gossip;
 pub mod election;
@@ -61,6 +61,7 @@ const DEFAULT_GOSSIP_LISTEN_PORT: u16 = 9634;
 
 static RING_ENVVAR: &'static str = "HAB_RING";
 static RING_KEY_ENVVAR: &'static str = "HAB_RING_KEY";
+static ZONE_ENVVAR: &'static str = "HAB_ZONE";
 
 /// Creates a [Config](config/struct.Config.html) from global args
 /// and subcommand args.
@@ -96,6 +97,19 @@ fn config_from_args(args: &ArgMatches, subcommand: &str, sub_args: &ArgMatches)
     if let Some(outfile) = sub_args.value_of("outfile") {
         config.set_outfile(outfile.to_string());
     }
+    if let Some(user_toml) = sub_args.value_of("user-toml") {
+        config.set_user_toml(user_toml.to_string());
+    }
+    if let Some(gossip_toml) = sub_args.value_of("gossip-toml") {
+        config.set_gossip_toml(gossip_toml.to_string());
+    }
+    if let Some(census) = sub_args.value_of("census") {
+        config.set_census_file(census.to_string());
+    }
+    config.set_diff(sub_args.is_present("diff"));
+    if let Some(config_dir) = sub_args.value_of("config-dir") {
+        config.set_config_dir(config_dir.to_string());
+    }
     if let Some(topology) = sub_args.value_of("topology") {
         match topology.as_ref() {
             "standalone" => {
@@ -123,6 +137,11 @@ fn config_from_args(args: &ArgMatches, subcommand: &str, sub_args: &ArgMatches)
         None => vec![],
     };
     config.set_bind(bindings);
+    let config_signers = match sub_args.values_of("config-signer") {
+        Some(signers) => signers.map(|s| s.to_string()).collect(),
+        None => vec![],
+    };
+    config.set_config_signers(config_signers);
     config.set_path(sub_args.value_of("path")
         .unwrap_or(fs::svc_path(sup::PROGRAM_NAME.as_str())
             .join("data")
@@ -164,6 +183,28 @@ fn config_from_args(args: &ArgMatches, subcommand: &str, sub_args: &ArgMatches)
         None => vec![],
     };
     config.set_gossip_peer(gossip_peers);
+    if sub_args.value_of("probe-interval").is_some() {
+        let ms = value_t!(sub_args, "probe-interval", u64).unwrap_or_else(|e| e.exit());
+        config.set_gossip_probe_interval(ms);
+    }
+    if sub_args.value_of("probe-timeout").is_some() {
+        let ms = value_t!(sub_args, "probe-timeout", u64).unwrap_or_else(|e| e.exit());
+        config.set_gossip_probe_timeout(ms);
+    }
+    if sub_args.value_of("suspicion-timeout").is_some() {
+        let ms = value_t!(sub_args, "suspicion-timeout", u64).unwrap_or_else(|e| e.exit());
+        config.set_gossip_suspicion_timeout(ms);
+    }
+    if sub_args.value_of("suspicion-max-timeout").is_some() {
+        let ms = value_t!(sub_args, "suspicion-max-timeout", u64).unwrap_or_else(|e| e.exit());
+        config.set_gossip_suspicion_max_timeout(ms);
+    }
+    if config.gossip_detector().suspicion_min > config.gossip_detector().suspicion_max {
+        clap::Error::with_description("The suspicion-timeout must not be longer than the \
+                                       suspicion-max-timeout",
+                                      clap::ErrorKind::ArgumentConflict)
+            .exit();
+    }
     if sub_args.value_of("permanent-peer").is_some() {
         config.set_gossip_permanent(true);
     }
@@ -208,6 +249,16 @@ fn config_from_args(args: &ArgMatches, subcommand: &str, sub_args: &ArgMatches)
     if let Some(org) = sub_args.value_of("organization") {
         config.set_organization(org.to_string());
     }
+    match sub_args.value_of("zone") {
+        Some(zone) => {
+            config.set_zone(zone.to_string());
+        }
+        None => {
+            if let Ok(zone) = henv::var(ZONE_ENVVAR) {
+                config.set_zone(zone);
+            }
+        }
+    }
     debug!("Config:\n{:?}", config);
     Ok(config)
 }
@@ -276,6 +327,16 @@ fn main() {
             .value_name("bind")
             .multiple(true)
             .help("One or more service groups to bind to a configuration"))
+        .arg(Arg::with_name("config-signer")
+            .long("config-signer")
+            .value_name("key")
+            .multiple(true)
+            .help("Only apply gossiped configuration and files signed by one of these sig keys"))
+        .arg(Arg::with_name("config-dir")
+            .long("config-dir")
+            .value_name("dir")
+            .help("A directory of files, each named for a config key and holding its value, \
+                   that override the service's config"))
         .arg(Arg::with_name("ring")
             .short("r")
             .long("ring")
@@ -294,6 +355,33 @@ fn main() {
             .long("listen-http")
             .value_name("ip:port")
             .help("The HTTP API listen address [default: 0.0.0.0:9631]"))
+        .arg(Arg::with_name("zone")
+            .long("zone")
+            .value_name("zone")
+            .help("The datacenter or zone this supervisor runs in; peers in other zones are \
+                   probed less often"))
+        .arg(Arg::with_name("probe-interval")
+            .long("probe-interval")
+            .value_name("ms")
+            .validator(valid_ms)
+            .help("How often to probe a gossip peer [default: 200]"))
+        .arg(Arg::with_name("probe-timeout")
+            .long("probe-timeout")
+            .value_name("ms")
+            .validator(valid_ms)
+            .help("How long to wait for a probe to be acknowledged [default: 5000]"))
+        .arg(Arg::with_name("suspicion-timeout")
+            .long("suspicion-timeout")
+            .value_name("ms")
+            .validator(valid_ms)
+            .help("The shortest time a peer is suspect before being confirmed dead \
+                   [default: 10000]"))
+        .arg(Arg::with_name("suspicion-max-timeout")
+            .long("suspicion-max-timeout")
+            .value_name("ms")
+            .validator(valid_ms)
+            .help("The longest time a peer is suspect before being confirmed dead \
+                   [default: 60000]"))
         .arg(Arg::with_name("permanent-peer")
             .short("I")
             .long("permanent-peer")
@@ -309,6 +397,38 @@ fn main() {
             .index(1)
             .required(true)
             .help("Name of package"));
+    let sub_render = SubCommand::with_name("render")
+        .about("Render a package's configuration files and hooks without starting it")
+        .arg(Arg::with_name("package")
+            .index(1)
+            .required(true)
+            .help("Name of package"))
+        .arg(arg_group())
+        .arg(Arg::with_name("bind")
+            .long("bind")
+            .value_name("bind")
+            .multiple(true)
+            .help("One or more service groups to bind to a configuration"))
+        .arg(Arg::with_name("user-toml")
+            .long("user-toml")
+            .value_name("file")
+            .help("A user.toml to render with [default: the service's own]"))
+        .arg(Arg::with_name("gossip-toml")
+            .long("gossip-toml")
+            .value_name("file")
+            .help("A gossiped config to render with [default: the service's own]"))
+        .arg(Arg::with_name("census")
+            .long("census")
+            .value_name("file")
+            .help("A toml file of [[member]] tables describing the census to render with"))
+        .arg(Arg::with_name("config-dir")
+            .long("config-dir")
+            .value_name("dir")
+            .help("A directory of files, each named for a config key and holding its value, \
+                   that override the service's config"))
+        .arg(Arg::with_name("diff")
+            .long("diff")
+            .help("Diff the rendered files against the service's current ones"));
     let args = App::new(sup::PROGRAM_NAME.as_str())
         .version(VERSION)
         .setting(AppSettings::VersionlessSubcommands)
@@ -324,7 +444,8 @@ fn main() {
         .subcommand(sub_start)
         .subcommand(sub_bash)
         .subcommand(sub_sh)
-        .subcommand(sub_config);
+        .subcommand(sub_config)
+        .subcommand(sub_render);
     let matches = args.get_matches();
 
     debug!("clap matches {:?}", matches);
@@ -341,6 +462,7 @@ fn main() {
         Command::ShellBash => shell_bash(&config),
         Command::ShellSh => shell_sh(&config),
         Command::Config => configure(&config),
+        Command::Render => render(&config),
         Command::Start => start(&config),
     };
 
@@ -350,6 +472,14 @@ fn main() {
     }
 }
 
+/// Check a duration given in milliseconds
+fn valid_ms(val: String) -> result::Result<(), String> {
+    match val.parse::<u64>() {
+        Ok(ms) if ms > 0 && ms <= i64::max_value() as u64 => Ok(()),
+        _ => Err(format!("'{}' is not a number of milliseconds greater than 0", val)),
+    }
+}
+
 /// Exit with an error message and the right status code
 #[allow(dead_code)]
 fn exit_with(e: SupError, code: i32) {
@@ -376,6 +506,13 @@ fn configure(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Render a service's configuration files and hooks
+#[allow(dead_code)]
+fn render(config: &Config) -> Result<()> {
+    try!(render::package(config));
+    Ok(())
+}
+
 /// Start a service
 #[allow(dead_code)]
 fn start(config: &Config) -> Result<()> {
@@ -19,11 +19,10 @@ use std::os::unix::fs::OpenOptionsExt;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
-use handlebars::Handlebars;
-
 use error::{Error, Result};
 use package::Package;
-use service_config::{ServiceConfig, never_escape_fn};
+use service_config::ServiceConfig;
+use templating;
 use util::convert;
 
 static LOGKEY: &'static str = "PH";
@@ -66,9 +65,20 @@ impl Hook {
     }
 
     pub fn run(&self, context: Option<&ServiceConfig>) -> Result<String> {
+        self.run_with_env(context, &[])
+    }
+
+    /// Run the hook with extra environment variables set.
+    pub fn run_with_env(&self,
+                        context: Option<&ServiceConfig>,
+                        env: &[(&str, String)])
+                        -> Result<String> {
         try!(self.compile(context));
-        let mut child = try!(Command::new(&self.path)
-            .stdin(Stdio::null())
+        let mut cmd = Command::new(&self.path);
+        for &(key, ref value) in env.iter() {
+            cmd.env(key, value);
+        }
+        let mut child = try!(cmd.stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn());
@@ -113,15 +123,20 @@ impl Hook {
         }
     }
 
+    /// Render the hook template against a service's configuration.
+    pub fn render(&self, ctx: &ServiceConfig) -> Result<String> {
+        debug!("Rendering hook {:?}", self);
+        let mut handlebars = templating::handlebars();
+        try!(handlebars.register_template_file("hook", &self.template));
+        let toml = try!(ctx.to_template_toml());
+        let svc_data = convert::toml_to_json(toml);
+        let data = try!(handlebars.render("hook", &svc_data));
+        Ok(data)
+    }
+
     pub fn compile(&self, context: Option<&ServiceConfig>) -> Result<()> {
         if let Some(ctx) = context {
-            debug!("Rendering hook {:?}", self);
-            let mut handlebars = Handlebars::new();
-            handlebars.register_escape_fn(never_escape_fn);
-            try!(handlebars.register_template_file("hook", &self.template));
-            let toml = try!(ctx.to_toml());
-            let svc_data = convert::toml_to_json(toml);
-            let data = try!(handlebars.render("hook", &svc_data));
+            let data = try!(self.render(ctx));
             let mut file = try!(OpenOptions::new()
                 .write(true)
                 .truncate(true)
@@ -19,6 +19,7 @@ pub use self::updater::{PackageUpdater, PackageUpdaterActor, UpdaterMessage};
 pub use self::hooks::HookType;
 
 use std;
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
 use std::fs::File;
@@ -33,7 +34,7 @@ use hcore::util;
 use self::hooks::HookTable;
 use error::{Error, Result, SupError};
 use health_check::{self, CheckResult};
-use service_config::ServiceConfig;
+use service_config::{ConfigAction, ConfigPerm, ServiceConfig};
 use supervisor::Supervisor;
 use util::path::busybox_paths;
 
@@ -43,9 +44,18 @@ const HEALTHCHECK_FILENAME: &'static str = "health_check";
 const FILEUPDATED_FILENAME: &'static str = "file_updated";
 const RECONFIGURE_FILENAME: &'static str = "reconfigure";
 const RUN_FILENAME: &'static str = "run";
+const CONFIG_CHANGED_ENVVAR: &'static str = "HAB_CONFIG_CHANGED";
 const SERVICE_PATH_OWNER: &'static str = "hab";
 const SERVICE_PATH_GROUP: &'static str = "hab";
 
+/// A service the package can be bound to, and the values that service has to export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindContract {
+    pub name: String,
+    pub exports: Vec<String>,
+    pub optional: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Package {
     pub origin: String,
@@ -54,6 +64,8 @@ pub struct Package {
     pub release: String,
     pub deps: Vec<PackageIdent>,
     pub tdeps: Vec<PackageIdent>,
+    /// The binds the package declared, required and optional.
+    pub binds: Vec<BindContract>,
     pub pkg_install: PackageInstall,
 }
 
@@ -82,6 +94,7 @@ impl Package {
             release: pkg_install.ident().release.as_ref().unwrap().clone(),
             deps: try!(pkg_install.deps()).clone(),
             tdeps: try!(pkg_install.tdeps()).clone(),
+            binds: try!(bind_contracts(&pkg_install)),
             pkg_install: pkg_install,
         })
     }
@@ -97,6 +110,13 @@ impl Package {
         }
     }
 
+    /// The values the package exports to services bound to it, and the config key each is read
+    /// from.
+    pub fn exports(&self) -> Result<Vec<(String, String)>> {
+        let exports = try!(self.pkg_install.exports());
+        Ok(exports)
+    }
+
     /// Returns a string with the full run path for this package. This path is composed of any
     /// binary paths specified by this package, or its TDEPS, plus a path to a BusyBox,
     /// plus the existing value of the PATH variable.
@@ -242,6 +262,50 @@ impl Package {
         self.pkg_install.ident()
     }
 
+    /// The action the package declared for changes to each of its config files. Files without a
+    /// valid declaration are left out.
+    pub fn config_actions(&self) -> HashMap<String, ConfigAction> {
+        let mut actions = HashMap::new();
+        let declared = match self.pkg_install.config_actions() {
+            Ok(declared) => declared,
+            Err(e) => {
+                outputln!("Failed to load config actions for {}: {}", self.ident(), e);
+                return actions;
+            }
+        };
+        for (file, action) in declared {
+            match ConfigAction::from_str(&action) {
+                Some(action) => {
+                    actions.insert(file, action);
+                }
+                None => outputln!("Ignoring unknown config action {} for {}", action, file),
+            }
+        }
+        actions
+    }
+
+    /// The mode and owner the package declared for each of its rendered config files. Files
+    /// without a valid declaration are left out.
+    pub fn config_perms(&self) -> HashMap<String, ConfigPerm> {
+        let mut perms = HashMap::new();
+        let declared = match self.pkg_install.config_perms() {
+            Ok(declared) => declared,
+            Err(e) => {
+                outputln!("Failed to load config perms for {}: {}", self.ident(), e);
+                return perms;
+            }
+        };
+        for (file, perm) in declared {
+            match ConfigPerm::from_str(&perm) {
+                Some(perm) => {
+                    perms.insert(file, perm);
+                }
+                None => outputln!("Ignoring invalid config perms {} for {}", perm, file),
+            }
+        }
+        perms
+    }
+
     /// Run initialization hook if present
     pub fn initialize(&self, context: &ServiceConfig) -> Result<()> {
         if let Some(hook) = self.hooks().init_hook {
@@ -255,10 +319,12 @@ impl Package {
     }
 
     /// Run reconfigure hook if present. Return false if it is not present, to trigger default
-    /// restart behavior.
-    pub fn reconfigure(&self, context: &ServiceConfig) -> Result<bool> {
+    /// restart behavior. The hook gets the names of the changed config files, space separated, in
+    /// `HAB_CONFIG_CHANGED`.
+    pub fn reconfigure(&self, context: &ServiceConfig, changed: &[String]) -> Result<bool> {
         if let Some(hook) = self.hooks().reconfigure_hook {
-            match hook.run(Some(context)) {
+            let env = [(CONFIG_CHANGED_ENVVAR, changed.join(" "))];
+            match hook.run_with_env(Some(context), &env) {
                 Ok(_) => Ok(true),
                 Err(e) => Err(e),
             }
@@ -328,6 +394,25 @@ impl Package {
     }
 }
 
+fn bind_contracts(pkg_install: &PackageInstall) -> Result<Vec<BindContract>> {
+    let mut binds = Vec::new();
+    for (name, exports) in try!(pkg_install.binds()) {
+        binds.push(BindContract {
+            name: name,
+            exports: exports,
+            optional: false,
+        });
+    }
+    for (name, exports) in try!(pkg_install.binds_optional()) {
+        binds.push(BindContract {
+            name: name,
+            exports: exports,
+            optional: true,
+        });
+    }
+    Ok(binds)
+}
+
 impl Into<PackageIdent> for Package {
     fn into(self) -> PackageIdent {
         PackageIdent::new(self.origin,
@@ -16,8 +16,9 @@ use std::sync::{Arc, RwLock};
 use std::path::Path;
 
 use common::command::ProgressBar;
-use depot_client::Client;
+use depot_client::{self, Client};
 use hcore::crypto::default_cache_key_path;
+use hcore::crypto::artifact::SignaturePolicy;
 use hcore::fs::{CACHE_ARTIFACT_PATH, FS_ROOT_PATH};
 use hcore::package::PackageIdent;
 use wonder;
@@ -26,6 +27,7 @@ use wonder::actor::{GenServer, InitResult, HandleResult, ActorSender, ActorResul
 use error::SupError;
 use package::Package;
 
+static LOGKEY: &'static str = "PU";
 const TIMEOUT_MS: u64 = 60_000;
 
 pub type PackageUpdaterActor = wonder::actor::Actor<UpdaterMessage>;
@@ -114,11 +116,26 @@ impl GenServer for PackageUpdater {
                                                      &Path::new(FS_ROOT_PATH)
                                                          .join(CACHE_ARTIFACT_PATH),
                                                      Some(&mut progress)) {
-                        Ok(archive) => {
+                        Ok(mut archive) => {
                             debug!("Updater downloaded new package to {:?}", archive);
-                            // JW TODO: actually handle verify and unpack results
-                            archive.verify(&default_cache_key_path(None)).unwrap();
-                            archive.unpack(None).unwrap();
+                            let cache_key_path = default_cache_key_path(None);
+                            match depot_client.fetch_revocation_list(&package.origin,
+                                                                     &cache_key_path) {
+                                Ok(_) => (),
+                                Err(depot_client::Error::HabitatCore(e)) => {
+                                    outputln!("Not updating to {}: {}", latest_ident, e);
+                                    return HandleResult::NoReply(Some(TIMEOUT_MS));
+                                }
+                                Err(e) => {
+                                    debug!("Updater failed to refresh revocation list: {:?}", e)
+                                }
+                            }
+                            if let Err(e) = archive.verify_and_unpack(None,
+                                                                      &cache_key_path,
+                                                                      &SignaturePolicy::Origin) {
+                                outputln!("Not updating to {}: {}", latest_ident, e);
+                                return HandleResult::NoReply(Some(TIMEOUT_MS));
+                            }
                             let latest_package = Package::load(&latest_ident, None).unwrap();
                             state.status = UpdaterStatus::Stopped;
                             let msg = wonder::actor::Message::Cast(UpdaterMessage::Update(latest_package));
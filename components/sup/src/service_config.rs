@@ -15,29 +15,39 @@
 /// Collect all the configuration data that is exposed to users, and render it.
 
 use std::ascii::AsciiExt;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-use std::fs::File;
+use std::fmt;
+use std::fs::{self, File};
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::result;
 
 use ansi_term::Colour::Purple;
-use rustc_serialize::Encodable;
+use rustc_serialize::{Encodable, Encoder};
 use toml;
-use handlebars::{Handlebars, JsonRender};
+use handlebars::JsonRender;
 
 use common::gossip_file::GOSSIP_TOML;
-use census::{Census, CensusList};
+use census::{Census, CensusEntry, CensusList};
 use config::Config;
-use error::{Error, Result};
-use hcore::package::PackageInstall;
+use config_schema::ConfigSchema;
+use error::{Error, Result, SupError};
+use hcore;
+use hcore::package::{PackageIdent, PackageInstall};
 use hcore::crypto;
-use package::Package;
+use hcore::util::perm;
+use package::{BindContract, Package};
+use templating;
 use util;
 use util::convert;
 use VERSION;
 
 static LOGKEY: &'static str = "SC";
 static ENV_VAR_PREFIX: &'static str = "HAB";
+/// Lists the files rendered into a service's config path, so files from templates the package no
+/// longer has can be swept, even if they were rendered before the supervisor restarted.
+static RENDERED_FILES: &'static str = ".rendered";
 
 /// The top level struct for all our configuration - this corresponds to the top level namespaces
 /// available in `config.toml`.
@@ -49,15 +59,120 @@ pub struct ServiceConfig {
     cfg: Cfg,
     svc: Svc,
     bind: Bind,
+    secret: Secret,
     // Keeps a list of the configuration files we have renders, and only re-writes them if they
     // have changed.
     config_hash: HashMap<String, String>,
     // Set to 'true' if we have data that needs to be sent to a configuration file
     pub needs_write: bool,
+    // Why the most recent `cfg` tree was rejected, if it was; we keep rendering the last good one
+    pub cfg_error: Option<String>,
+    // Why the census no longer meets the package's binds, if it doesn't; we keep rendering the
+    // last good bind data
+    pub bind_error: Option<String>,
+    // A directory of files, one config key each, that override the `cfg` tree
+    config_dir: Option<PathBuf>,
 }
 
-pub fn never_escape_fn(data: &str) -> String {
-    String::from(data)
+/// What to do when a rendered config file changes, as declared by the package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// Restart the service
+    Restart,
+    /// Run the reconfigure hook, but don't restart
+    Reconfigure,
+    /// Do nothing; the service picks up the change itself, or doesn't need it
+    Nothing,
+}
+
+impl ConfigAction {
+    pub fn from_str(action: &str) -> Option<ConfigAction> {
+        match action {
+            "restart" => Some(ConfigAction::Restart),
+            "reconfigure" => Some(ConfigAction::Reconfigure),
+            "none" => Some(ConfigAction::Nothing),
+            _ => None,
+        }
+    }
+}
+
+/// The mode, and optionally the owner, a package declared for one of its rendered config files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigPerm {
+    /// An octal mode, as given to `chmod`
+    pub mode: String,
+    /// A `user` or `user:group`, as given to `chown`
+    pub owner: Option<String>,
+}
+
+impl ConfigPerm {
+    /// Parse a `mode[:owner[:group]]` declaration, such as `0640:hab:hab`.
+    pub fn from_str(perm: &str) -> Option<ConfigPerm> {
+        let mut parts = perm.splitn(2, ':');
+        let mode = match parts.next() {
+            Some(mode) if (mode.len() == 3 || mode.len() == 4) &&
+                          mode.chars().all(|c| c >= '0' && c <= '7') => mode.to_string(),
+            _ => return None,
+        };
+        let owner = match parts.next() {
+            Some(owner) if owner.is_empty() || owner.starts_with(':') ||
+                           owner.ends_with(':') || owner.split(':').count() > 2 => return None,
+            Some(owner) => Some(owner.to_string()),
+            None => None,
+        };
+        Some(ConfigPerm {
+            mode: mode,
+            owner: owner,
+        })
+    }
+}
+
+/// The config files that changed on a write, grouped by the action the package declared for
+/// them. Files the package declared nothing for are `unspecified`; the caller decides what to do
+/// about those.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigChanges {
+    pub restart: Vec<String>,
+    pub reconfigure: Vec<String>,
+    pub nothing: Vec<String>,
+    pub unspecified: Vec<String>,
+}
+
+impl ConfigChanges {
+    fn add(&mut self, file: String, action: Option<ConfigAction>) {
+        match action {
+            Some(ConfigAction::Restart) => self.restart.push(file),
+            Some(ConfigAction::Reconfigure) => self.reconfigure.push(file),
+            Some(ConfigAction::Nothing) => self.nothing.push(file),
+            None => self.unspecified.push(file),
+        }
+    }
+
+    /// True if any file the service has to react to changed.
+    pub fn needs_action(&self) -> bool {
+        !(self.restart.is_empty() && self.reconfigure.is_empty() && self.unspecified.is_empty())
+    }
+
+    /// The changed files the reconfigure hook should handle: those declared `reconfigure`, and
+    /// those the package declared nothing for.
+    pub fn to_reconfigure(&self) -> Vec<String> {
+        let mut changed = Vec::new();
+        changed.extend(self.reconfigure.iter().cloned());
+        changed.extend(self.unspecified.iter().cloned());
+        changed.sort();
+        changed
+    }
+
+    /// Every file that changed.
+    pub fn changed(&self) -> Vec<String> {
+        let mut changed = Vec::new();
+        changed.extend(self.restart.iter().cloned());
+        changed.extend(self.reconfigure.iter().cloned());
+        changed.extend(self.nothing.iter().cloned());
+        changed.extend(self.unspecified.iter().cloned());
+        changed.sort();
+        changed
+    }
 }
 
 impl ServiceConfig {
@@ -69,8 +184,48 @@ impl ServiceConfig {
                cl: &CensusList,
                bindings: Vec<String>)
                -> Result<ServiceConfig> {
-        let cfg = try!(Cfg::new(package));
-        let bind = try!(Bind::new(bindings, &cl));
+        let config_dir = config.config_dir().as_ref().map(|d| PathBuf::from(d));
+        let cfg = try!(Cfg::new(package, config_dir.as_ref()));
+        ServiceConfig::with_cfg(config, package, cl, bindings, cfg, config_dir)
+    }
+
+    /// Like `new`, but the `cfg` data is loaded from the given user and gossip config files,
+    /// where given, rather than the service's own. A given file must exist, and a gossip config
+    /// that doesn't match the package's config schema is an error rather than ignored.
+    pub fn from_files(config: &Config,
+                      package: &Package,
+                      cl: &CensusList,
+                      bindings: Vec<String>,
+                      user_toml: Option<&Path>,
+                      gossip_toml: Option<&Path>)
+                      -> Result<ServiceConfig> {
+        for path in user_toml.iter().chain(gossip_toml.iter()) {
+            if !path.is_file() {
+                return Err(sup_error!(Error::FileNotFound(path.to_string_lossy().into_owned())));
+            }
+        }
+        let user_toml = user_toml.map(|p| p.to_path_buf())
+            .unwrap_or(package.svc_path().join("user.toml"));
+        let gossip_toml = gossip_toml.map(|p| p.to_path_buf())
+            .unwrap_or(package.svc_path().join(GOSSIP_TOML));
+        let config_dir = config.config_dir().as_ref().map(|d| PathBuf::from(d));
+        let cfg = try!(Cfg::from_files(package,
+                                       &user_toml,
+                                       &gossip_toml,
+                                       config_dir.as_ref(),
+                                       false));
+        ServiceConfig::with_cfg(config, package, cl, bindings, cfg, config_dir)
+    }
+
+    fn with_cfg(config: &Config,
+                package: &Package,
+                cl: &CensusList,
+                bindings: Vec<String>,
+                cfg: Cfg,
+                config_dir: Option<PathBuf>)
+                -> Result<ServiceConfig> {
+        try!(Bind::validate(&package.binds, &bindings, &cl));
+        let bind = try!(Bind::new(bindings, &cl, &package.binds));
         Ok(ServiceConfig {
             pkg: Pkg::new(&package.pkg_install),
             hab: Hab::new(),
@@ -78,8 +233,12 @@ impl ServiceConfig {
             cfg: cfg,
             svc: Svc::new(cl),
             bind: bind,
+            secret: Secret::default(),
             config_hash: HashMap::new(),
             needs_write: true,
+            cfg_error: None,
+            bind_error: None,
+            config_dir: config_dir,
         })
     }
 
@@ -108,6 +267,29 @@ impl ServiceConfig {
         Ok(toml::Value::Table(top))
     }
 
+    /// The values of the `cfg` keys a package exports, by export name. Values that aren't strings
+    /// are given as toml; keys missing from the config are left out.
+    pub fn exported_cfg(&self, exports: &[(String, String)]) -> BTreeMap<String, String> {
+        self.cfg.exported(exports)
+    }
+
+    /// Render this struct as toml for templates; this is `to_toml` plus the `secret` tree, so it
+    /// must never be written to disk.
+    pub fn to_template_toml(&self) -> Result<toml::Value> {
+        let mut top = match try!(self.to_toml()) {
+            toml::Value::Table(top) => top,
+            _ => unreachable!(),
+        };
+        top.insert(String::from("secret"), self.secret.to_toml());
+        Ok(toml::Value::Table(top))
+    }
+
+    /// Replace the `secret` data.
+    pub fn secret(&mut self, secrets: Option<toml::Table>) {
+        self.secret = Secret { toml: secrets.unwrap_or(toml::Table::new()) };
+        self.needs_write = true
+    }
+
     /// Replace the `pkg` data.
     pub fn pkg(&mut self, pkg_install: &PackageInstall) {
         self.pkg = Pkg::new(pkg_install);
@@ -120,38 +302,72 @@ impl ServiceConfig {
         self.needs_write = true
     }
 
-    /// Replace the `svc` data.
-    pub fn bind(&mut self, bindings: Vec<String>, cl: &CensusList) {
+    /// Replace the `bind` data. The census may have changed since we started, so members of the
+    /// bound groups are checked against the package's binds again; those that don't export what
+    /// the package needs are left out, and recorded in `bind_error`.
+    pub fn bind(&mut self, package: &Package, bindings: Vec<String>, cl: &CensusList) {
         // This is only safe because we will fail the first time if the bindings are badly
         // formatted - so we know we can't fail here.
-        self.bind = Bind::new(bindings, cl).unwrap();
+        let nonconforming = Bind::nonconforming(&package.binds, &bindings, cl).unwrap();
+        let bind_error = if nonconforming.is_empty() {
+            None
+        } else {
+            Some(nonconforming.join("; "))
+        };
+        if bind_error.is_some() && bind_error != self.bind_error {
+            outputln!("Leaving members out of the bind data: {}",
+                      bind_error.as_ref().unwrap());
+        }
+        self.bind_error = bind_error;
+        self.bind = Bind::new(bindings, cl, &package.binds).unwrap();
         self.needs_write = true
     }
 
-    /// Replace the `cfg` data.
+    /// Replace the `cfg` data. If the new tree can't be loaded, or doesn't match the package's
+    /// config schema, the last good tree is kept and the reason is recorded in `cfg_error`.
     pub fn cfg(&mut self, package: &Package) {
-        match Cfg::new(package) {
+        match Cfg::new(package, self.config_dir.as_ref()) {
             Ok(cfg) => {
                 self.cfg = cfg;
+                self.cfg_error = None;
                 self.needs_write = true;
             }
-            Err(e) => outputln!("Failed to write new cfg tree: {}", e),
+            Err(e) => {
+                outputln!("Failed to write new cfg tree, keeping the last good one: {}", e);
+                self.cfg_error = Some(e.to_string());
+            }
         }
     }
 
-    /// Write the configuration to `config.toml`, and render the templated configuration files.
-    pub fn write(&mut self, pkg: &Package) -> Result<bool> {
-        let pi = &pkg.pkg_install;
-        let final_toml = try!(self.to_toml());
-        {
-            let mut last_toml = try!(File::create(pi.svc_path().join("config.toml")));
-            try!(write!(&mut last_toml, "{}", toml::encode_str(&final_toml)));
-        }
+    /// Check gossiped `gossip.toml` contents against the package's config schema, merged over
+    /// the rest of the current `cfg` tree, before they are written to disk.
+    pub fn validate_gossip(&self, package: &Package, body: &str) -> Result<()> {
+        let schema = match try!(ConfigSchema::load(package.path())) {
+            Some(schema) => schema,
+            None => return Ok(()),
+        };
+        let mut toml_parser = toml::Parser::new(body);
+        let toml = try!(toml_parser.parse()
+            .ok_or(sup_error!(Error::TomlParser(toml_parser.errors))));
+        let mut cfg = self.cfg.clone();
+        cfg.gossip = Some(toml::Value::Table(toml));
+        schema.validate(&cfg.to_toml())
+    }
 
+    /// Record why gossiped config was rejected, so it's reported until a new `cfg` tree is
+    /// accepted.
+    pub fn reject_gossip(&mut self, reason: &SupError) {
+        outputln!("Rejecting gossiped {}, keeping the last good one: {}",
+                  GOSSIP_TOML,
+                  reason);
+        self.cfg_error = Some(format!("Rejected gossiped {}: {}", GOSSIP_TOML, reason));
+    }
+
+    /// Render the templated configuration files, returning each file's name and contents.
+    pub fn render(&self, pkg: &Package) -> Result<Vec<(String, String)>> {
+        let pi = &pkg.pkg_install;
         debug!("Registering configuration templates");
-        let mut handlebars = Handlebars::new();
-        // By default, handlebars escapes HTML. We don't want that.
-        handlebars.register_escape_fn(never_escape_fn);
+        let mut handlebars = templating::handlebars();
 
         // Register all the templates; this makes them available as partials!
         // I suspect this will be useful, but I think we'll want to make this
@@ -163,36 +379,166 @@ impl ServiceConfig {
             try!(handlebars.register_template_file(config, &path));
         }
 
-        let final_data = convert::toml_to_json(final_toml);
-        let mut should_restart = false;
+        let final_data = convert::toml_to_json(try!(self.to_template_toml()));
+        let mut rendered = Vec::new();
         for config in config_files {
             debug!("Rendering template {}", &config);
             let template_data = try!(handlebars.render(&config, &final_data));
+            rendered.push((config, template_data));
+        }
+        Ok(rendered)
+    }
+
+    /// Write the configuration to `config.toml`, and render the templated configuration files.
+    /// Returns the files that changed, grouped by the action the package declared for them.
+    ///
+    /// Every file is written to a temporary file and renamed into place, so a service never sees
+    /// a partially written config. The names of the rendered files are kept in the config path;
+    /// files rendered before from templates the package no longer has are removed, and count as
+    /// changed.
+    pub fn write(&mut self, pkg: &Package) -> Result<ConfigChanges> {
+        let pi = &pkg.pkg_install;
+        let final_toml = try!(self.to_toml());
+        try!(write_file(&pi.svc_path().join("config.toml"),
+                        toml::encode_str(&final_toml).as_bytes(),
+                        None));
+
+        let rendered = try!(self.render(pkg));
+        let actions = pkg.config_actions();
+        let perms = pkg.config_perms();
+        let rendered_path = pi.svc_config_path().join(RENDERED_FILES);
+        let rendered_before = try!(read_rendered_files(&rendered_path));
+        let mut changes = ConfigChanges::default();
+        let mut names = Vec::new();
+        for (config, template_data) in rendered {
+            names.push(config.clone());
             let file_hash = try!(crypto::hash::hash_string(&template_data));
             let filename = pi.svc_config_path().join(&config).to_string_lossy().into_owned();
+            if self.config_hash.get(&filename) == Some(&file_hash) {
+                debug!("Configuration {} has not changed", filename);
+                continue;
+            }
             if self.config_hash.contains_key(&filename) {
-                if file_hash == *self.config_hash.get(&filename).unwrap() {
-                    debug!("Configuration {} has not changed; not restarting", filename);
-                    continue;
-                } else {
-                    debug!("Configuration {} has changed; restarting", filename);
-                    outputln!("Updated {}", Purple.bold().paint(config));
-                    self.config_hash.insert(filename.clone(), file_hash);
-                    let mut config_file = try!(File::create(&filename));
-                    try!(config_file.write_all(&template_data.into_bytes()));
-                    should_restart = true;
-                }
+                debug!("Configuration {} has changed", filename);
             } else {
-                debug!("Configuration {} does not exist; restarting", filename);
-                outputln!("Updated {}", Purple.bold().paint(config));
-                self.config_hash.insert(filename.clone(), file_hash);
-                let mut config_file = try!(File::create(&filename));
-                try!(config_file.write_all(&template_data.into_bytes()));
-                should_restart = true
+                debug!("Configuration {} does not exist", filename);
             }
+            outputln!("Updated {}", Purple.bold().paint(config.clone()));
+            try!(write_file(Path::new(&filename), template_data.as_bytes(), perms.get(&config)));
+            self.config_hash.insert(filename, file_hash);
+            let action = actions.get(&config).cloned();
+            changes.add(config, action);
+        }
+
+        // Only remove files rendered here before, and the temporary files left behind writing
+        // them; anything else in the config path isn't ours to delete.
+        for entry in try!(fs::read_dir(pi.svc_config_path())) {
+            let entry = try!(entry);
+            if !try!(entry.file_type()).is_file() {
+                continue;
+            }
+            let config = entry.file_name().to_string_lossy().into_owned();
+            if is_temp_file(&config) {
+                let original = config[1..config.len() - 4].to_string();
+                if names.contains(&original) || rendered_before.contains(&original) ||
+                   original == RENDERED_FILES {
+                    try!(fs::remove_file(entry.path()));
+                }
+                continue;
+            }
+            if names.contains(&config) || !rendered_before.contains(&config) {
+                continue;
+            }
+            try!(fs::remove_file(entry.path()));
+            outputln!("Removed {}", Purple.bold().paint(config.clone()));
+            self.config_hash.remove(&entry.path().to_string_lossy().into_owned());
+            let action = actions.get(&config).cloned();
+            changes.add(config, action);
+        }
+        if names != rendered_before {
+            try!(write_file(&rendered_path, names.join("\n").as_bytes(), None));
         }
         self.needs_write = false;
-        Ok(should_restart)
+        Ok(changes)
+    }
+}
+
+/// Write `contents` to `path` atomically: they go to a temporary file beside it, which is synced,
+/// given the declared mode and owner, and renamed over `path`.
+fn write_file(path: &Path, contents: &[u8], config_perm: Option<&ConfigPerm>) -> Result<()> {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => unreachable!(),
+    };
+    let tmp_path = path.with_file_name(format!(".{}.tmp", name));
+    let result = write_temp_file(&tmp_path, contents, config_perm)
+        .and_then(|_| fs::rename(&tmp_path, path).map_err(SupError::from));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+fn write_temp_file(path: &Path, contents: &[u8], config_perm: Option<&ConfigPerm>) -> Result<()> {
+    {
+        let mut file = try!(File::create(path));
+        try!(file.write_all(contents));
+        try!(file.sync_all());
+    }
+    if let Some(config_perm) = config_perm {
+        try!(perm::set_permissions(path, &config_perm.mode));
+        if let Some(ref owner) = config_perm.owner {
+            // Only root can give a file away; otherwise it keeps the supervisor's owner.
+            if hcore::fs::am_i_root() {
+                try!(perm::set_owner(path, owner));
+            } else {
+                debug!("Not running as root; not changing the owner of {} to {}",
+                       path.display(),
+                       owner);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The names of the files rendered into a config path, as recorded in `path`; none if it hasn't
+/// been written yet.
+fn read_rendered_files(path: &Path) -> Result<Vec<String>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut data = String::new();
+    try!(try!(File::open(path)).read_to_string(&mut data));
+    Ok(data.lines().filter(|l| !l.is_empty()).map(String::from).collect())
+}
+
+/// True for the temporary files `write_file` leaves behind if the supervisor dies mid-write.
+fn is_temp_file(name: &str) -> bool {
+    name.starts_with('.') && name.ends_with(".tmp")
+}
+
+/// Secrets gossiped to our service group, decrypted. They are only ever held in memory, so they
+/// are left out of `config.toml`, the encoded `ServiceConfig`, and debug output.
+#[derive(Default)]
+struct Secret {
+    toml: toml::Table,
+}
+
+impl Secret {
+    fn to_toml(&self) -> toml::Value {
+        toml::Value::Table(self.toml.clone())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret {{ {} keys }}", self.toml.len())
+    }
+}
+
+impl Encodable for Secret {
+    fn encode<S: Encoder>(&self, s: &mut S) -> result::Result<(), S::Error> {
+        s.emit_struct("Secret", 0, |_| Ok(()))
     }
 }
 
@@ -202,14 +548,23 @@ struct Bind {
 }
 
 impl Bind {
-    fn new(binding_cfg: Vec<String>, cl: &CensusList) -> Result<Bind> {
+    /// The bind data for each binding. Members of a bound group that don't export what the
+    /// bind's contract needs are left out.
+    fn new(binding_cfg: Vec<String>, cl: &CensusList, contracts: &[BindContract]) -> Result<Bind> {
         let mut top = toml::Table::new();
         let bindings = try!(Bind::split_bindings(binding_cfg));
         for (bind, service_group) in bindings {
             match cl.get(&service_group) {
                 Some(census) => {
+                    let entry = match contracts.iter().find(|c| c.name == bind) {
+                        Some(contract) => {
+                            service_entry_where(census,
+                                                &|ce| missing_exports(contract, ce).is_empty())
+                        }
+                        None => service_entry(census),
+                    };
                     top.insert(format!("has_{}", bind), toml::Value::Boolean(true));
-                    top.insert(bind, toml::Value::Table(service_entry(census)));
+                    top.insert(bind, toml::Value::Table(entry));
                 }
                 None => {
                     top.insert(format!("has_{}", bind), toml::Value::Boolean(false));
@@ -219,6 +574,65 @@ impl Bind {
         Ok(Bind { toml: top })
     }
 
+    /// Check the bindings against the binds the package declared: every required bind is given,
+    /// no undeclared bind is, and every alive member of a bound group we know of exports the
+    /// values the package needs. Packages that declare no binds are not checked.
+    fn validate(contracts: &[BindContract],
+                bindings_cfg: &[String],
+                cl: &CensusList)
+                -> Result<()> {
+        if contracts.is_empty() {
+            return Ok(());
+        }
+        let bindings = try!(Bind::split_bindings(bindings_cfg.to_vec()));
+        for &(ref bind, _) in bindings.iter() {
+            if !contracts.iter().any(|c| c.name == *bind) {
+                return Err(sup_error!(Error::UnknownBind(bind.clone())));
+            }
+        }
+        let missing: Vec<String> = contracts.iter()
+            .filter(|c| !c.optional && !bindings.iter().any(|&(ref b, _)| *b == c.name))
+            .map(|c| c.name.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(sup_error!(Error::MissingBinds(missing)));
+        }
+        match try!(Bind::nonconforming(contracts, &bindings_cfg, cl)).into_iter().next() {
+            Some(reason) => Err(sup_error!(Error::BindContract(reason))),
+            None => Ok(()),
+        }
+    }
+
+    /// Why each alive member of a bound group we know of that doesn't export the values its
+    /// bind needs falls short.
+    fn nonconforming(contracts: &[BindContract],
+                     bindings: &[String],
+                     cl: &CensusList)
+                     -> Result<Vec<String>> {
+        let mut reasons = Vec::new();
+        for (bind, service_group) in try!(Bind::split_bindings(bindings.to_vec())) {
+            let contract = match contracts.iter().find(|c| c.name == bind) {
+                Some(contract) => contract,
+                None => continue,
+            };
+            let census = match cl.get(&service_group) {
+                Some(census) => census,
+                None => continue,
+            };
+            for ce in census.alive_members() {
+                let missing = missing_exports(contract, ce);
+                if !missing.is_empty() {
+                    reasons.push(format!("{} is bound to {}, but {} does not export {}",
+                                         bind,
+                                         service_group,
+                                         ce.hostname,
+                                         missing.join(", ")));
+                }
+            }
+        }
+        Ok(reasons)
+    }
+
     fn split_bindings(bindings: Vec<String>) -> Result<Vec<(String, String)>> {
         let mut bresult = Vec::new();
         for bind in bindings.into_iter() {
@@ -267,16 +681,31 @@ impl Svc {
     }
 }
 
+// The values a member of a bound group doesn't export, out of those its bind needs
+fn missing_exports(contract: &BindContract, ce: &CensusEntry) -> Vec<String> {
+    let exports = ce.exports.clone().unwrap_or(Vec::new());
+    contract.exports.iter().filter(|e| !exports.contains(e)).cloned().collect()
+}
+
 fn service_entry(census: &Census) -> toml::Table {
+    service_entry_where(census, &|_| true)
+}
+
+// The census of a service group, leaving out the members `keep` rejects
+fn service_entry_where(census: &Census, keep: &Fn(&CensusEntry) -> bool) -> toml::Table {
     let service = toml::Value::String(census.service.clone());
     let group = toml::Value::String(census.group.clone());
     let ident = toml::Value::String(census.service_group());
     let me = toml::encode(census.me());
-    let leader = census.get_leader().map(|ce| toml::encode(ce));
-    let mut members: Vec<toml::Value> = Vec::new();
+    let encode = |entries: Vec<&CensusEntry>| -> Vec<toml::Value> {
+        entries.into_iter().filter(|ce| keep(ce)).map(|ce| toml::encode(ce)).collect()
+    };
+    let leader = encode(census.get_leader().into_iter().collect()).pop();
+    let members = encode(census.members_ordered());
+    let alive_members = encode(census.alive_members());
+    let followers = encode(census.followers());
     let mut member_id = toml::Table::new();
-    for (sg, ce) in census.iter() {
-        members.push(toml::encode(ce));
+    for (sg, ce) in census.iter().filter(|&(_, ce)| keep(ce)) {
         member_id.insert(format!("{}", sg), toml::encode(ce));
     }
     let mut result = toml::Table::new();
@@ -288,16 +717,21 @@ fn service_entry(census: &Census) -> toml::Table {
         result.insert("leader".to_string(), l);
     }
     result.insert("members".to_string(), toml::Value::Array(members));
+    result.insert("alive_members".to_string(), toml::Value::Array(alive_members));
+    result.insert("followers".to_string(), toml::Value::Array(followers));
     result.insert("member_id".to_string(), toml::Value::Table(member_id));
     result
 }
 
-#[derive(Debug, RustcEncodable)]
+#[derive(Clone, Debug, RustcEncodable)]
 struct Cfg {
     default: Option<toml::Value>,
     user: Option<toml::Value>,
     gossip: Option<toml::Value>,
     environment: Option<toml::Value>,
+    // Overrides for single keys, rather than whole tables, so they are merged deeply
+    dir: Option<toml::Value>,
+    variables: Option<toml::Value>,
 }
 
 // Shallow merges two toml tables.
@@ -321,21 +755,165 @@ fn toml_merge(left: &toml::Table, right: &toml::Table) -> toml::Table {
     final_map
 }
 
+// Deep merges two toml tables; tables present in both are merged rather than replaced.
+fn toml_deep_merge(left: &toml::Table, right: &toml::Table) -> toml::Table {
+    let mut final_map = left.clone();
+    for (right_key, right_value) in right.iter() {
+        let merged = match (final_map.get(right_key), right_value) {
+            (Some(&toml::Value::Table(ref l)), &toml::Value::Table(ref r)) => {
+                toml::Value::Table(toml_deep_merge(l, r))
+            }
+            _ => right_value.clone(),
+        };
+        final_map.insert(right_key.clone(), merged);
+    }
+    final_map
+}
+
+/// Set the value at a path of keys in a table, creating tables along the way. Each key matches an
+/// existing one regardless of case, or of `-` versus `_`, so `MAX_CLIENTS` finds `max-clients`.
+/// The value is coerced to the type of the value it replaces; `source` names it in errors.
+fn toml_set_path(top: &mut toml::Table,
+                 base: Option<&toml::Table>,
+                 keys: &[String],
+                 raw: &str,
+                 source: &str)
+                 -> Result<()> {
+    let key = match base.and_then(|b| b.keys().find(|k| key_matches(k, &keys[0]))) {
+        Some(existing) => existing.clone(),
+        None => keys[0].to_ascii_lowercase(),
+    };
+    let existing = base.and_then(|b| b.get(&key));
+    if keys.len() == 1 {
+        let value = try!(coerce_value(raw, existing, source));
+        top.insert(key, value);
+        return Ok(());
+    }
+    let base = match existing {
+        Some(&toml::Value::Table(ref t)) => Some(t),
+        Some(_) => return Err(not_a_table(source, &key)),
+        None => None,
+    };
+    let entry = top.entry(key.clone()).or_insert(toml::Value::Table(toml::Table::new()));
+    match *entry {
+        toml::Value::Table(ref mut t) => toml_set_path(t, base, &keys[1..], raw, source),
+        _ => Err(not_a_table(source, &key)),
+    }
+}
+
+fn not_a_table(source: &str, key: &str) -> SupError {
+    sup_error!(Error::InvalidConfig(vec![format!("{}: {} is not a table", source, key)]))
+}
+
+fn key_matches(key: &str, wanted: &str) -> bool {
+    key.replace("-", "_").eq_ignore_ascii_case(&wanted.replace("-", "_"))
+}
+
+/// Coerce a raw string to the type of the value it overrides. Strings, and keys with no value to
+/// override, stay strings; arrays and tables are parsed as inline toml.
+fn coerce_value(raw: &str, existing: Option<&toml::Value>, source: &str) -> Result<toml::Value> {
+    let value = match existing {
+        None |
+        Some(&toml::Value::String(_)) => Some(toml::Value::String(raw.to_string())),
+        Some(&toml::Value::Integer(_)) => raw.trim().parse().ok().map(toml::Value::Integer),
+        Some(&toml::Value::Float(_)) => raw.trim().parse().ok().map(toml::Value::Float),
+        Some(&toml::Value::Boolean(_)) => {
+            match raw.trim().to_ascii_lowercase().as_ref() {
+                "true" => Some(toml::Value::Boolean(true)),
+                "false" => Some(toml::Value::Boolean(false)),
+                _ => None,
+            }
+        }
+        Some(&toml::Value::Datetime(_)) => Some(toml::Value::Datetime(raw.trim().to_string())),
+        Some(e) => {
+            let doc = format!("value = {}", raw);
+            toml::Parser::new(&doc)
+                .parse()
+                .and_then(|mut t| t.remove("value"))
+                .and_then(|v| if v.type_str() == e.type_str() { Some(v) } else { None })
+        }
+    };
+    match (value, existing) {
+        (Some(value), _) => Ok(value),
+        (None, Some(e)) => {
+            Err(sup_error!(Error::InvalidConfig(vec![format!("{}: expected {}, found {:?}",
+                                                             source,
+                                                             e.type_str(),
+                                                             raw)])))
+        }
+        (None, None) => unreachable!(),
+    }
+}
+
 impl Cfg {
-    fn new(pkg: &Package) -> Result<Cfg> {
+    fn new(pkg: &Package, config_dir: Option<&PathBuf>) -> Result<Cfg> {
+        Cfg::from_files(pkg,
+                        &pkg.svc_path().join("user.toml"),
+                        &pkg.svc_path().join(GOSSIP_TOML),
+                        config_dir,
+                        true)
+    }
+
+    /// Loads the `cfg` tree from the given user and gossip config files. With
+    /// `ignore_bad_gossip`, a gossip config that doesn't match the package's config schema is
+    /// left out rather than failing the load.
+    fn from_files(pkg: &Package,
+                  user_toml: &Path,
+                  gossip_toml: &Path,
+                  config_dir: Option<&PathBuf>,
+                  ignore_bad_gossip: bool)
+                  -> Result<Cfg> {
         let mut cfg = Cfg {
             default: None,
             user: None,
             gossip: None,
             environment: None,
+            dir: None,
+            variables: None,
         };
         try!(cfg.load_default(pkg));
-        try!(cfg.load_user(pkg));
-        try!(cfg.load_gossip(pkg));
+        try!(cfg.load_user(user_toml));
+        try!(cfg.load_gossip(gossip_toml));
         try!(cfg.load_environment(pkg));
+        if let Some(config_dir) = config_dir {
+            try!(cfg.load_dir(config_dir));
+        }
+        let prefix = format!("{}__", env_var_name(pkg));
+        try!(cfg.load_variables(&prefix, env::vars()));
+        if let Some(schema) = try!(ConfigSchema::load(pkg.path())) {
+            if let Err(e) = schema.validate(&cfg.to_toml()) {
+                // A gossip.toml persisted before it was checked must not keep the service from
+                // starting; fall back to the config without it.
+                if cfg.gossip.is_none() || !ignore_bad_gossip {
+                    return Err(e);
+                }
+                outputln!("Ignoring {}, it does not match the config schema: {}",
+                          GOSSIP_TOML,
+                          e);
+                cfg.gossip = None;
+                try!(schema.validate(&cfg.to_toml()));
+            }
+        }
         Ok(cfg)
     }
 
+    fn exported(&self, exports: &[(String, String)]) -> BTreeMap<String, String> {
+        let cfg = self.to_toml();
+        let mut exported = BTreeMap::new();
+        for &(ref name, ref key) in exports.iter() {
+            match cfg.lookup(key) {
+                Some(&toml::Value::String(ref value)) => {
+                    exported.insert(name.clone(), value.clone());
+                }
+                Some(value) => {
+                    exported.insert(name.clone(), value.to_string());
+                }
+                None => debug!("Not exporting {}; cfg.{} is not set", name, key),
+            }
+        }
+        exported
+    }
+
     fn to_toml(&self) -> toml::Value {
         let mut left = toml::Table::new();
         if let Some(toml::Value::Table(ref right)) = self.default {
@@ -350,6 +928,12 @@ impl Cfg {
         if let Some(toml::Value::Table(ref right)) = self.environment {
             left = toml_merge(&left, right);
         }
+        if let Some(toml::Value::Table(ref right)) = self.dir {
+            left = toml_deep_merge(&left, right);
+        }
+        if let Some(toml::Value::Table(ref right)) = self.variables {
+            left = toml_deep_merge(&left, right);
+        }
         toml::Value::Table(left)
     }
 
@@ -379,8 +963,8 @@ impl Cfg {
         Ok(())
     }
 
-    fn load_user(&mut self, pkg: &Package) -> Result<()> {
-        let mut file = match File::open(pkg.svc_path().join("user.toml")) {
+    fn load_user(&mut self, path: &Path) -> Result<()> {
+        let mut file = match File::open(path) {
             Ok(file) => file,
             Err(e) => {
                 debug!("Failed to open user.toml: {}", e);
@@ -404,8 +988,8 @@ impl Cfg {
         Ok(())
     }
 
-    fn load_gossip(&mut self, pkg: &Package) -> Result<()> {
-        let mut file = match File::open(pkg.svc_path().join(GOSSIP_TOML)) {
+    fn load_gossip(&mut self, path: &Path) -> Result<()> {
+        let mut file = match File::open(path) {
             Ok(file) => file,
             Err(e) => {
                 debug!("Failed to open {}: {}", GOSSIP_TOML, e);
@@ -430,9 +1014,7 @@ impl Cfg {
     }
 
     fn load_environment(&mut self, pkg: &Package) -> Result<()> {
-        let var_name = format!("{}_{}", ENV_VAR_PREFIX, pkg.name)
-            .to_ascii_uppercase()
-            .replace("-", "_");
+        let var_name = env_var_name(pkg);
         match env::var(&var_name) {
             Ok(config) => {
                 let mut toml_parser = toml::Parser::new(&config);
@@ -450,6 +1032,76 @@ impl Cfg {
         };
         Ok(())
     }
+
+    /// Load overrides from a directory of files, as a mounted Kubernetes secret or config map
+    /// gives us. Each file is named for a dotted key path, like `db.port`, and holds its value.
+    /// Hidden files are skipped, which includes the bookkeeping Kubernetes keeps there.
+    fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let base = self.to_toml();
+        let mut top = toml::Table::new();
+        let mut names = Vec::new();
+        for entry in try!(fs::read_dir(dir)) {
+            let entry = try!(entry);
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Follow symlinks; Kubernetes links every key to a file in a hidden directory
+            if name.starts_with('.') || !try!(fs::metadata(entry.path())).is_file() {
+                continue;
+            }
+            names.push(name);
+        }
+        names.sort();
+        for name in names {
+            let mut raw = String::new();
+            try!(try!(File::open(dir.join(&name))).read_to_string(&mut raw));
+            if raw.ends_with('\n') {
+                raw.pop();
+                if raw.ends_with('\r') {
+                    raw.pop();
+                }
+            }
+            let keys: Vec<String> = name.split('.').map(|k| k.to_string()).collect();
+            let source = dir.join(&name).to_string_lossy().into_owned();
+            try!(set_override(&mut top, &base, &keys, &raw, &source));
+        }
+        self.dir = Some(toml::Value::Table(top));
+        Ok(())
+    }
+
+    /// Load overrides from variables named for a key path, with each key after `prefix`
+    /// separated by `__`; `HAB_REDIS__DB__PORT=5432` sets `db.port`.
+    fn load_variables<I>(&mut self, prefix: &str, vars: I) -> Result<()>
+        where I: Iterator<Item = (String, String)>
+    {
+        let base = self.to_toml();
+        let mut top = toml::Table::new();
+        let mut vars: Vec<(String, String)> = vars.filter(|&(ref k, _)| k.starts_with(prefix))
+            .collect();
+        vars.sort();
+        for (name, raw) in vars {
+            let keys: Vec<String> =
+                name[prefix.len()..].split("__").map(|k| k.to_string()).collect();
+            try!(set_override(&mut top, &base, &keys, &raw, &name));
+        }
+        self.variables = Some(toml::Value::Table(top));
+        Ok(())
+    }
+}
+
+fn set_override(top: &mut toml::Table,
+                base: &toml::Value,
+                keys: &[String],
+                raw: &str,
+                source: &str)
+                -> Result<()> {
+    if keys.iter().any(|k| k.is_empty()) {
+        return Err(sup_error!(Error::InvalidConfig(vec![format!("{}: empty key", source)])));
+    }
+    toml_set_path(top, base.as_table(), keys, raw, source)
+}
+
+/// The variable holding a toml document of config for a package, like `HAB_REDIS`.
+fn env_var_name(pkg: &Package) -> String {
+    format!("{}_{}", ENV_VAR_PREFIX, pkg.name).to_ascii_uppercase().replace("-", "_")
 }
 
 #[derive(Debug, RustcEncodable)]
@@ -461,6 +1113,12 @@ pub struct Pkg {
     pub ident: String,
     pub deps: Vec<Pkg>,
     pub exposes: Vec<String>,
+    pub licenses: Vec<String>,
+    pub source: Option<String>,
+    pub shasum: Option<String>,
+    pub build_host: Option<String>,
+    pub builder_version: Option<String>,
+    pub plan_commit: Option<String>,
     pub path: String,
     pub svc_path: String,
     pub svc_config_path: String,
@@ -499,6 +1157,15 @@ impl Pkg {
                 Vec::new()
             }
         };
+        let licenses: Vec<String> = match pkg_install.licenses() {
+            Ok(licenses) => licenses,
+            Err(_) => {
+                outputln!("Failed to load license metadata for {} - \
+                          it will be missing from the configuration",
+                          &ident);
+                Vec::new()
+            }
+        };
         let version = match ident.version.as_ref() {
             Some(v) => v.clone(),
             None => "".to_string(),
@@ -515,6 +1182,14 @@ impl Pkg {
             ident: ident.to_string(),
             deps: deps,
             exposes: exposes,
+            licenses: licenses,
+            source: optional_metadata(ident, "source", pkg_install.source()),
+            shasum: optional_metadata(ident, "shasum", pkg_install.shasum()),
+            build_host: optional_metadata(ident, "build host", pkg_install.build_host()),
+            builder_version: optional_metadata(ident,
+                                               "builder version",
+                                               pkg_install.builder_version()),
+            plan_commit: optional_metadata(ident, "plan commit", pkg_install.plan_commit()),
             path: pkg_install.installed_path().to_string_lossy().into_owned(),
             svc_path: pkg_install.svc_path().to_string_lossy().into_owned(),
             svc_config_path: pkg_install.svc_config_path().to_string_lossy().into_owned(),
@@ -533,6 +1208,22 @@ impl Pkg {
     }
 }
 
+fn optional_metadata(ident: &PackageIdent,
+                     name: &str,
+                     result: hcore::error::Result<Option<String>>)
+                     -> Option<String> {
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            outputln!("Failed to load {} metadata for {} - it will be missing from the \
+                      configuration",
+                      name,
+                      ident);
+            None
+        }
+    }
+}
+
 #[derive(Debug, RustcEncodable)]
 pub struct Sys {
     pub ip: String,
@@ -599,13 +1290,17 @@ impl Hab {
 
 #[cfg(test)]
 mod test {
+    use std::fs::File;
+    use std::io::prelude::*;
     use std::path::PathBuf;
     use std::str::FromStr;
 
     use regex::Regex;
+    use tempdir::TempDir;
 
     use census::{CensusEntry, Census, CensusList};
     use config::Config;
+    use error::{Error, SupError};
     use gossip::member::MemberId;
     use hcore::package::{PackageIdent, PackageInstall};
     use package::Package;
@@ -625,6 +1320,7 @@ mod test {
             release: String::from("20160222201258"),
             deps: Vec::new(),
             tdeps: Vec::new(),
+            binds: Vec::new(),
             pkg_install: pkg_install,
         }
     }
@@ -655,6 +1351,48 @@ mod test {
         assert_eq!(name, "sovereign");
     }
 
+    #[test]
+    fn from_files_missing_file() {
+        let pkg = gen_pkg();
+        let cl = gen_census_list();
+        let dir = TempDir::new("render").unwrap();
+        let missing = dir.path().join("user.toml");
+        match ServiceConfig::from_files(&Config::default(),
+                                        &pkg,
+                                        &cl,
+                                        Vec::new(),
+                                        Some(missing.as_path()),
+                                        None) {
+            Err(SupError { err: Error::FileNotFound(_), .. }) => (),
+            Err(e) => panic!("Unexpected error {}", e),
+            Ok(_) => panic!("A missing user.toml should not be skipped"),
+        }
+    }
+
+    #[test]
+    fn from_files_gossip_not_matching_schema() {
+        let dir = TempDir::new("render").unwrap();
+        File::create(dir.path().join("schema.toml"))
+            .unwrap()
+            .write_all(b"[keys.port]\ntype = \"integer\"\n")
+            .unwrap();
+        let gossip_toml = dir.path().join("gossip.toml");
+        File::create(&gossip_toml).unwrap().write_all(b"port = \"eighty\"\n").unwrap();
+        let mut pkg = gen_pkg();
+        pkg.pkg_install = PackageInstall::new_from_parts(pkg.pkg_install.ident().clone(),
+                                                         dir.path().to_path_buf(),
+                                                         dir.path().to_path_buf(),
+                                                         dir.path().to_path_buf());
+        let cl = gen_census_list();
+        assert!(ServiceConfig::from_files(&Config::default(),
+                                          &pkg,
+                                          &cl,
+                                          Vec::new(),
+                                          None,
+                                          Some(gossip_toml.as_path()))
+            .is_err());
+    }
+
     #[test]
     fn to_toml_sys() {
         let pkg = gen_pkg();
@@ -713,4 +1451,265 @@ mod test {
             assert_eq!(version, VERSION);
         }
     }
+    mod config_changes {
+        use service_config::{ConfigAction, ConfigChanges, ConfigPerm};
+
+        #[test]
+        fn action_from_str() {
+            assert_eq!(ConfigAction::from_str("restart"), Some(ConfigAction::Restart));
+            assert_eq!(ConfigAction::from_str("reconfigure"),
+                       Some(ConfigAction::Reconfigure));
+            assert_eq!(ConfigAction::from_str("none"), Some(ConfigAction::Nothing));
+            assert_eq!(ConfigAction::from_str("reload"), None);
+        }
+
+        #[test]
+        fn needs_action() {
+            let mut changes = ConfigChanges::default();
+            assert!(!changes.needs_action());
+            changes.add(String::from("mime.types"), Some(ConfigAction::Nothing));
+            assert!(!changes.needs_action());
+            changes.add(String::from("nginx.conf"), Some(ConfigAction::Reconfigure));
+            assert!(changes.needs_action());
+            changes.add(String::from("fastcgi.conf"), None);
+            assert_eq!(changes.changed(),
+                       vec!["fastcgi.conf", "mime.types", "nginx.conf"]);
+        }
+
+        #[test]
+        fn to_reconfigure() {
+            let mut changes = ConfigChanges::default();
+            changes.add(String::from("nginx.conf"), Some(ConfigAction::Restart));
+            changes.add(String::from("mime.types"), Some(ConfigAction::Nothing));
+            assert!(changes.to_reconfigure().is_empty());
+            changes.add(String::from("upstream.conf"), Some(ConfigAction::Reconfigure));
+            changes.add(String::from("fastcgi.conf"), None);
+            assert_eq!(changes.to_reconfigure(), vec!["fastcgi.conf", "upstream.conf"]);
+        }
+
+        #[test]
+        fn perm_from_str() {
+            assert_eq!(ConfigPerm::from_str("0640"),
+                       Some(ConfigPerm {
+                           mode: String::from("0640"),
+                           owner: None,
+                       }));
+            assert_eq!(ConfigPerm::from_str("600:hab:hab"),
+                       Some(ConfigPerm {
+                           mode: String::from("600"),
+                           owner: Some(String::from("hab:hab")),
+                       }));
+            assert_eq!(ConfigPerm::from_str("0640:hab").unwrap().owner,
+                       Some(String::from("hab")));
+            assert_eq!(ConfigPerm::from_str("0648"), None);
+            assert_eq!(ConfigPerm::from_str("rw-r-----"), None);
+            assert_eq!(ConfigPerm::from_str("0640:"), None);
+            assert_eq!(ConfigPerm::from_str("0640:hab:"), None);
+            assert_eq!(ConfigPerm::from_str("0640:hab:hab:hab"), None);
+        }
+    }
+
+    mod binds {
+        use census::{Census, CensusEntry, CensusList};
+        use gossip::member::MemberId;
+        use toml;
+
+        use package::BindContract;
+        use service_config::Bind;
+
+        fn contracts() -> Vec<BindContract> {
+            vec![BindContract {
+                     name: String::from("database"),
+                     exports: vec![String::from("port")],
+                     optional: false,
+                 },
+                 BindContract {
+                     name: String::from("cache"),
+                     exports: Vec::new(),
+                     optional: true,
+                 }]
+        }
+
+        fn census_list(exports: Option<Vec<String>>) -> CensusList {
+            let mut cl = CensusList::new(Census::new(CensusEntry::new("web",
+                                                                      "default",
+                                                                      MemberId::new_v4())));
+            let mut ce = CensusEntry::new("postgresql", "default", MemberId::new_v4());
+            ce.exports(exports);
+            cl.insert_entry(ce);
+            cl
+        }
+
+        #[test]
+        fn required_and_optional() {
+            let cl = census_list(Some(vec![String::from("port")]));
+            let bindings = vec![String::from("database:postgresql.default")];
+            assert!(Bind::validate(&contracts(), &bindings, &cl).is_ok());
+            assert!(Bind::validate(&contracts(), &Vec::new(), &cl).is_err());
+        }
+
+        #[test]
+        fn unknown_bind() {
+            let cl = census_list(Some(vec![String::from("port")]));
+            let bindings = vec![String::from("database:postgresql.default"),
+                                String::from("queue:rabbitmq.default")];
+            assert!(Bind::validate(&contracts(), &bindings, &cl).is_err());
+            assert!(Bind::validate(&Vec::new(), &bindings, &cl).is_ok());
+        }
+
+        #[test]
+        fn missing_exports() {
+            let bindings = vec![String::from("database:postgresql.default")];
+            assert!(Bind::validate(&contracts(), &bindings, &census_list(None)).is_err());
+            let cl = census_list(Some(vec![String::from("host")]));
+            assert!(Bind::validate(&contracts(), &bindings, &cl).is_err());
+        }
+
+        #[test]
+        fn unknown_group() {
+            let cl = census_list(None);
+            let bindings = vec![String::from("database:postgresql.prod")];
+            assert!(Bind::validate(&contracts(), &bindings, &cl).is_ok());
+        }
+
+        #[test]
+        fn nonconforming_members_are_left_out() {
+            let mut cl = census_list(Some(vec![String::from("port")]));
+            let mut ce = CensusEntry::new("postgresql", "default", MemberId::new_v4());
+            ce.exports(Some(vec![String::from("host")]));
+            cl.insert_entry(ce);
+            let bindings = vec![String::from("database:postgresql.default")];
+            assert_eq!(Bind::nonconforming(&contracts(), &bindings, &cl).unwrap().len(), 1);
+            let bind = Bind::new(bindings, &cl, &contracts()).unwrap().to_toml();
+            let members = bind.lookup("database.alive_members").unwrap().as_slice().unwrap();
+            assert_eq!(members.len(), 1);
+            assert_eq!(members[0].lookup("exports").unwrap().as_slice().unwrap(),
+                       &[toml::Value::String(String::from("port"))]);
+        }
+    }
+
+    mod overrides {
+        use std::fs::File;
+        use std::io::prelude::*;
+
+        use tempdir::TempDir;
+        use toml;
+
+        use service_config::Cfg;
+
+        fn cfg() -> Cfg {
+            let default = "port = 6379\n[db]\nhost = \"localhost\"\nmax-conns = 10\ntls = false\n";
+            Cfg {
+                default: Some(toml::Value::Table(toml::Parser::new(default).parse().unwrap())),
+                user: None,
+                gossip: None,
+                environment: None,
+                dir: None,
+                variables: None,
+            }
+        }
+
+        fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+            vars.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect()
+        }
+
+        #[test]
+        fn variables() {
+            let mut cfg = cfg();
+            cfg.load_variables("HAB_REDIS__",
+                                vars(&[("HAB_REDIS__PORT", "6380"),
+                                       ("HAB_REDIS__DB__MAX_CONNS", "20"),
+                                       ("HAB_REDIS__DB__TLS", "TRUE"),
+                                       ("HAB_REDIS__NAME", "cache"),
+                                       ("HAB_POSTGRES__PORT", "5432")])
+                                    .into_iter())
+                .unwrap();
+            let toml = cfg.to_toml();
+            assert_eq!(toml.lookup("port").unwrap().as_integer(), Some(6380));
+            assert_eq!(toml.lookup("db.host").unwrap().as_str(), Some("localhost"));
+            assert_eq!(toml.lookup("db.max-conns").unwrap().as_integer(), Some(20));
+            assert_eq!(toml.lookup("db.tls").unwrap().as_bool(), Some(true));
+            assert_eq!(toml.lookup("name").unwrap().as_str(), Some("cache"));
+        }
+
+        #[test]
+        fn exported() {
+            let exports = vec![(String::from("port"), String::from("port")),
+                               (String::from("host"), String::from("db.host")),
+                               (String::from("user"), String::from("db.user"))];
+            let exported = cfg().exported(&exports);
+            assert_eq!(exported.get("port"), Some(&String::from("6379")));
+            assert_eq!(exported.get("host"), Some(&String::from("localhost")));
+            assert_eq!(exported.get("user"), None);
+        }
+
+        #[test]
+        fn variables_of_the_wrong_type() {
+            for var in &[("HAB_REDIS__PORT", "high"),
+                         ("HAB_REDIS__PORT__MAX", "1"),
+                         ("HAB_REDIS__DB____TLS", "true")] {
+                let mut cfg = cfg();
+                assert!(cfg.load_variables("HAB_REDIS__", vars(&[*var]).into_iter()).is_err());
+            }
+        }
+
+        #[test]
+        fn dir() {
+            let dir = TempDir::new("config_dir").unwrap();
+            let files = [("port", "6380\n"), ("db.host", "db.internal\n"), (".hidden", "x")];
+            for &(name, value) in files.iter() {
+                let mut file = File::create(dir.path().join(name)).unwrap();
+                file.write_all(value.as_bytes()).unwrap();
+            }
+            let mut cfg = cfg();
+            cfg.load_dir(dir.path()).unwrap();
+            let toml = cfg.to_toml();
+            assert_eq!(toml.lookup("port").unwrap().as_integer(), Some(6380));
+            assert_eq!(toml.lookup("db.host").unwrap().as_str(), Some("db.internal"));
+            assert_eq!(toml.lookup("db.tls").unwrap().as_bool(), Some(false));
+            assert!(toml.as_table().unwrap().get("").is_none());
+        }
+    }
+
+    mod write_file {
+        use std::fs::{self, File};
+        use std::io::prelude::*;
+
+        use tempdir::TempDir;
+
+        use service_config::{is_temp_file, read_rendered_files, write_file};
+
+        #[test]
+        fn replaces_contents() {
+            let dir = TempDir::new("write_file").unwrap();
+            let path = dir.path().join("redis.conf");
+            write_file(&path, b"port 6379", None).unwrap();
+            write_file(&path, b"port 6380", None).unwrap();
+            let mut contents = String::new();
+            File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+            assert_eq!(contents, "port 6380");
+            let names: Vec<String> = fs::read_dir(dir.path())
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+                .collect();
+            assert_eq!(names, vec!["redis.conf"]);
+        }
+
+        #[test]
+        fn temp_files() {
+            assert!(is_temp_file(".redis.conf.tmp"));
+            assert!(!is_temp_file("redis.conf"));
+            assert!(!is_temp_file("backup.tmp"));
+        }
+
+        #[test]
+        fn rendered_files() {
+            let dir = TempDir::new("write_file").unwrap();
+            let path = dir.path().join(".rendered");
+            assert!(read_rendered_files(&path).unwrap().is_empty());
+            write_file(&path, b"redis.conf\nsentinel.conf", None).unwrap();
+            assert_eq!(read_rendered_files(&path).unwrap(),
+                       vec!["redis.conf", "sentinel.conf"]);
+        }
+    }
 }
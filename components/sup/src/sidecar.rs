@@ -18,6 +18,7 @@
 //! Supports:
 //!
 //! * /config: Returns the current configuration of the service
+//! * /config/errors: Returns why the most recent configuration or binds were rejected, if they were
 //! * /health: Returns the current health of the service
 //! * /status: Returns the current status of the service, from the supervisors point of view
 
@@ -47,6 +48,7 @@ use supervisor::Supervisor;
 static LOGKEY: &'static str = "SI";
 const GET_HEALTH: &'static str = "/health";
 const GET_CONFIG: &'static str = "/config";
+const GET_CONFIG_ERRORS: &'static str = "/config/errors";
 const GET_STATUS: &'static str = "/status";
 const GET_GOSSIP: &'static str = "/gossip";
 const GET_CENSUS: &'static str = "/census";
@@ -150,8 +152,11 @@ impl GenServer for Sidecar {
         let package_1 = state.package.clone();
         let package_2 = state.package.clone();
         let config_1 = state.config.clone();
+        let config_2 = state.config.clone();
 
         router.get(GET_CONFIG, move |r: &mut Request| config(&package_1, r));
+        router.get(GET_CONFIG_ERRORS,
+                   move |r: &mut Request| config_errors(&config_2, r));
 
         let supervisor_1 = state.supervisor.clone();
         router.get(GET_STATUS, move |r: &mut Request| status(&supervisor_1, r));
@@ -294,6 +299,38 @@ fn config(lock: &Arc<RwLock<Package>>, _req: &mut Request) -> IronResult<Respons
     Ok(Response::with((status::Ok, last_config)))
 }
 
+#[derive(Debug, RustcEncodable)]
+struct ConfigErrorsResponse<'a> {
+    rejected: bool,
+    error: Option<&'a String>,
+    bind_error: Option<&'a String>,
+}
+
+impl<'a> ConfigErrorsResponse<'a> {
+    fn new(service_config: &'a ServiceConfig) -> Self {
+        ConfigErrorsResponse {
+            rejected: service_config.cfg_error.is_some(),
+            error: service_config.cfg_error.as_ref(),
+            bind_error: service_config.bind_error.as_ref(),
+        }
+    }
+}
+
+/// The /config/errors callback.
+///
+/// Returns why the most recently applied configuration was rejected, in which case the service
+/// keeps running with the last good configuration, and which members of bound groups were left
+/// out of the bind data for not meeting the package's binds.
+fn config_errors(lock: &Arc<RwLock<ServiceConfig>>, _req: &mut Request) -> IronResult<Response> {
+    let service_config = lock.read().unwrap();
+    let json_response = match json::encode(&ConfigErrorsResponse::new(&service_config)) {
+        Ok(json_response) => json_response,
+        Err(e) => return Err(IronError::from(sup_error!(Error::JsonEncode(e)))),
+    };
+
+    Ok(Response::with((status::Ok, json_response)))
+}
+
 /// The /status callback.
 ///
 /// Returns the current status from the supervisors perspective.
@@ -348,3 +385,72 @@ impl From<SupError> for IronError {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::prelude::*;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use rustc_serialize::json::{self, Json};
+    use tempdir::TempDir;
+
+    use census::{Census, CensusEntry, CensusList};
+    use config::Config;
+    use gossip::member::MemberId;
+    use hcore::package::{PackageIdent, PackageInstall};
+    use package::Package;
+    use service_config::ServiceConfig;
+    use super::ConfigErrorsResponse;
+
+    fn gen_pkg(path: &Path) -> Package {
+        let pkg_install = PackageInstall::new_from_parts(
+            PackageIdent::from_str("neurosis/sovereign/2000/20160222201258").unwrap(),
+            path.to_path_buf(),
+            path.to_path_buf(),
+            path.to_path_buf());
+        Package {
+            origin: String::from("neurosis"),
+            name: String::from("sovereign"),
+            version: String::from("2000"),
+            release: String::from("20160222201258"),
+            deps: Vec::new(),
+            tdeps: Vec::new(),
+            binds: Vec::new(),
+            pkg_install: pkg_install,
+        }
+    }
+
+    fn write_file(path: &Path, content: &str) {
+        File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    fn config_errors(service_config: &ServiceConfig) -> Json {
+        let response = json::encode(&ConfigErrorsResponse::new(service_config)).unwrap();
+        Json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn config_errors_reports_rejected_gossip() {
+        let dir = TempDir::new("sovereign").unwrap();
+        write_file(&dir.path().join("default.toml"), "port = 80\n");
+        write_file(&dir.path().join("schema.toml"), "[keys.port]\ntype = \"integer\"\n");
+        let pkg = gen_pkg(dir.path());
+        let cl = CensusList::new(Census::new(CensusEntry::new("sovereign",
+                                                              "default",
+                                                              MemberId::new_v4())));
+        let mut sc = ServiceConfig::new(&Config::default(), &pkg, &cl, Vec::new()).unwrap();
+        assert_eq!(config_errors(&sc).find("rejected"), Some(&Json::Boolean(false)));
+
+        let e = sc.validate_gossip(&pkg, "port = \"eighty\"").unwrap_err();
+        sc.reject_gossip(&e);
+        let errors = config_errors(&sc);
+        assert_eq!(errors.find("rejected"), Some(&Json::Boolean(true)));
+        assert!(errors.find("error").and_then(|e| e.as_string()).unwrap().contains("cfg.port"));
+
+        // The next accepted cfg tree clears it
+        sc.cfg(&pkg);
+        assert_eq!(config_errors(&sc).find("rejected"), Some(&Json::Boolean(false)));
+    }
+}
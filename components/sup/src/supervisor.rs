@@ -34,7 +34,6 @@ use time::{Duration, SteadyTime};
 use error::{Result, Error};
 use util::signals;
 
-const PIDFILE_NAME: &'static str = "PID";
 static LOGKEY: &'static str = "SV";
 
 // Functions from POSIX libc.
@@ -319,7 +318,13 @@ impl Supervisor {
     }
 
     pub fn pid_file(&self) -> PathBuf {
-        self.service_dir().join(PIDFILE_NAME)
+        hcore::fs::svc_pid_file(&self.package_ident.name)
+    }
+
+    /// The file naming the package release the service runs from, so that commands outside the
+    /// Supervisor can tell which packages are in use.
+    pub fn pkg_ident_file(&self) -> PathBuf {
+        hcore::fs::svc_pkg_ident_file(&self.package_ident.name)
     }
 
     /// Create a pid file for a package
@@ -334,6 +339,8 @@ impl Supervisor {
                        pid);
                 let mut f = try!(File::create(pid_file));
                 try!(write!(f, "{}", pid));
+                let mut f = try!(File::create(self.pkg_ident_file()));
+                try!(write!(f, "{}", self.package_ident));
                 Ok(())
             }
             None => Ok(()),
@@ -353,6 +360,9 @@ impl Supervisor {
                 debug!("Error removing pidfile: {}, continuing", e);
             }
         };
+        if let Err(e) = fs::remove_file(self.pkg_ident_file()) {
+            debug!("Error removing package ident file: {}, continuing", e);
+        }
     }
 
     /// attempt to read the pidfile for this package.
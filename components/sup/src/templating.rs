@@ -0,0 +1,344 @@
+// Copyright (c) 2016 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Handlebars registry used to render configuration files and hooks, along with the helpers
+//! available to their templates.
+//!
+//! * `{{toJson cfg.section}}`, `{{toToml cfg.section}}`, `{{toYaml cfg.section}}`: serialize a
+//!   subtree
+//! * `{{upper s}}`, `{{lower s}}`: change the case of a string
+//! * `{{replace s "from" "to"}}`: replace every occurrence of `from` with `to`
+//! * `{{join list ","}}`: join a list with a separator
+//! * `{{strConcat a b ...}}`: concatenate values
+//! * `{{pkgPathFor "core/openssl"}}`: the install path of one of the package's dependencies
+//! * `{{#eachAlive svc.members}}...{{else}}...{{/eachAlive}}`: iterate over the census members
+//!   that are alive. Inside the block, the member is the context.
+
+use std::io::Write;
+use std::iter;
+use std::str::FromStr;
+
+use handlebars::{Context, Handlebars, Helper, RenderContext, RenderError, Renderable};
+use hcore::package::{Identifiable, PackageIdent};
+use rustc_serialize::json::Json;
+
+use util::convert;
+
+/// By default, handlebars escapes HTML. We don't want that.
+pub fn never_escape_fn(data: &str) -> String {
+    String::from(data)
+}
+
+/// Returns a Handlebars registry with our escape function and helpers registered.
+pub fn handlebars() -> Handlebars {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(never_escape_fn);
+    handlebars.register_helper("toJson", Box::new(to_json));
+    handlebars.register_helper("toToml", Box::new(to_toml));
+    handlebars.register_helper("toYaml", Box::new(to_yaml));
+    handlebars.register_helper("upper", Box::new(upper));
+    handlebars.register_helper("lower", Box::new(lower));
+    handlebars.register_helper("replace", Box::new(replace));
+    handlebars.register_helper("join", Box::new(join));
+    handlebars.register_helper("strConcat", Box::new(str_concat));
+    handlebars.register_helper("pkgPathFor", Box::new(pkg_path_for));
+    handlebars.register_helper("eachAlive", Box::new(each_alive));
+    handlebars
+}
+
+fn param<'a>(h: &'a Helper, idx: usize) -> Result<&'a Json, RenderError> {
+    match h.param(idx) {
+        Some(param) => Ok(param.value()),
+        None => {
+            Err(RenderError::new(format!("Parameter {} not found for helper \"{}\"",
+                                         idx,
+                                         h.name())))
+        }
+    }
+}
+
+fn string_param(h: &Helper, idx: usize) -> Result<String, RenderError> {
+    match *try!(param(h, idx)) {
+        Json::String(ref s) => Ok(s.clone()),
+        ref other => {
+            Err(RenderError::new(format!("Parameter {} for helper \"{}\" must be a string, \
+                                          found {}",
+                                         idx,
+                                         h.name(),
+                                         other)))
+        }
+    }
+}
+
+/// Renders a value the way it would appear in a template.
+fn render_value(value: &Json) -> String {
+    match *value {
+        Json::String(ref s) => s.clone(),
+        Json::Null => String::new(),
+        ref other => other.to_string(),
+    }
+}
+
+fn write(rc: &mut RenderContext, output: &str) -> Result<(), RenderError> {
+    try!(rc.writer.write_all(output.as_bytes()));
+    Ok(())
+}
+
+fn to_json(_: &Context,
+           h: &Helper,
+           _: &Handlebars,
+           rc: &mut RenderContext)
+          -> Result<(), RenderError> {
+    let value = try!(param(h, 0));
+    write(rc, &value.pretty().to_string())
+}
+
+fn to_toml(_: &Context,
+           h: &Helper,
+           _: &Handlebars,
+           rc: &mut RenderContext)
+          -> Result<(), RenderError> {
+    let value = try!(param(h, 0));
+    write(rc, &convert::json_to_toml(value).to_string())
+}
+
+fn to_yaml(_: &Context,
+           h: &Helper,
+           _: &Handlebars,
+           rc: &mut RenderContext)
+          -> Result<(), RenderError> {
+    let value = try!(param(h, 0));
+    let mut yaml = String::new();
+    yaml_value(value, 0, &mut yaml);
+    write(rc, yaml.trim_right())
+}
+
+/// Appends the YAML for a value. Collections are written in block style on the following lines;
+/// scalars are written inline, with strings as JSON strings, which YAML also accepts.
+fn yaml_value(value: &Json, indent: usize, yaml: &mut String) {
+    let pad: String = iter::repeat("  ").take(indent).collect();
+    match *value {
+        Json::Object(ref o) if !o.is_empty() => {
+            for (key, v) in o.iter() {
+                yaml.push_str(&format!("{}{}:", pad, Json::String(key.clone())));
+                yaml_child(v, indent, yaml);
+            }
+        }
+        Json::Array(ref a) if !a.is_empty() => {
+            for v in a.iter() {
+                yaml.push_str(&format!("{}-", pad));
+                yaml_child(v, indent, yaml);
+            }
+        }
+        Json::Object(_) => yaml.push_str(&format!("{}{{}}\n", pad)),
+        Json::Array(_) => yaml.push_str(&format!("{}[]\n", pad)),
+        Json::Null => yaml.push_str(&format!("{}null\n", pad)),
+        ref scalar => yaml.push_str(&format!("{}{}\n", pad, scalar)),
+    }
+}
+
+fn yaml_child(value: &Json, indent: usize, yaml: &mut String) {
+    match *value {
+        Json::Object(ref o) if !o.is_empty() => {
+            yaml.push('\n');
+            yaml_value(value, indent + 1, yaml);
+        }
+        Json::Array(ref a) if !a.is_empty() => {
+            yaml.push('\n');
+            yaml_value(value, indent + 1, yaml);
+        }
+        _ => {
+            yaml.push(' ');
+            yaml_value(value, 0, yaml);
+        }
+    }
+}
+
+fn upper(_: &Context,
+         h: &Helper,
+         _: &Handlebars,
+         rc: &mut RenderContext)
+        -> Result<(), RenderError> {
+    let s = try!(string_param(h, 0));
+    write(rc, &s.to_uppercase())
+}
+
+fn lower(_: &Context,
+         h: &Helper,
+         _: &Handlebars,
+         rc: &mut RenderContext)
+        -> Result<(), RenderError> {
+    let s = try!(string_param(h, 0));
+    write(rc, &s.to_lowercase())
+}
+
+fn replace(_: &Context,
+           h: &Helper,
+           _: &Handlebars,
+           rc: &mut RenderContext)
+          -> Result<(), RenderError> {
+    let s = try!(string_param(h, 0));
+    let from = try!(string_param(h, 1));
+    let to = try!(string_param(h, 2));
+    write(rc, &s.replace(&from, &to))
+}
+
+fn join(_: &Context,
+        h: &Helper,
+        _: &Handlebars,
+        rc: &mut RenderContext)
+       -> Result<(), RenderError> {
+    let list = match *try!(param(h, 0)) {
+        Json::Array(ref list) => list.iter().map(render_value).collect::<Vec<String>>(),
+        Json::Null => Vec::new(),
+        ref other => vec![render_value(other)],
+    };
+    let separator = match h.param(1) {
+        Some(_) => try!(string_param(h, 1)),
+        None => String::from(","),
+    };
+    write(rc, &list.join(&separator))
+}
+
+fn str_concat(_: &Context,
+              h: &Helper,
+              _: &Handlebars,
+              rc: &mut RenderContext)
+             -> Result<(), RenderError> {
+    let output: String = h.params().iter().map(|p| render_value(p.value())).collect();
+    write(rc, &output)
+}
+
+fn pkg_path_for(c: &Context,
+                h: &Helper,
+                _: &Handlebars,
+                rc: &mut RenderContext)
+               -> Result<(), RenderError> {
+    let wanted = try!(string_param(h, 0));
+    let wanted_ident = match PackageIdent::from_str(&wanted) {
+        Ok(ident) => ident,
+        Err(e) => return Err(RenderError::new(format!("pkgPathFor: {}", e))),
+    };
+    let deps = match c.data().find_path(&["pkg", "deps"]) {
+        Some(&Json::Array(ref deps)) => deps,
+        _ => return Err(RenderError::new("pkgPathFor: no package dependencies to search")),
+    };
+    for dep in deps.iter() {
+        let ident = dep.find("ident")
+            .and_then(|i| i.as_string())
+            .and_then(|i| PackageIdent::from_str(i).ok());
+        if let Some(ident) = ident {
+            if ident.satisfies(&wanted_ident) {
+                let path = dep.find("path").and_then(|p| p.as_string()).unwrap_or("");
+                return write(rc, path);
+            }
+        }
+    }
+    Err(RenderError::new(format!("pkgPathFor: {} is not a dependency of this package", wanted)))
+}
+
+fn is_alive(member: &Json) -> bool {
+    match member.find("alive") {
+        Some(&Json::Boolean(alive)) => alive,
+        _ => false,
+    }
+}
+
+fn each_alive(c: &Context,
+              h: &Helper,
+              r: &Handlebars,
+              rc: &mut RenderContext)
+             -> Result<(), RenderError> {
+    let alive: Vec<&Json> = match *try!(param(h, 0)) {
+        Json::Array(ref members) => members.iter().filter(|m| is_alive(m)).collect(),
+        Json::Object(ref members) => members.values().filter(|m| is_alive(m)).collect(),
+        Json::Null => Vec::new(),
+        _ => return Err(RenderError::new("eachAlive: expected a list of census members")),
+    };
+    if alive.is_empty() {
+        return match h.inverse() {
+            Some(t) => t.render(c, r, rc),
+            None => Ok(()),
+        };
+    }
+    if let Some(t) = h.template() {
+        let last = alive.len() - 1;
+        for (i, member) in alive.into_iter().enumerate() {
+            let mut local_rc = rc.derive();
+            local_rc.set_path(String::from("this"));
+            local_rc.set_local_var(String::from("@index"), Json::U64(i as u64));
+            local_rc.set_local_var(String::from("@first"), Json::Boolean(i == 0));
+            local_rc.set_local_var(String::from("@last"), Json::Boolean(i == last));
+            try!(t.render(&Context::wraps(member), r, &mut local_rc));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use rustc_serialize::json::Json;
+
+    use super::handlebars;
+
+    fn render(template: &str, data: &str) -> String {
+        let mut hb = handlebars();
+        hb.register_template_string("t", template.to_string()).unwrap();
+        hb.render("t", &Json::from_str(data).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn string_helpers() {
+        let data = r#"{"name": "Redis", "list": ["a", "b", 3]}"#;
+        assert_eq!(render("{{upper name}}", data), "REDIS");
+        assert_eq!(render("{{lower name}}", data), "redis");
+        assert_eq!(render("{{replace name \"Re\" \"Ra\"}}", data), "Radis");
+        assert_eq!(render("{{join list \", \"}}", data), "a, b, 3");
+        assert_eq!(render("{{strConcat name \"-\" 6379}}", data), "Redis-6379");
+    }
+
+    #[test]
+    fn serialization_helpers() {
+        let data = r#"{"cfg": {"port": 80, "hosts": ["a", "b"], "tls": {"on": true}}}"#;
+        assert_eq!(render("{{toToml cfg.tls}}", data), "on = true\n");
+        assert_eq!(render("{{toJson cfg.hosts}}", data), "[\n  \"a\",\n  \"b\"\n]");
+        assert_eq!(render("{{toYaml cfg}}", data),
+                   "\"hosts\":\n  - \"a\"\n  - \"b\"\n\"port\": 80\n\"tls\":\n  \"on\": true");
+    }
+
+    #[test]
+    fn each_alive() {
+        let data = r#"{"members": [{"ip": "10.0.0.1", "alive": true},
+                                   {"ip": "10.0.0.2", "alive": false},
+                                   {"ip": "10.0.0.3", "alive": true}]}"#;
+        assert_eq!(render("{{#eachAlive members}}{{ip}} {{/eachAlive}}", data),
+                   "10.0.0.1 10.0.0.3 ");
+        assert_eq!(render("{{#eachAlive nobody}}x{{else}}none{{/eachAlive}}", data),
+                   "none");
+    }
+
+    #[test]
+    fn pkg_path_for() {
+        let data = r#"{"pkg": {"deps": [
+            {"ident": "core/openssl/1.0.2/20160614",
+             "path": "/hab/pkgs/core/openssl/1.0.2/20160614"},
+            {"ident": "core/glibc/2.22/20160612",
+             "path": "/hab/pkgs/core/glibc/2.22/20160612"}]}}"#;
+        assert_eq!(render("{{pkgPathFor \"core/glibc\"}}", data),
+                   "/hab/pkgs/core/glibc/2.22/20160612");
+        let mut hb = handlebars();
+        hb.register_template_string("t", "{{pkgPathFor \"core/zlib\"}}".to_string()).unwrap();
+        assert!(hb.render("t", &Json::from_str(data).unwrap()).is_err());
+    }
+}
@@ -26,6 +26,7 @@ pub mod standalone;
 pub mod leader;
 pub mod initializer;
 
+use std::collections::BTreeMap;
 use std::mem;
 use std::net::SocketAddrV4;
 use std::ops::DerefMut;
@@ -39,12 +40,12 @@ use wonder;
 
 use state_machine::StateMachine;
 use census::{self, CensusList};
-use common::gossip_file::GossipFileList;
+use common::gossip_file::{GossipFileList, GOSSIP_TOML};
 use package::{self, Package, PackageUpdaterActor};
 use util::signals::SignalNotifier;
 use error::{Result, SupError};
 use config::Config;
-use service_config::ServiceConfig;
+use service_config::{ConfigChanges, ServiceConfig};
 use sidecar;
 use supervisor::Supervisor;
 use gossip;
@@ -131,6 +132,8 @@ impl<'a> Worker<'a> {
         let package_name = package.name.clone();
         let package_exposes = package.exposes().clone();
         let package_port = package_exposes.first().map(|e| e.clone());
+        let package_exports: Vec<String> =
+            try!(package.exports()).into_iter().map(|(name, _)| name).collect();
         let package_ident = package.ident().clone();
         let pkg_lock = Arc::new(RwLock::new(package));
         let pkg_lock_1 = pkg_lock.clone();
@@ -154,8 +157,18 @@ impl<'a> Worker<'a> {
                                                         config.group().to_string(),
                                                         config.organization().clone(),
                                                         Some(package_exposes),
-                                                        package_port);
+                                                        package_port,
+                                                        config.zone().clone(),
+                                                        config.gossip_detector().clone());
 
+        if !package_exports.is_empty() {
+            let mut cl = gossip_server.census_list.write().unwrap();
+            cl.me_mut().exports(Some(package_exports));
+        }
+        if !config.config_signers().is_empty() {
+            let mut gfl = gossip_server.gossip_file_list.write().unwrap();
+            gfl.set_allowed_signers(config.config_signers().to_vec());
+        }
         try!(gossip_server.start_inbound());
         try!(gossip_server.initial_peers(config.gossip_peer()));
         gossip_server.start_outbound();
@@ -216,6 +229,11 @@ impl<'a> Worker<'a> {
     /// update a package, but does NOT restart the service
     pub fn update_package(&self, updated: Package) -> Result<()> {
         let service_config = self.service_config.read().unwrap();
+        {
+            // The restart which follows records the new release as the one the service runs
+            let mut supervisor = self.supervisor.write().unwrap();
+            supervisor.package_ident = updated.ident().clone();
+        }
         {
             let mut package = self.package.write().unwrap();
             mem::replace(package.deref_mut(), updated);
@@ -287,6 +305,8 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
         }
 
         let mut restart_process = false;
+        // The exported config values to publish in our census entry, if we wrote new config
+        let mut exported_cfg = None;
 
         // This section, and the following really need to be refactored:
         //
@@ -307,8 +327,9 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
                 if !in_event {
                     let mut service_config = worker.service_config.write().unwrap();
                     let cl = worker.census_list.read().unwrap();
+                    let package = worker.package.read().unwrap();
                     service_config.svc(&cl);
-                    service_config.bind(worker.config.bind(), &cl);
+                    service_config.bind(&package, worker.config.bind(), &cl);
                 }
                 if write_rumor {
                     outputln!("Writing our census rumor: {:#?}", me_clone);
@@ -329,11 +350,15 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
                     if service_config.needs_write {
                         let package = worker.package.read().unwrap();
                         // Write the configuration, and restart if needed
-                        if try!(service_config.write(&package)) {
-                            try!(package.copy_run(&service_config));
-                            try!(package.reconfigure(&service_config));
-                            outputln!("Restarting because the service config was updated via the \
-                                       census");
+                        let changes = try!(service_config.write(&package));
+                        exported_cfg = Some(try!(export_cfg(&package, &service_config)));
+                        if changes.needs_action() &&
+                           try!(apply_config_changes(&package,
+                                                     &service_config,
+                                                     &changes,
+                                                     false)) {
+                            outputln!("Restarting because the service config was updated via \
+                                       the census");
                             restart_process = true;
                         }
                     }
@@ -350,6 +375,23 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
             let (needs_file_updated, needs_reconfigure) = if in_event {
                 (false, false)
             } else {
+                // Check gossiped config against the package's schema before it is persisted, so
+                // a bad rumor can't leave a gossip.toml the service won't start with.
+                let pending = worker.gossip_file_list.read().unwrap().pending(GOSSIP_TOML);
+                if let Some((checksum, body)) = pending {
+                    let valid = {
+                        let service_config = worker.service_config.read().unwrap();
+                        let package = worker.package.read().unwrap();
+                        match body.map(|b| String::from_utf8_lossy(&b).into_owned()) {
+                            Ok(body) => service_config.validate_gossip(&package, &body),
+                            Err(e) => Err(SupError::from(e)),
+                        }
+                    };
+                    if let Err(e) = valid {
+                        worker.service_config.write().unwrap().reject_gossip(&e);
+                        worker.gossip_file_list.write().unwrap().reject(GOSSIP_TOML, &checksum);
+                    }
+                }
                 let mut gossip_file_list = worker.gossip_file_list.write().unwrap();
                 let needs_write = {
                     gossip_file_list.needs_write()
@@ -371,17 +413,25 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
             if needs_reconfigure {
                 let mut service_config = worker.service_config.write().unwrap();
                 let package = worker.package.read().unwrap();
+                match worker.gossip_file_list.read().unwrap().secrets() {
+                    Ok(secrets) => service_config.secret(secrets),
+                    Err(e) => outputln!("Failed to decrypt secrets, keeping the old ones: {}", e),
+                }
                 service_config.cfg(&package);
-                if try!(service_config.write(&package)) {
-                    try!(package.copy_run(&service_config));
-                    let existed = try!(package.reconfigure(&service_config));
-                    if !existed {
-                        restart_process = true;
-                    }
+                let changes = try!(service_config.write(&package));
+                exported_cfg = Some(try!(export_cfg(&package, &service_config)));
+                if changes.needs_action() &&
+                   try!(apply_config_changes(&package, &service_config, &changes, true)) {
+                    restart_process = true;
                 }
             }
         }
 
+        if let Some(Some(cfg)) = exported_cfg {
+            let mut cl = worker.census_list.write().unwrap();
+            cl.me_mut().cfg(Some(cfg));
+        }
+
         if let Some(ref updater) = worker.pkg_updater {
             match updater.receiver.try_recv() {
                 Ok(wonder::actor::Message::Cast(package::UpdaterMessage::Update(package))) => {
@@ -435,3 +485,35 @@ fn run_internal<'a>(sm: &mut StateMachine<State, Worker<'a>, SupError>,
     }
     Ok(())
 }
+
+/// The config values the package exports, to publish in our census entry. `None` if the package
+/// exports nothing.
+fn export_cfg(package: &Package,
+              service_config: &ServiceConfig)
+              -> Result<Option<BTreeMap<String, String>>> {
+    let exports = try!(package.exports());
+    if exports.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(service_config.exported_cfg(&exports)))
+}
+
+/// React to rewritten config files the way the package declared: the reconfigure hook gets the
+/// files declared `reconfigure` and those without a declaration. Returns true if the service has
+/// to be restarted, because a `restart` file changed or an undeclared one did. Only a `gossiped`
+/// change to an undeclared file may be handled by the reconfigure hook instead of a restart.
+fn apply_config_changes(package: &Package,
+                        service_config: &ServiceConfig,
+                        changes: &ConfigChanges,
+                        gossiped: bool)
+                        -> Result<bool> {
+    try!(package.copy_run(service_config));
+    let to_reconfigure = changes.to_reconfigure();
+    let reconfigured = if to_reconfigure.is_empty() {
+        false
+    } else {
+        try!(package.reconfigure(service_config, &to_reconfigure))
+    };
+    let unspecified_restart = !changes.unspecified.is_empty() && !(gossiped && reconfigured);
+    Ok(!changes.restart.is_empty() || unspecified_restart)
+}
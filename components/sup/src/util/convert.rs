@@ -45,3 +45,26 @@ pub fn toml_table_to_json(toml: BTreeMap<String, toml::Value>) -> Json {
     }
     Json::Object(hashmap)
 }
+
+/// Translates json back to toml. TOML has no null, so nulls are dropped from tables and arrays,
+/// and a bare null becomes an empty string.
+pub fn json_to_toml(json: &Json) -> toml::Value {
+    match *json {
+        Json::I64(i) => toml::Value::Integer(i),
+        Json::U64(u) => toml::Value::Integer(u as i64),
+        Json::F64(f) => toml::Value::Float(f),
+        Json::String(ref s) => toml::Value::String(s.clone()),
+        Json::Boolean(b) => toml::Value::Boolean(b),
+        Json::Array(ref a) => {
+            toml::Value::Array(a.iter().filter(|v| !v.is_null()).map(json_to_toml).collect())
+        }
+        Json::Object(ref o) => {
+            let mut table = toml::Table::new();
+            for (key, value) in o.iter().filter(|&(_, v)| !v.is_null()) {
+                table.insert(key.clone(), json_to_toml(value));
+            }
+            toml::Value::Table(table)
+        }
+        Json::Null => toml::Value::String(String::new()),
+    }
+}